@@ -0,0 +1,113 @@
+//! Benchmarks of the read paths against byteorder and plain unaligned pointer reads.
+//!
+//! Run with `cargo bench`; `cargo bench -- scalar` selects a single group.
+
+#[macro_use]
+extern crate criterion;
+extern crate byteorder;
+extern crate endianness;
+
+use std::hint::black_box;
+use std::ptr;
+
+use byteorder::{BigEndian, ByteOrder as _, LittleEndian};
+use criterion::{Criterion, Throughput};
+use endianness::{ByteCursor, ByteOrder, read_u32, read_u32_into, read_u64, read_u64_into};
+
+const LEN: usize = 64 * 1024;
+
+fn data() -> Vec<u8> {
+    (0..LEN).map(|i| (i * 37) as u8).collect()
+}
+
+// Sums every value in the buffer so that each read is used.
+fn scalar(c: &mut Criterion) {
+    let data = data();
+    let mut group = c.benchmark_group("scalar");
+    group.throughput(Throughput::Bytes(LEN as u64));
+
+    group.bench_function("endianness_u32_be", |b| b.iter(|| {
+        let data = black_box(&data[..]);
+        (0..LEN / 4).fold(0u32, |sum, i| {
+            sum.wrapping_add(read_u32(&data[i * 4..], ByteOrder::BigEndian).unwrap())
+        })
+    }));
+    group.bench_function("byteorder_u32_be", |b| b.iter(|| {
+        let data = black_box(&data[..]);
+        (0..LEN / 4).fold(0u32, |sum, i| sum.wrapping_add(BigEndian::read_u32(&data[i * 4..])))
+    }));
+    group.bench_function("pointer_u32_be", |b| b.iter(|| {
+        let data = black_box(&data[..]);
+        (0..LEN / 4).fold(0u32, |sum, i| {
+            // Safety: `i * 4 + 4 <= LEN`.
+            let n = unsafe { ptr::read_unaligned(data.as_ptr().add(i * 4) as *const u32) };
+            sum.wrapping_add(u32::from_be(n))
+        })
+    }));
+
+    group.bench_function("endianness_u64_le", |b| b.iter(|| {
+        let data = black_box(&data[..]);
+        (0..LEN / 8).fold(0u64, |sum, i| {
+            sum.wrapping_add(read_u64(&data[i * 8..], ByteOrder::LittleEndian).unwrap())
+        })
+    }));
+    group.bench_function("byteorder_u64_le", |b| b.iter(|| {
+        let data = black_box(&data[..]);
+        (0..LEN / 8).fold(0u64, |sum, i| sum.wrapping_add(LittleEndian::read_u64(&data[i * 8..])))
+    }));
+    group.bench_function("pointer_u64_le", |b| b.iter(|| {
+        let data = black_box(&data[..]);
+        (0..LEN / 8).fold(0u64, |sum, i| {
+            // Safety: `i * 8 + 8 <= LEN`.
+            let n = unsafe { ptr::read_unaligned(data.as_ptr().add(i * 8) as *const u64) };
+            sum.wrapping_add(u64::from_le(n))
+        })
+    }));
+    group.finish();
+}
+
+fn bulk(c: &mut Criterion) {
+    let data = data();
+    let mut dst32 = vec![0u32; LEN / 4];
+    let mut dst64 = vec![0u64; LEN / 8];
+    let mut group = c.benchmark_group("bulk");
+    group.throughput(Throughput::Bytes(LEN as u64));
+
+    group.bench_function("endianness_u32_be", |b| b.iter(|| {
+        read_u32_into(black_box(&data), &mut dst32, ByteOrder::BigEndian).unwrap()
+    }));
+    group.bench_function("byteorder_u32_be", |b| b.iter(|| {
+        BigEndian::read_u32_into(black_box(&data), &mut dst32)
+    }));
+    group.bench_function("endianness_u64_be", |b| b.iter(|| {
+        read_u64_into(black_box(&data), &mut dst64, ByteOrder::BigEndian).unwrap()
+    }));
+    group.bench_function("byteorder_u64_be", |b| b.iter(|| {
+        BigEndian::read_u64_into(black_box(&data), &mut dst64)
+    }));
+    group.finish();
+}
+
+// A mixed record layout, as in a typical binary header.
+fn cursor(c: &mut Criterion) {
+    let data = data();
+    let mut group = c.benchmark_group("cursor");
+    group.throughput(Throughput::Bytes(LEN as u64));
+
+    group.bench_function("records", |b| b.iter(|| {
+        let mut cursor = ByteCursor::new(black_box(&data), ByteOrder::LittleEndian);
+        let mut sum = 0u64;
+        while cursor.remaining() >= 16 {
+            sum = sum.wrapping_add(u64::from(cursor.read_u16().unwrap()));
+            sum = sum.wrapping_add(u64::from(cursor.read_u8().unwrap()));
+            sum = sum.wrapping_add(u64::from(cursor.read_u8().unwrap()));
+            sum = sum.wrapping_add(u64::from(cursor.read_u32().unwrap()));
+            sum = sum.wrapping_add(cursor.read_u64().unwrap());
+        }
+        sum
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, scalar, bulk, cursor);
+criterion_main!(benches);
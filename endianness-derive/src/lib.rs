@@ -0,0 +1,142 @@
+//! Derive macros for the `endianness` crate.
+//!
+//! The macros are re-exported by `endianness` when its `derive` feature is enabled,
+//! so they are normally used as `#[derive(endianness::EndianRead)]`.
+
+#![crate_name = "endianness_derive"]
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DeriveInput, Fields, Index, Member};
+
+/// Derives `EndianRead` for a struct whose fields all implement `EndianRead`.
+///
+/// The fields are read one after another in declaration order, without padding.
+/// `SIZE` is the sum of the sizes of the fields.
+#[proc_macro_derive(EndianRead)]
+pub fn derive_endian_read(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_read(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Derives `EndianWrite` for a struct whose fields all implement `EndianWrite`.
+///
+/// The fields are written one after another in declaration order, without padding, so
+/// a struct that also derives `EndianRead` is written back byte for byte. Nothing is
+/// written if the stream is too short for the whole struct.
+#[proc_macro_derive(EndianWrite)]
+pub fn derive_endian_write(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_write(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Derives `SwapBytes` for a struct whose fields all implement `SwapBytes`.
+///
+/// The generated `swap_bytes` reverses the byte order of every field in place.
+#[proc_macro_derive(SwapBytes)]
+pub fn derive_swap_bytes(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_swap(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn struct_fields(input: &DeriveInput) -> syn::Result<&Fields> {
+    match input.data {
+        Data::Struct(ref data) => Ok(&data.fields),
+        _ => Err(syn::Error::new_spanned(&input.ident, "only structs are supported")),
+    }
+}
+
+fn members(fields: &Fields) -> Vec<Member> {
+    fields.iter().enumerate().map(|(i, field)| {
+        match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        }
+    }).collect()
+}
+
+fn expand_read(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fields = struct_fields(input)?;
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let reads: Vec<_> = types.iter().map(|ty| quote! {
+        {
+            let value = <#ty as ::endianness::EndianRead>::read(&data[offset..], endianness)?;
+            offset += <#ty as ::endianness::EndianRead>::SIZE;
+            value
+        }
+    }).collect();
+    let value = match *fields {
+        Fields::Named(ref named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#name { #(#names: #reads),* })
+        }
+        Fields::Unnamed(_) => quote!(#name(#(#reads),*)),
+        Fields::Unit => quote!(#name),
+    };
+    Ok(quote! {
+        impl #impl_generics ::endianness::EndianRead for #name #ty_generics #where_clause {
+            const SIZE: usize = 0 #(+ <#types as ::endianness::EndianRead>::SIZE)*;
+
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn read(data: &[u8], endianness: ::endianness::ByteOrder)
+                    -> ::endianness::EndiannessResult<Self> {
+                let expected = <Self as ::endianness::EndianRead>::SIZE;
+                if data.len() < expected {
+                    return Err(::endianness::EndiannessError::ShortSlice { expected, found: data.len() });
+                }
+                let mut offset = 0;
+                Ok(#value)
+            }
+        }
+    })
+}
+
+fn expand_write(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let members = &members(struct_fields(input)?);
+    Ok(quote! {
+        impl #impl_generics ::endianness::EndianWrite for #name #ty_generics #where_clause {
+            fn encoded_len(&self) -> usize {
+                0 #(+ ::endianness::EndianWrite::encoded_len(&self.#members))*
+            }
+
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn write(&self, data: &mut [u8], endianness: ::endianness::ByteOrder)
+                     -> ::endianness::EndiannessResult<()> {
+                let expected = ::endianness::EndianWrite::encoded_len(self);
+                if data.len() < expected {
+                    return Err(::endianness::EndiannessError::ShortSlice { expected, found: data.len() });
+                }
+                let mut offset = 0;
+                #(
+                    ::endianness::EndianWrite::write(&self.#members, &mut data[offset..], endianness)?;
+                    offset += ::endianness::EndianWrite::encoded_len(&self.#members);
+                )*
+                Ok(())
+            }
+        }
+    })
+}
+
+fn expand_swap(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let members = members(struct_fields(input)?);
+    Ok(quote! {
+        impl #impl_generics ::endianness::SwapBytes for #name #ty_generics #where_clause {
+            fn swap_bytes(&mut self) {
+                #(::endianness::SwapBytes::swap_bytes(&mut self.#members);)*
+            }
+        }
+    })
+}
@@ -0,0 +1,89 @@
+extern crate endianness;
+
+use endianness::*;
+
+#[derive(Debug, PartialEq, EndianRead, EndianWrite, SwapBytes)]
+struct Header {
+    magic: u32,
+    version: u16,
+    flags: i8,
+    scale: f32,
+}
+
+#[derive(Debug, PartialEq, EndianRead, EndianWrite, SwapBytes)]
+struct Record(u8, Header, i64);
+
+#[derive(Debug, PartialEq, EndianRead, EndianWrite)]
+struct Marker;
+
+const BYTES: [u8; 20] = [0x7f, 0x45, 0x4c, 0x46, 0, 2, 0xfe, 194, 255, 0, 0,
+                         0, 0, 0, 0, 0, 0, 0, 0, 1];
+
+#[test]
+fn named_fields() {
+    assert_eq!(11, Header::SIZE);
+    let header = Header::read(&BYTES, ByteOrder::BigEndian).unwrap();
+    assert_eq!(Header { magic: 0x7f454c46, version: 2, flags: -2, scale: -127.5 }, header);
+    let header = Header::read(&BYTES, ByteOrder::LittleEndian).unwrap();
+    assert_eq!(0x464c457f, header.magic);
+    assert_eq!(0x0200, header.version);
+}
+
+#[test]
+fn nested_fields() {
+    assert_eq!(20, Record::SIZE);
+    let record: Record = read(&[&[9][..], &BYTES[..19]].concat(), ByteOrder::BigEndian).unwrap();
+    assert_eq!(9, record.0);
+    assert_eq!(0x7f454c46, record.1.magic);
+    assert_eq!(0, record.2);
+    assert_eq!(0, Marker::SIZE);
+    assert_eq!(Marker, Marker::read(&[], ByteOrder::BigEndian).unwrap());
+}
+
+#[test]
+fn short_slice() {
+    assert_eq!(EndiannessError::ShortSlice { expected: 11, found: 10 },
+               Header::read(&BYTES[..10], ByteOrder::BigEndian).unwrap_err());
+    assert_eq!(EndiannessError::ShortSlice { expected: 20, found: 19 },
+               Record::read(&BYTES[..19], ByteOrder::BigEndian).unwrap_err());
+}
+
+#[test]
+fn round_trip() {
+    for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+        let record = Record::read(&BYTES, order).unwrap();
+        assert_eq!(20, record.encoded_len());
+        let mut out = [0u8; 20];
+        record.write(&mut out, order).unwrap();
+        assert_eq!(BYTES, out);
+        assert_eq!(record, read(&out, order).unwrap());
+    }
+    let mut out = [0u8; 0];
+    write(&mut out, Marker, ByteOrder::BigEndian).unwrap();
+}
+
+#[test]
+fn short_write() {
+    let header = Header { magic: 1, version: 2, flags: 3, scale: 4.0 };
+    let mut out = [0u8; 10];
+    assert_eq!(EndiannessError::ShortSlice { expected: 11, found: 10 },
+               header.write(&mut out, ByteOrder::BigEndian).unwrap_err());
+    assert_eq!([0; 10], out);
+}
+
+#[derive(Debug, PartialEq, SwapBytes)]
+struct Samples {
+    channels: [i16; 3],
+    rate: u32,
+}
+
+#[test]
+fn swap_bytes() {
+    let mut record = Record::read(&BYTES, ByteOrder::BigEndian).unwrap();
+    record.swap_bytes();
+    assert_eq!(Record::read(&BYTES, ByteOrder::LittleEndian).unwrap(), record);
+
+    let mut samples = Samples { channels: [1, -2, 0x0102], rate: 48000 };
+    samples.swap_bytes();
+    assert_eq!(Samples { channels: [0x0100, -257, 0x0201], rate: 0x80bb0000 }, samples);
+}
@@ -0,0 +1,220 @@
+//! Integration with the `futures` `AsyncRead` trait.
+//!
+//! Slices implement both `std::io::Read` and `AsyncRead`, so the extension trait is not
+//! exported from the crate root: importing it next to `ReadEndian` would make the method
+//! names ambiguous.
+
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_io::AsyncRead;
+use std::io;
+
+use {ByteOrder, EndianRead, EndiannessError, EndiannessResult};
+
+/// Extends any `futures::io::AsyncRead` with methods that read numbers in a given byte order.
+///
+/// It is the asynchronous counterpart of `ReadEndian` and converts errors the same way:
+/// `stream.read_u32_endian(ByteOrder::BigEndian).await` resolves to an `EndiannessResult<u32>`.
+/// Requires the `async` feature.
+pub trait AsyncReadEndian: AsyncRead + Unpin {
+    /// Reads a value of any supported type.
+    fn read_endian<T: EndianRead>(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, T> {
+        ReadEndianFuture {
+            reader: self,
+            endianness,
+            buf: [0; 16],
+            heap: if T::SIZE > 16 { vec![0; T::SIZE] } else { Vec::new() },
+            filled: 0,
+            value: PhantomData,
+        }
+    }
+
+    /// Reads unsigned 8-bit integer.
+    fn read_u8_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, u8> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 8-bit integer.
+    fn read_i8_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, i8> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 16-bit integer.
+    fn read_u16_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, u16> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 16-bit integer.
+    fn read_i16_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, i16> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 32-bit integer.
+    fn read_u32_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, u32> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 32-bit integer.
+    fn read_i32_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, i32> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 64-bit integer.
+    fn read_u64_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, u64> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 64-bit integer.
+    fn read_i64_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, i64> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 128-bit integer.
+    fn read_u128_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, u128> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 128-bit integer.
+    fn read_i128_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, i128> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads a single-precision floating point number.
+    fn read_f32_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, f32> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads a double-precision floating point number.
+    fn read_f64_endian(&mut self, endianness: ByteOrder) -> ReadEndianFuture<'_, Self, f64> {
+        self.read_endian(endianness)
+    }
+}
+
+impl<R: AsyncRead + Unpin + ?Sized> AsyncReadEndian for R {}
+
+/// The future returned by the `AsyncReadEndian` methods.
+///
+/// Bytes read before the future is dropped are lost.
+#[must_use = "futures do nothing unless polled"]
+pub struct ReadEndianFuture<'a, R: ?Sized, T> {
+    reader: &'a mut R,
+    endianness: ByteOrder,
+    buf: [u8; 16],
+    // Only used for types larger than `buf`.
+    heap: Vec<u8>,
+    filled: usize,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'a, R: ?Sized, T> fmt::Debug for ReadEndianFuture<'a, R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadEndianFuture")
+            .field("endianness", &self.endianness)
+            .field("filled", &self.filled)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, R: AsyncRead + Unpin + ?Sized, T: EndianRead> Future for ReadEndianFuture<'a, R, T> {
+    type Output = EndiannessResult<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<EndiannessResult<T>> {
+        let this = self.get_mut();
+        let buf = if T::SIZE <= this.buf.len() { &mut this.buf[..T::SIZE] } else { &mut this.heap[..] };
+        while this.filled < buf.len() {
+            match Pin::new(&mut *this.reader).poll_read(cx, &mut buf[this.filled..]) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => {
+                    let err = EndiannessError::ShortSlice { expected: buf.len(), found: this.filled };
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Ready(Ok(n)) => this.filled += n,
+                Poll::Ready(Err(ref err)) if err.kind() == io::ErrorKind::Interrupted => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+            }
+        }
+        Poll::Ready(T::read(buf, this.endianness))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, Waker};
+    use futures_io::AsyncRead;
+    use std::io;
+
+    use {ByteOrder, EndiannessError};
+    use super::AsyncReadEndian;
+
+    // Returns one byte per read and is not ready every other time, so that every
+    // future has to resume from a partially filled buffer.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        ready: bool,
+        error: Option<io::ErrorKind>,
+    }
+
+    impl<'a> AsyncRead for Trickle<'a> {
+        fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            self.ready = !self.ready;
+            if !self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if self.data.is_empty() {
+                if let Some(kind) = self.error.take() {
+                    return Poll::Ready(Err(io::Error::new(kind, "trickle")));
+                }
+                return Poll::Ready(Ok(0));
+            }
+            buf[0] = self.data[0];
+            self.data = &self.data[1..];
+            Poll::Ready(Ok(1))
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    fn trickle(data: &[u8]) -> Trickle<'_> {
+        Trickle { data, ready: false, error: None }
+    }
+
+    #[test]
+    fn reads_consume_stream() {
+        let mut reader = trickle(&[0, 0, 0, 1, 194, 255, 0, 0, 255]);
+        assert_eq!(1, block_on(reader.read_u32_endian(ByteOrder::BigEndian)).unwrap());
+        assert_eq!(-127.5, block_on(reader.read_f32_endian(ByteOrder::BigEndian)).unwrap());
+        assert_eq!(-1, block_on(reader.read_i8_endian(ByteOrder::LittleEndian)).unwrap());
+        assert!(reader.data.is_empty());
+    }
+
+    #[test]
+    fn short_read() {
+        let mut reader = trickle(&[1, 2, 3]);
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 3 },
+                   block_on(reader.read_u64_endian(ByteOrder::LittleEndian)).unwrap_err());
+    }
+
+    #[test]
+    fn io_errors() {
+        let mut reader = Trickle { data: &[1], ready: false, error: Some(io::ErrorKind::Interrupted) };
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   block_on(reader.read_u16_endian(ByteOrder::BigEndian)).unwrap_err());
+        let mut reader = Trickle { data: &[1], ready: false, error: Some(io::ErrorKind::BrokenPipe) };
+        assert_eq!(EndiannessError::Io(io::ErrorKind::BrokenPipe),
+                   block_on(reader.read_u16_endian(ByteOrder::BigEndian)).unwrap_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! Packed binary-coded decimal numbers.
+//!
+//! Every byte holds two decimal digits, the more significant one in the high nibble.
+//! The byte order decides the order of the bytes: `BigEndian` puts the most significant
+//! digits first, as in COBOL and most financial formats, and `LittleEndian` puts them
+//! last, as in M-Bus smart meters.
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+
+/// Reads an unsigned packed BCD number of `nbytes` bytes, that is `2 * nbytes` digits.
+///
+/// Returns `InvalidDigit` if a nibble is greater than 9, `Overflow` if the number does
+/// not fit into `u64`, and `InvalidWidth` if `nbytes` is zero.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(12345678, read_bcd(&[0x12, 0x34, 0x56, 0x78], 4, ByteOrder::BigEndian).unwrap());
+/// assert_eq!(12345678, read_bcd(&[0x78, 0x56, 0x34, 0x12], 4, ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_bcd(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<u64> {
+    if nbytes == 0 {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    if data.len() < nbytes {
+        return Err(EndiannessError::ShortSlice { expected: nbytes, found: data.len() });
+    }
+    let mut value = 0u64;
+    for i in 0..nbytes {
+        let byte = match endianness {
+            ByteOrder::BigEndian => data[i],
+            ByteOrder::LittleEndian => data[nbytes - 1 - i],
+        };
+        for &digit in &[byte >> 4, byte & 0x0f] {
+            if digit > 9 {
+                return Err(EndiannessError::InvalidDigit);
+            }
+            value = value.checked_mul(10)
+                .and_then(|value| value.checked_add(u64::from(digit)))
+                .ok_or(EndiannessError::Overflow)?;
+        }
+    }
+    Ok(value)
+}
+
+/// Writes an unsigned packed BCD number of `nbytes` bytes, padded with leading zeros.
+///
+/// Returns `Overflow` if the value has more than `2 * nbytes` digits and `InvalidWidth`
+/// if `nbytes` is zero.
+pub fn write_bcd(data: &mut [u8], value: u64, nbytes: usize, endianness: ByteOrder)
+                 -> EndiannessResult<()> {
+    if nbytes == 0 {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    if data.len() < nbytes {
+        return Err(EndiannessError::ShortSlice { expected: nbytes, found: data.len() });
+    }
+    // Digits fit if the value is below 10^(2 * nbytes); larger widths always fit u64.
+    if nbytes < 10 && value >= 100u64.pow(nbytes as u32) {
+        return Err(EndiannessError::Overflow);
+    }
+    let mut rest = value;
+    for i in 0..nbytes {
+        let byte = (((rest % 100 / 10) << 4) | (rest % 10)) as u8;
+        rest /= 100;
+        match endianness {
+            ByteOrder::BigEndian => data[nbytes - 1 - i] = byte,
+            ByteOrder::LittleEndian => data[i] = byte,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError};
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(0, read_bcd(&[0x00], 1, ByteOrder::BigEndian).unwrap());
+        assert_eq!(99, read_bcd(&[0x99, 0xff], 1, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(2024, read_bcd(&[0x20, 0x24], 2, ByteOrder::BigEndian).unwrap());
+        assert_eq!(2420, read_bcd(&[0x20, 0x24], 2, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(u64::MAX,
+                   read_bcd(&[0x00, 0x18, 0x44, 0x67, 0x44, 0x07, 0x37, 0x09, 0x55, 0x16, 0x15],
+                            11, ByteOrder::BigEndian).unwrap());
+    }
+
+    #[test]
+    fn round_trip() {
+        for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            for &(value, nbytes) in &[(0, 1), (7, 1), (99, 1), (1234, 3), (9999_9999, 4),
+                                      (u64::MAX, 10), (42, 12)] {
+                let mut v = [0xffu8; 12];
+                write_bcd(&mut v, value, nbytes, order).unwrap();
+                assert_eq!(value, read_bcd(&v, nbytes, order).unwrap());
+                assert_eq!(&[0xff; 12][nbytes..], &v[nbytes..]);
+            }
+        }
+        let mut v = [0u8; 3];
+        write_bcd(&mut v, 1234, 3, ByteOrder::BigEndian).unwrap();
+        assert_eq!([0x00, 0x12, 0x34], v);
+        write_bcd(&mut v, 1234, 3, ByteOrder::LittleEndian).unwrap();
+        assert_eq!([0x34, 0x12, 0x00], v);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(EndiannessError::InvalidDigit, read_bcd(&[0x1a], 1, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidDigit, read_bcd(&[0x12, 0xf0], 2, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, read_bcd(&[0x99; 10], 10, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   read_bcd(&[0x12], 2, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth, read_bcd(&[0x12], 0, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, write_bcd(&mut [0; 2], 10000, 2, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   write_bcd(&mut [0; 1], 1, 2, ByteOrder::BigEndian).unwrap_err());
+    }
+}
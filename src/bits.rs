@@ -0,0 +1,433 @@
+//! Reading and writing fields that are not aligned to whole bytes.
+//!
+//! The byte order decides how bits are numbered. In `BigEndian` order bits are taken from
+//! the most significant end of every byte and the first bit read is the most significant
+//! bit of the value, as in most packet headers and video codecs. In `LittleEndian` order
+//! bits are taken from the least significant end and the first bit read is the least
+//! significant bit of the value, as in DEFLATE. Either way, a byte-aligned 16-bit field
+//! reads the same as `read_u16` in that byte order.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+
+/// A reader of bit fields from a stream of bytes.
+///
+/// A failed read leaves the reader where it was.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // An IPv4 header starts with two 4-bit fields: the version and the header length.
+/// let mut bits = BitReader::new(&[0x45, 0x00], ByteOrder::BigEndian);
+/// assert_eq!(4, bits.read_bits(4).unwrap());
+/// assert_eq!(5, bits.read_bits(4).unwrap());
+/// assert_eq!(8, bits.position());
+/// ```
+// Not Copy for the same reason as `ByteCursor`.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    endianness: ByteOrder,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader at the first bit of the stream.
+    pub fn new(data: &'a [u8], endianness: ByteOrder) -> BitReader<'a> {
+        BitReader {
+            data,
+            position: 0,
+            endianness,
+        }
+    }
+
+    /// Returns the offset of the next bit to be read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of bits left to read.
+    pub fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.position
+    }
+
+    /// Returns true if the next bit is the first bit of a byte.
+    pub fn is_aligned(&self) -> bool {
+        self.position.is_multiple_of(8)
+    }
+
+    /// Returns the byte order that decides the bit numbering.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Reads `count` bits, at most 64, as an unsigned integer.
+    ///
+    /// Returns `InvalidWidth` if `count` is greater than 64.
+    pub fn read_bits(&mut self, count: usize) -> EndiannessResult<u64> {
+        if count > 64 {
+            return Err(EndiannessError::InvalidWidth);
+        }
+        if count > self.remaining() {
+            return Err(self.short_slice(count));
+        }
+        let mut value = 0u64;
+        let mut done = 0;
+        while done < count {
+            let byte = self.data[self.position / 8];
+            let offset = self.position % 8;
+            let take = if 8 - offset < count - done { 8 - offset } else { count - done };
+            let mask = ((1u16 << take) - 1) as u8;
+            match self.endianness {
+                ByteOrder::BigEndian => {
+                    let bits = (byte >> (8 - offset - take)) & mask;
+                    value = (value << take) | u64::from(bits);
+                }
+                ByteOrder::LittleEndian => {
+                    let bits = (byte >> offset) & mask;
+                    value |= u64::from(bits) << done;
+                }
+            }
+            done += take;
+            self.position += take;
+        }
+        Ok(value)
+    }
+
+    /// Reads `count` bits, at most 64, as a two's complement integer and sign-extends it.
+    pub fn read_signed_bits(&mut self, count: usize) -> EndiannessResult<i64> {
+        let value = self.read_bits(count)?;
+        if count == 0 {
+            Ok(0)
+        } else {
+            let shift = 64 - count;
+            Ok(((value << shift) as i64) >> shift)
+        }
+    }
+
+    /// Reads a single bit.
+    pub fn read_bit(&mut self) -> EndiannessResult<bool> {
+        Ok(self.read_bits(1)? == 1)
+    }
+
+    /// Skips `count` bits.
+    pub fn skip_bits(&mut self, count: usize) -> EndiannessResult<()> {
+        if count > self.remaining() {
+            Err(self.short_slice(count))
+        } else {
+            self.position += count;
+            Ok(())
+        }
+    }
+
+    /// Skips to the beginning of the next byte unless the reader is already there.
+    pub fn align_to_byte(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+
+    // The error for `count` more bits than are left, with the lengths in bytes.
+    fn short_slice(&self, count: usize) -> EndiannessError {
+        EndiannessError::ShortSlice {
+            expected: (self.position + count).div_ceil(8),
+            found: self.data.len(),
+        }
+    }
+}
+
+/// Buffers a `BitWriter` can write into.
+///
+/// It is implemented for mutable byte slices, which have a fixed size, and for
+/// `Vec<u8>` (with the `std` feature), which grows as needed.
+pub trait BitBuffer {
+    /// Makes sure the buffer holds at least `len` bytes and returns false if it cannot.
+    fn reserve_bytes(&mut self, len: usize) -> bool;
+
+    /// Returns the bytes of the buffer.
+    fn bytes_mut(&mut self) -> &mut [u8];
+}
+
+impl BitBuffer for &mut [u8] {
+    fn reserve_bytes(&mut self, len: usize) -> bool {
+        self.len() >= len
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl BitBuffer for Vec<u8> {
+    fn reserve_bytes(&mut self, len: usize) -> bool {
+        if self.len() < len {
+            self.resize(len, 0);
+        }
+        true
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+/// A writer of bit fields, the counterpart of `BitReader`.
+///
+/// Writing starts at the first bit of the buffer, and the bits of a partially written byte
+/// that have not been written yet are zero. A failed write leaves the writer and the
+/// buffer as they were.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut buf = [0xffu8; 2];
+/// let mut bits = BitWriter::new(&mut buf[..], ByteOrder::BigEndian);
+/// bits.write_bits(4, 4).unwrap();
+/// bits.write_bits(5, 4).unwrap();
+/// bits.write_bit(true).unwrap();
+/// assert_eq!(2, bits.finish());
+/// assert_eq!([0x45, 0x80], buf);
+/// ```
+#[derive(Debug)]
+pub struct BitWriter<B: BitBuffer> {
+    buffer: B,
+    position: usize,
+    endianness: ByteOrder,
+}
+
+impl<B: BitBuffer> BitWriter<B> {
+    /// Creates a writer at the first bit of the buffer.
+    pub fn new(buffer: B, endianness: ByteOrder) -> BitWriter<B> {
+        BitWriter {
+            buffer,
+            position: 0,
+            endianness,
+        }
+    }
+
+    /// Returns the offset of the next bit to be written.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns true if the next bit is the first bit of a byte.
+    pub fn is_aligned(&self) -> bool {
+        self.position.is_multiple_of(8)
+    }
+
+    /// Returns the byte order that decides the bit numbering.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Writes the lowest `count` bits of `value`, at most 64.
+    ///
+    /// Returns `Overflow` if the value does not fit into `count` bits and `InvalidWidth`
+    /// if `count` is greater than 64.
+    pub fn write_bits(&mut self, value: u64, count: usize) -> EndiannessResult<()> {
+        if count > 64 {
+            return Err(EndiannessError::InvalidWidth);
+        }
+        if count < 64 && value >> count != 0 {
+            return Err(EndiannessError::Overflow);
+        }
+        let expected = (self.position + count).div_ceil(8);
+        if !self.buffer.reserve_bytes(expected) {
+            let found = self.buffer.bytes_mut().len();
+            return Err(EndiannessError::ShortSlice { expected, found });
+        }
+        let data = self.buffer.bytes_mut();
+        let mut done = 0;
+        while done < count {
+            let offset = self.position % 8;
+            let take = if 8 - offset < count - done { 8 - offset } else { count - done };
+            let mask = ((1u16 << take) - 1) as u8;
+            let (bits, shift) = match self.endianness {
+                ByteOrder::BigEndian => ((value >> (count - done - take)) as u8 & mask, 8 - offset - take),
+                ByteOrder::LittleEndian => ((value >> done) as u8 & mask, offset),
+            };
+            // Bits after the written ones are cleared, so a partial byte never keeps old data.
+            let byte = &mut data[self.position / 8];
+            let keep = match self.endianness {
+                ByteOrder::BigEndian => !(0xffu8 >> offset),
+                ByteOrder::LittleEndian => ((1u16 << offset) - 1) as u8,
+            };
+            *byte = (*byte & keep) | (bits << shift);
+            done += take;
+            self.position += take;
+        }
+        Ok(())
+    }
+
+    /// Writes `value` as a two's complement integer of `count` bits, at most 64.
+    ///
+    /// Returns `Overflow` if the value does not fit into `count` bits.
+    pub fn write_signed_bits(&mut self, value: i64, count: usize) -> EndiannessResult<()> {
+        if count == 0 || count > 64 {
+            return self.write_bits(value as u64, count);
+        }
+        let shift = 64 - count;
+        if (value << shift) >> shift != value {
+            return Err(EndiannessError::Overflow);
+        }
+        self.write_bits((value as u64) << shift >> shift, count)
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> EndiannessResult<()> {
+        self.write_bits(u64::from(bit), 1)
+    }
+
+    /// Pads the current byte with zero bits unless the writer is already at a byte boundary.
+    pub fn align_to_byte(&mut self) -> EndiannessResult<()> {
+        let padding = (8 - self.position % 8) % 8;
+        self.write_bits(0, padding)
+    }
+
+    /// Pads the last byte with zero bits and returns the number of bytes written.
+    pub fn finish(mut self) -> usize {
+        // Padding never needs a new byte, so it cannot fail.
+        let _ = self.align_to_byte();
+        self.position / 8
+    }
+
+    /// Pads the last byte with zero bits and returns the buffer.
+    pub fn into_inner(mut self) -> B {
+        let _ = self.align_to_byte();
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {BitReader, BitWriter, ByteOrder, EndiannessError, read_u16, read_u32};
+
+    #[test]
+    fn msb_first() {
+        let v = [0b1011_0010, 0b0111_1111, 0x80];
+        let mut bits = BitReader::new(&v, ByteOrder::BigEndian);
+        assert_eq!(0b101, bits.read_bits(3).unwrap());
+        assert_eq!(0b1_0010_0111, bits.read_bits(9).unwrap());
+        assert!(bits.read_bit().unwrap());
+        assert_eq!(-1, bits.read_signed_bits(4).unwrap());
+        assert_eq!(0b000_0000, bits.read_bits(7).unwrap());
+        assert_eq!(0, bits.remaining());
+    }
+
+    #[test]
+    fn lsb_first() {
+        let v = [0b1011_0010, 0b0111_1111];
+        let mut bits = BitReader::new(&v, ByteOrder::LittleEndian);
+        assert_eq!(0b010, bits.read_bits(3).unwrap());
+        assert_eq!(0b1_1111_0110, bits.read_bits(9).unwrap());
+        assert_eq!(7, bits.read_signed_bits(4).unwrap());
+    }
+
+    #[test]
+    fn aligned_fields_match_byte_reads() {
+        let v = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc];
+        for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut bits = BitReader::new(&v, order);
+            assert_eq!(u64::from(read_u16(&v, order).unwrap()), bits.read_bits(16).unwrap());
+            assert_eq!(u64::from(read_u32(&v[2..], order).unwrap()), bits.read_bits(32).unwrap());
+            assert!(bits.is_aligned());
+        }
+        let v = [0xff; 9];
+        let mut bits = BitReader::new(&v, ByteOrder::BigEndian);
+        bits.skip_bits(3).unwrap();
+        assert_eq!(u64::MAX, bits.read_bits(64).unwrap());
+        assert_eq!(-1, bits.read_signed_bits(5).unwrap());
+    }
+
+    #[test]
+    fn alignment() {
+        let v = [0xff, 0x01];
+        let mut bits = BitReader::new(&v, ByteOrder::LittleEndian);
+        bits.align_to_byte();
+        assert_eq!(0, bits.position());
+        bits.read_bit().unwrap();
+        assert!(!bits.is_aligned());
+        bits.align_to_byte();
+        assert_eq!(8, bits.position());
+        assert!(bits.read_bit().unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let v = [0u8; 2];
+        let mut bits = BitReader::new(&v, ByteOrder::BigEndian);
+        bits.read_bits(5).unwrap();
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   bits.read_bits(12).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   bits.skip_bits(12).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth, bits.read_bits(65).unwrap_err());
+        assert_eq!(5, bits.position());
+        assert_eq!(0, bits.read_bits(0).unwrap());
+    }
+
+    #[test]
+    fn write_msb_first() {
+        let mut v = [0xffu8; 3];
+        {
+            let mut bits = BitWriter::new(&mut v[..], ByteOrder::BigEndian);
+            bits.write_bits(0b101, 3).unwrap();
+            bits.write_bits(0b1_0010_0111, 9).unwrap();
+            bits.write_signed_bits(-3, 4).unwrap();
+            bits.write_bit(true).unwrap();
+            assert_eq!(17, bits.position());
+            assert_eq!(3, bits.finish());
+        }
+        assert_eq!([0b1011_0010, 0b0111_1101, 0b1000_0000], v);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_lsb_first() {
+        let mut bits = BitWriter::new(Vec::new(), ByteOrder::LittleEndian);
+        bits.write_bits(0b010, 3).unwrap();
+        bits.write_bits(0b1_1111_0110, 9).unwrap();
+        bits.write_signed_bits(7, 4).unwrap();
+        bits.write_bits(1, 1).unwrap();
+        assert_eq!(vec![0b1011_0010, 0b0111_1111, 0b0000_0001], bits.into_inner());
+    }
+
+    #[test]
+    fn round_trip() {
+        let fields: [(u64, usize); 6] = [(1, 1), (0x1234, 13), (u64::MAX, 64), (0, 0), (5, 3), (0xab, 8)];
+        for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let mut v = [0u8; 13];
+            {
+                let mut writer = BitWriter::new(&mut v[..], order);
+                for &(value, count) in &fields {
+                    writer.write_bits(value, count).unwrap();
+                }
+                writer.write_signed_bits(-1000, 11).unwrap();
+                assert_eq!(13, writer.finish());
+            }
+            let mut reader = BitReader::new(&v, order);
+            for &(value, count) in &fields {
+                assert_eq!(value, reader.read_bits(count).unwrap());
+            }
+            assert_eq!(-1000, reader.read_signed_bits(11).unwrap());
+        }
+    }
+
+    #[test]
+    fn write_errors() {
+        let mut v = [0u8; 1];
+        let mut bits = BitWriter::new(&mut v[..], ByteOrder::BigEndian);
+        bits.write_bits(1, 3).unwrap();
+        assert_eq!(EndiannessError::Overflow, bits.write_bits(4, 2).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, bits.write_signed_bits(-5, 3).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, bits.write_signed_bits(4, 3).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth, bits.write_bits(0, 65).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   bits.write_bits(0, 6).unwrap_err());
+        assert_eq!(3, bits.position());
+        bits.align_to_byte().unwrap();
+        assert_eq!(1, bits.finish());
+    }
+}
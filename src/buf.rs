@@ -0,0 +1,258 @@
+//! Integration with the `Buf` and `BufMut` traits of the `bytes` crate.
+
+use bytes::{Buf, BufMut};
+
+use {ByteOrder, EndianRead, EndianWrite, EndiannessError, EndiannessResult};
+
+// Largest value read or written through a stack buffer.
+const MAX_SIZE: usize = 16;
+
+/// Extends any `bytes::Buf` with methods that read numbers in a given byte order.
+///
+/// Unlike the `get_*` methods of `Buf`, they return `ShortSlice` instead of panicking
+/// when the buffer runs out, and leave the buffer untouched in that case.
+/// Requires the `bytes` feature.
+///
+/// ```rust
+/// # extern crate bytes;
+/// # extern crate endianness;
+/// use bytes::Bytes;
+/// use endianness::*;
+///
+/// # fn main() {
+/// let mut buf = Bytes::from_static(&[0, 1, 2]);
+/// assert_eq!(1, buf.get_u16_endian(ByteOrder::BigEndian).unwrap());
+/// assert!(buf.get_u16_endian(ByteOrder::BigEndian).is_err());
+/// assert_eq!(1, buf.len());
+/// # }
+/// ```
+pub trait BufEndian: Buf {
+    /// Reads a value of any supported type.
+    ///
+    /// Values that are not contiguous in the buffer, such as in a `Chain`, must be at
+    /// most 16 bytes long; larger ones return `InvalidWidth`.
+    fn get_endian<T: EndianRead>(&mut self, endianness: ByteOrder) -> EndiannessResult<T> {
+        if self.remaining() < T::SIZE {
+            return Err(EndiannessError::ShortSlice { expected: T::SIZE, found: self.remaining() });
+        }
+        if self.chunk().len() >= T::SIZE {
+            let value = T::read(self.chunk(), endianness)?;
+            self.advance(T::SIZE);
+            Ok(value)
+        } else if T::SIZE <= MAX_SIZE {
+            let mut buf = [0u8; MAX_SIZE];
+            self.copy_to_slice(&mut buf[..T::SIZE]);
+            T::read(&buf, endianness)
+        } else {
+            Err(EndiannessError::InvalidWidth)
+        }
+    }
+
+    /// Reads unsigned 8-bit integer.
+    fn get_u8_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u8> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads signed 8-bit integer.
+    fn get_i8_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i8> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads unsigned 16-bit integer.
+    fn get_u16_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u16> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads signed 16-bit integer.
+    fn get_i16_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i16> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads unsigned 32-bit integer.
+    fn get_u32_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u32> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads signed 32-bit integer.
+    fn get_i32_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i32> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads unsigned 64-bit integer.
+    fn get_u64_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u64> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads signed 64-bit integer.
+    fn get_i64_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i64> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads unsigned 128-bit integer.
+    fn get_u128_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u128> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads signed 128-bit integer.
+    fn get_i128_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i128> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads a single-precision floating point number.
+    fn get_f32_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<f32> {
+        self.get_endian(endianness)
+    }
+
+    /// Reads a double-precision floating point number.
+    fn get_f64_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<f64> {
+        self.get_endian(endianness)
+    }
+}
+
+impl<B: Buf + ?Sized> BufEndian for B {}
+
+/// Extends any `bytes::BufMut` with methods that write numbers in a given byte order.
+///
+/// Unlike the `put_*` methods of `BufMut`, they return `ShortSlice` instead of panicking
+/// when the buffer cannot grow, and leave the buffer untouched in that case.
+/// Requires the `bytes` feature.
+///
+/// ```rust
+/// # extern crate bytes;
+/// # extern crate endianness;
+/// use bytes::BytesMut;
+/// use endianness::*;
+///
+/// # fn main() {
+/// let mut buf = BytesMut::new();
+/// buf.put_u16_endian(1, ByteOrder::LittleEndian).unwrap();
+/// buf.put_f32_endian(-127.5, ByteOrder::BigEndian).unwrap();
+/// assert_eq!(&[1, 0, 194, 255, 0, 0], &buf[..]);
+/// # }
+/// ```
+pub trait BufMutEndian: BufMut {
+    /// Writes a value of any supported type of up to 16 bytes; larger types return
+    /// `InvalidWidth`.
+    fn put_endian<T: EndianWrite>(&mut self, value: T, endianness: ByteOrder) -> EndiannessResult<()> {
+        let len = value.encoded_len();
+        if len > MAX_SIZE {
+            return Err(EndiannessError::InvalidWidth);
+        }
+        if self.remaining_mut() < len {
+            return Err(EndiannessError::ShortSlice { expected: len, found: self.remaining_mut() });
+        }
+        let mut buf = [0u8; MAX_SIZE];
+        value.write(&mut buf, endianness)?;
+        self.put_slice(&buf[..len]);
+        Ok(())
+    }
+
+    /// Writes unsigned 8-bit integer.
+    fn put_u8_endian(&mut self, value: u8, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes signed 8-bit integer.
+    fn put_i8_endian(&mut self, value: i8, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes unsigned 16-bit integer.
+    fn put_u16_endian(&mut self, value: u16, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes signed 16-bit integer.
+    fn put_i16_endian(&mut self, value: i16, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes unsigned 32-bit integer.
+    fn put_u32_endian(&mut self, value: u32, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes signed 32-bit integer.
+    fn put_i32_endian(&mut self, value: i32, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes unsigned 64-bit integer.
+    fn put_u64_endian(&mut self, value: u64, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes signed 64-bit integer.
+    fn put_i64_endian(&mut self, value: i64, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes unsigned 128-bit integer.
+    fn put_u128_endian(&mut self, value: u128, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes signed 128-bit integer.
+    fn put_i128_endian(&mut self, value: i128, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes a single-precision floating point number.
+    fn put_f32_endian(&mut self, value: f32, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+
+    /// Writes a double-precision floating point number.
+    fn put_f64_endian(&mut self, value: f64, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.put_endian(value, endianness)
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMutEndian for B {}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Buf, Bytes, BytesMut};
+
+    use {BufEndian, BufMutEndian, ByteOrder, EndiannessError};
+
+    #[test]
+    fn get_consumes_buffer() {
+        let mut buf = Bytes::from_static(&[0, 0, 0, 1, 194, 255, 0, 0, 255]);
+        assert_eq!(1, buf.get_u32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-127.5, buf.get_f32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-1, buf.get_i8_endian(ByteOrder::LittleEndian).unwrap());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn get_across_chunks() {
+        let mut buf = (&[1u8, 2][..]).chain(&[3u8, 4, 5][..]);
+        assert_eq!(0x04030201, buf.get_u32_endian(ByteOrder::LittleEndian).unwrap());
+        assert_eq!(1, buf.remaining());
+    }
+
+    #[test]
+    fn get_underflow() {
+        let mut buf: &[u8] = &[1, 2, 3];
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 3 },
+                   buf.get_u64_endian(ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(3, buf.len());
+    }
+
+    #[test]
+    fn put() {
+        let mut buf = BytesMut::new();
+        buf.put_i16_endian(-2, ByteOrder::BigEndian).unwrap();
+        buf.put_u128_endian(1, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(18, buf.len());
+        assert_eq!(&[255, 254, 1, 0], &buf[..4]);
+
+        let mut v = [0u8; 3];
+        let mut slice: &mut [u8] = &mut v;
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   slice.put_u32_endian(1, ByteOrder::BigEndian).unwrap_err());
+        slice.put_u16_endian(0x0102, ByteOrder::BigEndian).unwrap();
+        assert_eq!([1, 2, 0], v);
+    }
+}
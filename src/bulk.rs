@@ -0,0 +1,382 @@
+//! Conversion of whole slices of numbers at once.
+//!
+//! With the `simd` feature, byte swapping is done with SIMD shuffles where the CPU
+//! supports them (AVX2 or SSSE3 on x86, NEON on AArch64).
+
+use core::convert::TryInto;
+
+use {ByteOrder, EndianRead, EndiannessError, EndiannessResult};
+use simd;
+
+macro_rules! read_into {
+    ($($name:ident, $ty:ident, $size:expr, $desc:expr;)*) => (
+        $(
+            #[doc = concat!("Reads ", $desc, "s from a stream of bytes until `dst` is filled.")]
+            ///
+            /// The length of the stream is checked once, before anything is read, so the
+            /// conversion loop itself runs without per-element bounds checks.
+            pub fn $name(src: &[u8], dst: &mut [$ty], endianness: ByteOrder) -> EndiannessResult<()> {
+                let expected = dst.len() * $size;
+                if src.len() < expected {
+                    return Err(EndiannessError::ShortSlice { expected, found: src.len() });
+                }
+                let done = if !endianness.is_native() {
+                    simd::swap_into(src, dst)
+                } else {
+                    0
+                };
+                let pairs = src[done * $size..].chunks_exact($size).zip(dst[done..].iter_mut());
+                match endianness {
+                    ByteOrder::BigEndian => {
+                        for (chunk, n) in pairs {
+                            *n = $ty::from_be_bytes(chunk.try_into().unwrap());
+                        }
+                    }
+                    ByteOrder::LittleEndian => {
+                        for (chunk, n) in pairs {
+                            *n = $ty::from_le_bytes(chunk.try_into().unwrap());
+                        }
+                    }
+                }
+                Ok(())
+            }
+        )*
+    );
+}
+
+read_into! {
+    read_u16_into, u16, 2, "unsigned 16-bit integer";
+    read_i16_into, i16, 2, "signed 16-bit integer";
+    read_u32_into, u32, 4, "unsigned 32-bit integer";
+    read_i32_into, i32, 4, "signed 32-bit integer";
+    read_u64_into, u64, 8, "unsigned 64-bit integer";
+    read_i64_into, i64, 8, "signed 64-bit integer";
+    read_u128_into, u128, 16, "unsigned 128-bit integer";
+    read_i128_into, i128, 16, "signed 128-bit integer";
+    read_f32_into, f32, 4, "single-precision floating point number";
+    read_f64_into, f64, 8, "double-precision floating point number";
+}
+
+// Splits a byte into its two nibbles in stream order: low nibble first for
+// `LittleEndian`, high nibble first for `BigEndian`, like the bit order of `BitReader`.
+fn nibbles(byte: u8, endianness: ByteOrder) -> [u8; 2] {
+    match endianness {
+        ByteOrder::BigEndian => [byte >> 4, byte & 0x0f],
+        ByteOrder::LittleEndian => [byte & 0x0f, byte >> 4],
+    }
+}
+
+/// Unpacks unsigned 4-bit integers, two per byte, until `dst` is filled.
+///
+/// `endianness` selects the nibble order: `LittleEndian` takes the low nibble of each
+/// byte first and `BigEndian` the high one. If `dst` has an odd length, the second
+/// nibble of the last byte is ignored.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut dst = [0u8; 3];
+/// read_u4_into(&[0x21, 0x03], &mut dst, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!([1, 2, 3], dst);
+/// ```
+pub fn read_u4_into(src: &[u8], dst: &mut [u8], endianness: ByteOrder) -> EndiannessResult<()> {
+    let expected = dst.len().div_ceil(2);
+    if src.len() < expected {
+        return Err(EndiannessError::ShortSlice { expected, found: src.len() });
+    }
+    for (pair, &byte) in dst.chunks_mut(2).zip(src) {
+        let nibbles = nibbles(byte, endianness);
+        pair.copy_from_slice(&nibbles[..pair.len()]);
+    }
+    Ok(())
+}
+
+/// Unpacks signed two's complement 4-bit integers, two per byte, until `dst` is filled.
+///
+/// The values are sign-extended to the range -8..=7; nibble order and odd lengths are
+/// handled like in `read_u4_into`.
+pub fn read_i4_into(src: &[u8], dst: &mut [i8], endianness: ByteOrder) -> EndiannessResult<()> {
+    let expected = dst.len().div_ceil(2);
+    if src.len() < expected {
+        return Err(EndiannessError::ShortSlice { expected, found: src.len() });
+    }
+    for (pair, &byte) in dst.chunks_mut(2).zip(src) {
+        let nibbles = nibbles(byte, endianness);
+        for (n, &nibble) in pair.iter_mut().zip(&nibbles) {
+            *n = ((nibble << 4) as i8) >> 4;
+        }
+    }
+    Ok(())
+}
+
+/// Reads one field from each of a run of fixed-size records until `dst` is filled: the
+/// value at `offset` within record `i` goes to `dst[i]`.
+///
+/// The length of the stream is checked once, before anything is read. The last record
+/// only needs to extend to the end of its field. Returns `InvalidWidth` if the field does
+/// not fit within a record.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // 4-byte records: an 8-bit tag, padding, and a 16-bit value at offset 2.
+/// let src = [1, 0, 0, 10, 2, 0, 0, 20, 3, 0, 0, 30];
+/// let mut values = [0u16; 3];
+/// read_strided_into(&src, 4, 2, &mut values, ByteOrder::BigEndian).unwrap();
+/// assert_eq!([10, 20, 30], values);
+/// ```
+pub fn read_strided_into<T: EndianRead>(src: &[u8], record_size: usize, offset: usize, dst: &mut [T],
+                                        endianness: ByteOrder) -> EndiannessResult<()> {
+    let end = match offset.checked_add(T::SIZE) {
+        Some(end) if end <= record_size => end,
+        _ => return Err(EndiannessError::InvalidWidth),
+    };
+    if dst.is_empty() {
+        return Ok(());
+    }
+    // Saturates on overflow, which no slice can satisfy.
+    let expected = (dst.len() - 1).saturating_mul(record_size).saturating_add(end);
+    if src.len() < expected {
+        return Err(EndiannessError::ShortSlice { expected, found: src.len() });
+    }
+    for (i, n) in dst.iter_mut().enumerate() {
+        *n = T::read(&src[i * record_size + offset..], endianness)?;
+    }
+    Ok(())
+}
+
+macro_rules! swap_slice {
+    ($($name:ident, $ty:ident, $desc:expr;)*) => (
+        $(
+            #[doc = concat!("Reverses the byte order of every ", $desc, " in the slice in place.")]
+            pub fn $name(data: &mut [$ty]) {
+                let done = simd::swap_in_place(data);
+                for n in data[done..].iter_mut() {
+                    *n = n.swap_bytes();
+                }
+            }
+        )*
+    );
+}
+
+swap_slice! {
+    swap_slice_u16, u16, "unsigned 16-bit integer";
+    swap_slice_i16, i16, "signed 16-bit integer";
+    swap_slice_u32, u32, "unsigned 32-bit integer";
+    swap_slice_i32, i32, "signed 32-bit integer";
+    swap_slice_u64, u64, "unsigned 64-bit integer";
+    swap_slice_i64, i64, "signed 64-bit integer";
+    swap_slice_u128, u128, "unsigned 128-bit integer";
+    swap_slice_i128, i128, "signed 128-bit integer";
+}
+
+/// Reverses the byte order of every single-precision floating point number in the slice in place.
+pub fn swap_slice_f32(data: &mut [f32]) {
+    let done = simd::swap_in_place(data);
+    for n in data[done..].iter_mut() {
+        *n = f32::from_bits(n.to_bits().swap_bytes());
+    }
+}
+
+/// Reverses the byte order of every double-precision floating point number in the slice in place.
+pub fn swap_slice_f64(data: &mut [f64]) {
+    let done = simd::swap_in_place(data);
+    for n in data[done..].iter_mut() {
+        *n = f64::from_bits(n.to_bits().swap_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Bulk functions must agree with the scalar ones element by element.
+    macro_rules! bulk_correctness {
+        ($name:ident, $ty:ty, $size:expr, $read_into:ident, $read:ident) => (
+            mod $name {
+                use {ByteOrder, EndiannessError, $read_into, $read};
+
+                fn check(endianness: ByteOrder) {
+                    let src: Vec<u8> = (0..($size * 5 + 1) as u32).map(|i| (i * 37) as u8).collect();
+                    let mut dst: [$ty; 5] = Default::default();
+                    $read_into(&src, &mut dst, endianness).unwrap();
+                    for (i, n) in dst.iter().enumerate() {
+                        assert_eq!($read(&src[i * $size..], endianness).unwrap(), *n);
+                    }
+                }
+
+                #[test]
+                fn big_endian() {
+                    check(ByteOrder::BigEndian);
+                }
+
+                #[test]
+                fn little_endian() {
+                    check(ByteOrder::LittleEndian);
+                }
+
+                #[test]
+                fn short_slice() {
+                    let src = [0u8; $size * 3 - 1];
+                    let mut dst: [$ty; 3] = Default::default();
+                    assert_eq!(EndiannessError::ShortSlice { expected: $size * 3, found: $size * 3 - 1 },
+                               $read_into(&src, &mut dst, ByteOrder::BigEndian).unwrap_err());
+                    assert!($read_into(&src, &mut dst[..2], ByteOrder::BigEndian).is_ok());
+                }
+            }
+        );
+    }
+
+    bulk_correctness!(test_u16, u16, 2, read_u16_into, read_u16);
+    bulk_correctness!(test_i16, i16, 2, read_i16_into, read_i16);
+    bulk_correctness!(test_u32, u32, 4, read_u32_into, read_u32);
+    bulk_correctness!(test_i32, i32, 4, read_i32_into, read_i32);
+    bulk_correctness!(test_u64, u64, 8, read_u64_into, read_u64);
+    bulk_correctness!(test_i64, i64, 8, read_i64_into, read_i64);
+    bulk_correctness!(test_u128, u128, 16, read_u128_into, read_u128);
+    bulk_correctness!(test_i128, i128, 16, read_i128_into, read_i128);
+
+    // Random bytes may decode to NaN, so floats are compared by their bit patterns.
+    macro_rules! bulk_float_correctness {
+        ($name:ident, $ty:ty, $size:expr, $read_into:ident, $read:ident) => (
+            mod $name {
+                use {ByteOrder, EndiannessError, $read_into, $read};
+
+                fn check(endianness: ByteOrder) {
+                    let src: Vec<u8> = (0..($size * 64) as u32).map(|i| (i * 37) as u8).collect();
+                    let mut dst: [$ty; 64] = [0.0; 64];
+                    $read_into(&src, &mut dst, endianness).unwrap();
+                    for (i, n) in dst.iter().enumerate() {
+                        assert_eq!($read(&src[i * $size..], endianness).unwrap().to_bits(), n.to_bits());
+                    }
+                }
+
+                #[test]
+                fn big_endian() {
+                    check(ByteOrder::BigEndian);
+                }
+
+                #[test]
+                fn little_endian() {
+                    check(ByteOrder::LittleEndian);
+                }
+
+                #[test]
+                fn short_slice() {
+                    let src = [0u8; $size * 3 - 1];
+                    let mut dst: [$ty; 3] = [0.0; 3];
+                    assert_eq!(EndiannessError::ShortSlice { expected: $size * 3, found: $size * 3 - 1 },
+                               $read_into(&src, &mut dst, ByteOrder::LittleEndian).unwrap_err());
+                }
+            }
+        );
+    }
+
+    bulk_float_correctness!(test_f32, f32, 4, read_f32_into, read_f32);
+    bulk_float_correctness!(test_f64, f64, 8, read_f64_into, read_f64);
+
+    mod int4 {
+        use {ByteOrder, EndiannessError, read_i4_into, read_u4_into};
+
+        #[test]
+        fn unsigned() {
+            let src = [0x21, 0x43, 0xf5];
+            let mut dst = [0u8; 6];
+            read_u4_into(&src, &mut dst, ByteOrder::LittleEndian).unwrap();
+            assert_eq!([1, 2, 3, 4, 5, 15], dst);
+            read_u4_into(&src, &mut dst, ByteOrder::BigEndian).unwrap();
+            assert_eq!([2, 1, 4, 3, 15, 5], dst);
+        }
+
+        #[test]
+        fn signed() {
+            let src = [0x7f, 0x18, 0xe0];
+            let mut dst = [0i8; 5];
+            read_i4_into(&src, &mut dst, ByteOrder::LittleEndian).unwrap();
+            assert_eq!([-1, 7, -8, 1, 0], dst);
+            read_i4_into(&src, &mut dst, ByteOrder::BigEndian).unwrap();
+            assert_eq!([7, -1, 1, -8, -2], dst);
+        }
+
+        #[test]
+        fn short_slice() {
+            let mut dst = [0u8; 5];
+            assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                       read_u4_into(&[0; 2], &mut dst, ByteOrder::BigEndian).unwrap_err());
+            assert!(read_u4_into(&[0; 3], &mut dst, ByteOrder::BigEndian).is_ok());
+            assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                       read_i4_into(&[0; 1], &mut [0; 3], ByteOrder::LittleEndian).unwrap_err());
+        }
+    }
+
+    mod strided {
+        use {ByteOrder, EndiannessError, read_strided_into};
+
+        #[test]
+        fn fields() {
+            let src: Vec<u8> = (0..64).collect();
+            let mut dst = [0u64; 4];
+            read_strided_into(&src, 16, 8, &mut dst, ByteOrder::LittleEndian).unwrap();
+            assert_eq!([0x0f0e0d0c0b0a0908, 0x1f1e1d1c1b1a1918, 0x2f2e2d2c2b2a2928, 0x3f3e3d3c3b3a3938], dst);
+            let mut dst = [0i8; 3];
+            read_strided_into(&src, 1, 0, &mut dst, ByteOrder::BigEndian).unwrap();
+            assert_eq!([0, 1, 2], dst);
+        }
+
+        #[test]
+        fn errors() {
+            let src = [0u8; 9];
+            let mut dst = [0u32; 2];
+            assert_eq!(EndiannessError::ShortSlice { expected: 10, found: 9 },
+                       read_strided_into(&src, 6, 0, &mut dst, ByteOrder::BigEndian).unwrap_err());
+            assert!(read_strided_into(&src, 5, 0, &mut dst, ByteOrder::BigEndian).is_ok());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_strided_into(&src, 4, 1, &mut dst, ByteOrder::BigEndian).unwrap_err());
+            assert!(read_strided_into(&[], 8, 4, &mut [0u32; 0], ByteOrder::BigEndian).is_ok());
+            assert!(read_strided_into(&src, usize::MAX, 0, &mut dst, ByteOrder::BigEndian).is_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_strided_into(&src, usize::MAX, usize::MAX, &mut dst, ByteOrder::BigEndian).unwrap_err());
+        }
+    }
+
+    mod swap {
+        use {ByteOrder, read_u64_into, read_f32_into, read_f64_into};
+        use {swap_slice_u16, swap_slice_i32, swap_slice_u64, swap_slice_u128, swap_slice_f32,
+             swap_slice_f64};
+
+        #[test]
+        fn integers() {
+            let mut v = [0x0102u16, 0x0304];
+            swap_slice_u16(&mut v);
+            assert_eq!([0x0201, 0x0403], v);
+
+            let mut v = [0x01020304i32, -2];
+            swap_slice_i32(&mut v);
+            assert_eq!([0x04030201, -0x0100_0001], v);
+
+            let src: Vec<u8> = (0..32).collect();
+            let mut v = [0u64; 4];
+            read_u64_into(&src, &mut v, ByteOrder::BigEndian).unwrap();
+            swap_slice_u64(&mut v);
+            let mut expected = [0u64; 4];
+            read_u64_into(&src, &mut expected, ByteOrder::LittleEndian).unwrap();
+            assert_eq!(expected, v);
+
+            let mut v = [1u128];
+            swap_slice_u128(&mut v);
+            assert_eq!([1 << 120], v);
+        }
+
+        #[test]
+        fn floats() {
+            let src = [194, 255, 0, 0, 0, 0, 0, 0, 0, 0, 240, 63];
+            let mut v = [0f32; 3];
+            read_f32_into(&src, &mut v, ByteOrder::LittleEndian).unwrap();
+            swap_slice_f32(&mut v);
+            assert_eq!(-127.5, v[0]);
+
+            let mut v = [0f64; 1];
+            read_f64_into(&src[4..], &mut v, ByteOrder::BigEndian).unwrap();
+            swap_slice_f64(&mut v);
+            assert_eq!(1.0, v[0]);
+        }
+    }
+}
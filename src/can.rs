@@ -0,0 +1,177 @@
+//! Signals packed into CAN frames, as described by DBC files.
+//!
+//! A signal is given by its start bit, its length in bits, and its byte order. Bits of a
+//! frame are numbered from 0 (the least significant bit of the first byte) upwards.
+//!
+//! * `LittleEndian` is the Intel layout (`@1` in DBC files). The start bit is the least
+//!   significant bit of the signal, and the signal continues into the more significant
+//!   bits and the following bytes.
+//! * `BigEndian` is the Motorola layout (`@0` in DBC files). The start bit is the most
+//!   significant bit of the signal, and the signal continues into the less significant
+//!   bits of the same byte and then into the most significant bits of the next byte.
+//!
+//! Frames may be of any length, so CAN FD frames of up to 64 bytes are supported too.
+
+use {BitReader, ByteOrder, EndiannessError, EndiannessResult};
+
+// Converts the start bit into the offset of the first signal bit in the bit order used by
+// `BitReader`: LSB-first for Intel and MSB-first for Motorola signals.
+fn first_bit(start_bit: usize, endianness: ByteOrder) -> usize {
+    match endianness {
+        ByteOrder::LittleEndian => start_bit,
+        ByteOrder::BigEndian => start_bit / 8 * 8 + 7 - start_bit % 8,
+    }
+}
+
+fn check_signal(data: &[u8], start_bit: usize, length: usize, endianness: ByteOrder)
+                -> EndiannessResult<usize> {
+    if length == 0 || length > 64 {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    let first = first_bit(start_bit, endianness);
+    let expected = (first + length).div_ceil(8);
+    if expected > data.len() {
+        Err(EndiannessError::ShortSlice { expected, found: data.len() })
+    } else {
+        Ok(first)
+    }
+}
+
+/// Reads an unsigned signal of `length` bits, at most 64, from a CAN frame.
+///
+/// Returns `ShortSlice` if the signal does not fit into the frame.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let frame = [0x12, 0x34, 0, 0, 0, 0, 0, 0];
+/// // SG_ Speed : 7|16@0+ (Motorola)
+/// assert_eq!(0x1234, read_can_signal(&frame, 7, 16, ByteOrder::BigEndian).unwrap());
+/// // SG_ Speed : 0|16@1+ (Intel)
+/// assert_eq!(0x3412, read_can_signal(&frame, 0, 16, ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_can_signal(data: &[u8], start_bit: usize, length: usize, endianness: ByteOrder)
+                       -> EndiannessResult<u64> {
+    let first = check_signal(data, start_bit, length, endianness)?;
+    let mut bits = BitReader::new(data, endianness);
+    bits.skip_bits(first)?;
+    bits.read_bits(length)
+}
+
+/// Reads a signed (`-` in DBC files) signal of `length` bits from a CAN frame.
+pub fn read_can_signal_signed(data: &[u8], start_bit: usize, length: usize, endianness: ByteOrder)
+                              -> EndiannessResult<i64> {
+    let value = read_can_signal(data, start_bit, length, endianness)?;
+    let shift = 64 - length;
+    Ok(((value << shift) as i64) >> shift)
+}
+
+/// Writes an unsigned signal of `length` bits, at most 64, into a CAN frame.
+///
+/// The other bits of the frame are left as they are. Returns `Overflow` if the value
+/// does not fit into `length` bits.
+pub fn write_can_signal(data: &mut [u8], value: u64, start_bit: usize, length: usize,
+                        endianness: ByteOrder) -> EndiannessResult<()> {
+    let first = check_signal(data, start_bit, length, endianness)?;
+    if length < 64 && value >> length != 0 {
+        return Err(EndiannessError::Overflow);
+    }
+    for i in 0..length {
+        let bit = ((value >> i) & 1) as u8;
+        let (byte, shift) = match endianness {
+            ByteOrder::LittleEndian => ((first + i) / 8, (first + i) % 8),
+            ByteOrder::BigEndian => {
+                let k = first + length - 1 - i;
+                (k / 8, 7 - k % 8)
+            }
+        };
+        data[byte] = (data[byte] & !(1 << shift)) | (bit << shift);
+    }
+    Ok(())
+}
+
+/// Writes a signed signal of `length` bits into a CAN frame.
+///
+/// Returns `Overflow` if the value does not fit into `length` bits.
+pub fn write_can_signal_signed(data: &mut [u8], value: i64, start_bit: usize, length: usize,
+                               endianness: ByteOrder) -> EndiannessResult<()> {
+    if length == 0 || length > 64 {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    let shift = 64 - length;
+    if (value << shift) >> shift != value {
+        return Err(EndiannessError::Overflow);
+    }
+    write_can_signal(data, (value as u64) << shift >> shift, start_bit, length, endianness)
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError};
+    use super::*;
+
+    const FRAME: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+
+    #[test]
+    fn intel() {
+        let order = ByteOrder::LittleEndian;
+        assert_eq!(0x3412, read_can_signal(&FRAME, 0, 16, order).unwrap());
+        assert_eq!(0x1, read_can_signal(&FRAME, 4, 4, order).unwrap());
+        assert_eq!(0x412, read_can_signal(&FRAME, 0, 12, order).unwrap());
+        assert_eq!(0x563, read_can_signal(&FRAME, 12, 12, order).unwrap());
+        assert_eq!(0xf0debc9a78563412, read_can_signal(&FRAME, 0, 64, order).unwrap());
+        assert_eq!(-1, read_can_signal_signed(&FRAME, 60, 4, order).unwrap());
+    }
+
+    #[test]
+    fn motorola() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(0x1234, read_can_signal(&FRAME, 7, 16, order).unwrap());
+        assert_eq!(0x123, read_can_signal(&FRAME, 7, 12, order).unwrap());
+        assert_eq!(0x234, read_can_signal(&FRAME, 3, 12, order).unwrap());
+        assert_eq!(0x2, read_can_signal(&FRAME, 3, 4, order).unwrap());
+        assert_eq!(0x123456789abcdef0, read_can_signal(&FRAME, 7, 64, order).unwrap());
+        assert_eq!(-0x10, read_can_signal_signed(&FRAME, 63, 8, order).unwrap());
+        // The signal continues from the last bit of a byte into the first bit of the next.
+        assert_eq!(0b00, read_can_signal(&FRAME, 0, 2, order).unwrap());
+        assert_eq!(0b1010, read_can_signal(&FRAME, 33, 4, order).unwrap());
+    }
+
+    #[test]
+    fn write_keeps_other_signals() {
+        for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let full = if let ByteOrder::BigEndian = order { 7 } else { 0 };
+            for &(start, length) in &[(3, 12), (7, 16), (13, 7), (0, 1), (39, 23), (full, 64)] {
+                let mut frame = FRAME;
+                let value = read_can_signal(&frame, start, length, order).unwrap();
+                let flipped = !value & (u64::MAX >> (64 - length));
+                write_can_signal(&mut frame, flipped, start, length, order).unwrap();
+                assert_eq!(flipped, read_can_signal(&frame, start, length, order).unwrap());
+                let ones: u32 = frame.iter().zip(&FRAME).map(|(a, b)| (a ^ b).count_ones()).sum();
+                assert_eq!(length as u32, ones);
+            }
+        }
+        let mut frame = [0u8; 8];
+        write_can_signal_signed(&mut frame, -2, 3, 12, ByteOrder::BigEndian).unwrap();
+        assert_eq!([0x0f, 0xfe], frame[..2]);
+        assert_eq!(-2, read_can_signal_signed(&frame, 3, 12, ByteOrder::BigEndian).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let mut frame = [0u8; 2];
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   read_can_signal(&frame, 8, 9, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   read_can_signal(&frame, 8, 9, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth,
+                   read_can_signal(&frame, 7, 0, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth,
+                   read_can_signal_signed(&[0; 9], 0, 65, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::Overflow,
+                   write_can_signal(&mut frame, 16, 0, 4, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::Overflow,
+                   write_can_signal_signed(&mut frame, -9, 7, 4, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!([0, 0], frame);
+    }
+}
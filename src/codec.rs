@@ -0,0 +1,184 @@
+//! A tokio-util codec for messages framed by a length prefix.
+
+use bytes::{Buf, Bytes, BytesMut};
+use core::convert::TryFrom;
+use tokio_util::codec::{Decoder, Encoder};
+
+use {ByteOrder, EndiannessError, EndiannessResult, read_uint, write_uint};
+
+/// Splits a stream into messages that start with their length, stored as an unsigned
+/// integer of `width` bytes. The length counts the payload only, not the prefix.
+///
+/// Decoded frames are the payloads without the prefix. Requires the `codec` feature.
+///
+/// ```rust
+/// # extern crate bytes;
+/// # extern crate endianness;
+/// # extern crate tokio_util;
+/// use bytes::BytesMut;
+/// use endianness::*;
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// # fn main() {
+/// let mut codec = LengthPrefixCodec::new(2, ByteOrder::BigEndian).unwrap();
+/// let mut buf = BytesMut::new();
+/// codec.encode(&b"hello"[..], &mut buf).unwrap();
+/// assert_eq!(b"\x00\x05hello", &buf[..]);
+/// assert_eq!(b"hello", &codec.decode(&mut buf).unwrap().unwrap()[..]);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct LengthPrefixCodec {
+    width: usize,
+    endianness: ByteOrder,
+    max_length: usize,
+}
+
+impl LengthPrefixCodec {
+    /// The default limit on the payload length, 8 MiB.
+    pub const DEFAULT_MAX_LENGTH: usize = 8 * 1024 * 1024;
+
+    /// Creates a codec with a prefix of 1 to 8 bytes in the given byte order.
+    ///
+    /// Returns `InvalidWidth` for any other width.
+    pub fn new(width: usize, endianness: ByteOrder) -> EndiannessResult<LengthPrefixCodec> {
+        if width == 0 || width > 8 {
+            return Err(EndiannessError::InvalidWidth);
+        }
+        Ok(LengthPrefixCodec {
+            width,
+            endianness,
+            max_length: LengthPrefixCodec::DEFAULT_MAX_LENGTH,
+        })
+    }
+
+    /// Returns the width of the length prefix in bytes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the byte order of the length prefix.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Returns the longest payload the codec accepts.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Sets the longest payload the codec accepts. Longer messages fail with `Overflow`
+    /// before any of their payload is buffered.
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length;
+    }
+}
+
+impl Decoder for LengthPrefixCodec {
+    type Item = BytesMut;
+    type Error = EndiannessError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> EndiannessResult<Option<BytesMut>> {
+        if src.len() < self.width {
+            return Ok(None);
+        }
+        let length = read_uint(src, self.width, self.endianness)?;
+        let length = match usize::try_from(length) {
+            Ok(length) if length <= self.max_length => length,
+            _ => return Err(EndiannessError::Overflow),
+        };
+        let frame = self.width.checked_add(length).ok_or(EndiannessError::Overflow)?;
+        if src.len() < frame {
+            src.reserve(frame - src.len());
+            return Ok(None);
+        }
+        src.advance(self.width);
+        Ok(Some(src.split_to(length)))
+    }
+}
+
+impl<'a> Encoder<&'a [u8]> for LengthPrefixCodec {
+    type Error = EndiannessError;
+
+    fn encode(&mut self, item: &'a [u8], dst: &mut BytesMut) -> EndiannessResult<()> {
+        if item.len() > self.max_length {
+            return Err(EndiannessError::Overflow);
+        }
+        let mut prefix = [0u8; 8];
+        write_uint(&mut prefix, item.len() as u64, self.width, self.endianness)?;
+        dst.reserve(self.width + item.len());
+        dst.extend_from_slice(&prefix[..self.width]);
+        dst.extend_from_slice(item);
+        Ok(())
+    }
+}
+
+impl Encoder<Bytes> for LengthPrefixCodec {
+    type Error = EndiannessError;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> EndiannessResult<()> {
+        self.encode(&item[..], dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    use {ByteOrder, EndiannessError};
+    use super::LengthPrefixCodec;
+
+    #[test]
+    fn partial_frames() {
+        let mut codec = LengthPrefixCodec::new(4, ByteOrder::LittleEndian).unwrap();
+        let mut buf = BytesMut::new();
+        let stream = [3, 0, 0, 0, b'a', b'b', b'c', 0, 0, 0, 0, 1];
+        for &b in &stream[..6] {
+            assert_eq!(None, codec.decode(&mut buf).unwrap());
+            buf.extend_from_slice(&[b]);
+        }
+        assert_eq!(None, codec.decode(&mut buf).unwrap());
+        buf.extend_from_slice(&stream[6..]);
+        assert_eq!(&b"abc"[..], &codec.decode(&mut buf).unwrap().unwrap()[..]);
+        assert_eq!(&b""[..], &codec.decode(&mut buf).unwrap().unwrap()[..]);
+        assert_eq!(None, codec.decode(&mut buf).unwrap());
+        assert_eq!(&[1], &buf[..]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut codec = LengthPrefixCodec::new(3, ByteOrder::BigEndian).unwrap();
+        let mut buf = BytesMut::new();
+        codec.encode(Bytes::from_static(b"first"), &mut buf).unwrap();
+        codec.encode(&[0u8; 300][..], &mut buf).unwrap();
+        assert_eq!(&[0, 0, 5], &buf[..3]);
+        assert_eq!(&[0, 1, 44], &buf[8..11]);
+        assert_eq!(&b"first"[..], &codec.decode(&mut buf).unwrap().unwrap()[..]);
+        assert_eq!(300, codec.decode(&mut buf).unwrap().unwrap().len());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(EndiannessError::InvalidWidth,
+                   LengthPrefixCodec::new(0, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth,
+                   LengthPrefixCodec::new(9, ByteOrder::BigEndian).unwrap_err());
+
+        let mut codec = LengthPrefixCodec::new(1, ByteOrder::BigEndian).unwrap();
+        let mut buf = BytesMut::new();
+        assert_eq!(EndiannessError::Overflow, codec.encode(&[0u8; 256][..], &mut buf).unwrap_err());
+        codec.set_max_length(4);
+        assert_eq!(EndiannessError::Overflow, codec.encode(&[0u8; 5][..], &mut buf).unwrap_err());
+        assert!(buf.is_empty());
+        buf.extend_from_slice(&[5]);
+        assert_eq!(EndiannessError::Overflow, codec.decode(&mut buf).unwrap_err());
+
+        // Without a limit, the prefix and the length together still must fit in usize.
+        let mut codec = LengthPrefixCodec::new(8, ByteOrder::BigEndian).unwrap();
+        codec.set_max_length(usize::MAX);
+        let mut buf = BytesMut::from(&[0xff; 8][..]);
+        assert_eq!(EndiannessError::Overflow, codec.decode(&mut buf).unwrap_err());
+    }
+}
@@ -0,0 +1,812 @@
+//! A reader that keeps track of its position in a stream of bytes.
+
+use core::any;
+use core::cmp;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+use {ByteOrder, EndianRead, EndiannessError, Scalar, ScalarType, read_array, read_split};
+use {read_cstr, read_pascal_str};
+
+/// The error returned by `ByteCursor`: what failed, where, and while reading which type.
+///
+/// It converts into the underlying `EndiannessError`, so `?` works in functions that
+/// return `EndiannessResult`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0, 1, 0, 0, 0];
+/// let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+/// cursor.read_u16().unwrap();
+/// let err = cursor.read_u32().unwrap_err();
+/// assert_eq!(2, err.offset());
+/// assert_eq!("u32", err.type_name());
+/// assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 }, err.error());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CursorError {
+    offset: usize,
+    type_name: &'static str,
+    error: EndiannessError,
+}
+
+impl CursorError {
+    /// Returns the offset from the beginning of the stream at which the read started.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the name of the type that was being read, such as `u32`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the underlying error. Lengths in `ShortSlice` count from `offset`.
+    pub fn error(&self) -> EndiannessError {
+        self.error
+    }
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot read {} at offset {}: {}", self.type_name, self.offset, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for CursorError {
+    fn description(&self) -> &str {
+        "Cannot read a value from the cursor."
+    }
+    fn cause(&self) -> Option<&dyn error::Error> {
+        Some(&self.error)
+    }
+}
+
+impl From<CursorError> for EndiannessError {
+    fn from(err: CursorError) -> EndiannessError {
+        err.error
+    }
+}
+
+/// A position and byte order of a `ByteCursor` saved by `checkpoint`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Checkpoint {
+    position: usize,
+    endianness: ByteOrder,
+}
+
+impl Checkpoint {
+    /// Returns the saved position.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Result type alias for the `ByteCursor` methods.
+pub type CursorResult<T> = Result<T, CursorError>;
+
+/// A cursor over a stream of bytes which reads values in a fixed byte order
+/// and advances past them.
+///
+/// A failed read leaves the cursor where it was, so `position()` reports
+/// the offset of the value that could not be read.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = vec![0, 128, 128, 0, 1];
+/// let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+/// assert_eq!(128, cursor.read_u16().unwrap());
+/// assert_eq!(-32768, cursor.read_i16().unwrap());
+/// assert!(cursor.read_u16().is_err());
+/// assert_eq!(4, cursor.position());
+/// ```
+// Not Copy on purpose: advancing an implicit copy instead of the cursor itself is an easy mistake.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone)]
+pub struct ByteCursor<'a> {
+    data: &'a [u8],
+    position: usize,
+    endianness: ByteOrder,
+    // Offset of `data` in the outermost stream, for errors of cursors made by `take`.
+    base: usize,
+}
+
+macro_rules! cursor_read {
+    ($($name:ident, $ty:ty, $doc:expr;)*) => (
+        $(
+            #[doc = $doc]
+            #[inline]
+            pub fn $name(&mut self) -> CursorResult<$ty> {
+                self.read()
+            }
+        )*
+    );
+}
+
+macro_rules! cursor_peek {
+    ($($name:ident, $ty:ty, $doc:expr;)*) => (
+        $(
+            #[doc = $doc]
+            #[inline]
+            pub fn $name(&self) -> CursorResult<$ty> {
+                self.peek()
+            }
+        )*
+    );
+}
+
+impl<'a> ByteCursor<'a> {
+    /// Creates a cursor at the beginning of the stream.
+    pub fn new(data: &'a [u8], endianness: ByteOrder) -> ByteCursor<'a> {
+        ByteCursor {
+            data,
+            position: 0,
+            endianness,
+            base: 0,
+        }
+    }
+
+    /// Returns the offset of the next byte to be read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of bytes left to read.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    /// Returns the bytes left to read.
+    #[inline]
+    pub fn remaining_slice(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+
+    /// Returns the whole underlying stream.
+    pub fn get_ref(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the byte order values are read in.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Changes the byte order for subsequent reads.
+    pub fn set_endianness(&mut self, endianness: ByteOrder) {
+        self.endianness = endianness;
+    }
+
+    /// Advances the cursor by `n` bytes, such as a reserved field.
+    ///
+    /// Fails like `read_bytes(n)` if fewer bytes remain, without moving the cursor.
+    pub fn skip(&mut self, n: usize) -> CursorResult<()> {
+        self.read_bytes(n)?;
+        Ok(())
+    }
+
+    /// Moves the cursor to `position`, counted from the beginning of the stream.
+    ///
+    /// Moving to the end of the stream is allowed. Moving past it fails with `ShortSlice`
+    /// counted from the current position, without moving the cursor.
+    pub fn seek(&mut self, position: usize) -> CursorResult<()> {
+        if position > self.data.len() {
+            let err = EndiannessError::ShortSlice {
+                expected: position - self.position,
+                found: self.remaining(),
+            };
+            return Err(self.error("[u8]", err));
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Skips padding up to the next position that is a multiple of `alignment`, counted
+    /// from the beginning of the stream.
+    ///
+    /// Returns `InvalidWidth` if `alignment` is zero, and fails like `skip` if the stream
+    /// ends within the padding.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let v = [1, 0, 0, 0, 0, 0, 0, 2];
+    /// let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+    /// cursor.read_u8().unwrap();
+    /// cursor.align_to(4).unwrap();
+    /// assert_eq!(2, cursor.read_u32().unwrap());
+    /// ```
+    pub fn align_to(&mut self, alignment: usize) -> CursorResult<()> {
+        if alignment == 0 {
+            return Err(self.error("[u8]", EndiannessError::InvalidWidth));
+        }
+        let padding = (alignment - self.position % alignment) % alignment;
+        self.skip(padding)
+    }
+
+    /// Splits off the next `n` bytes as a cursor of their own and advances past them, so
+    /// that a length-prefixed section cannot be read beyond its declared end.
+    ///
+    /// The new cursor has the same byte order. Its positions count from the start of the
+    /// section, while its errors report offsets in the stream of this cursor.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let v = [2, 0, 1, 9];
+    /// let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+    /// let len = cursor.read_u8().unwrap() as usize;
+    /// let mut section = cursor.take(len).unwrap();
+    /// assert_eq!(1, section.read_u16().unwrap());
+    /// assert!(section.read_u8().is_err());
+    /// assert_eq!(9, cursor.read_u8().unwrap());
+    /// ```
+    pub fn take(&mut self, n: usize) -> CursorResult<ByteCursor<'a>> {
+        let base = self.base + self.position;
+        let data = self.read_bytes(n)?;
+        Ok(ByteCursor {
+            data,
+            position: 0,
+            endianness: self.endianness,
+            base,
+        })
+    }
+
+    /// Saves the position and byte order, to return to them with `rollback`.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.position,
+            endianness: self.endianness,
+        }
+    }
+
+    /// Restores the position and byte order saved by `checkpoint` on this cursor.
+    ///
+    /// A checkpoint of a longer cursor may lie past the end of this one; it then fails
+    /// like `seek` and leaves the cursor as it was.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) -> CursorResult<()> {
+        self.seek(checkpoint.position)?;
+        self.endianness = checkpoint.endianness;
+        Ok(())
+    }
+
+    /// Runs `f` on the cursor and, if it fails, restores the position and byte order it
+    /// had before, so that another layout can be tried from the same place.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let v = [0, 0, 0, 7];
+    /// let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+    /// let long = cursor.transaction(|c| {
+    ///     let n = c.read_u32()?;
+    ///     c.read_u8()?;
+    ///     Ok::<_, CursorError>(n)
+    /// });
+    /// assert!(long.is_err());
+    /// assert_eq!(0, cursor.position());
+    /// assert_eq!(7, cursor.read_u32().unwrap());
+    /// ```
+    pub fn transaction<T, E, F: FnOnce(&mut ByteCursor<'a>) -> Result<T, E>>(&mut self, f: F) -> Result<T, E> {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+        if result.is_err() {
+            // A checkpoint of the cursor itself is always within bounds.
+            let _ = self.rollback(checkpoint);
+        }
+        result
+    }
+
+    /// Reads a value of any supported type without advancing the cursor.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let v = [2, 0, 7, 0, 0, 0];
+    /// let mut cursor = ByteCursor::new(&v, ByteOrder::LittleEndian);
+    /// let value = match cursor.peek_u8().unwrap() {
+    ///     1 => u32::from(cursor.read_u16().unwrap()),
+    ///     _ => cursor.read_u32().unwrap(),
+    /// };
+    /// assert_eq!(0x00070002, value);
+    /// ```
+    #[inline]
+    pub fn peek<T: EndianRead>(&self) -> CursorResult<T> {
+        match T::read(self.remaining_slice(), self.endianness) {
+            Ok(value) => Ok(value),
+            Err(err) => Err(self.error(any::type_name::<T>(), err)),
+        }
+    }
+
+    /// Returns the next `n` bytes as they are without advancing the cursor.
+    #[inline]
+    pub fn peek_bytes(&self, n: usize) -> CursorResult<&'a [u8]> {
+        match self.remaining_slice().get(..n) {
+            Some(bytes) => Ok(bytes),
+            None => {
+                let err = EndiannessError::ShortSlice { expected: n, found: self.remaining() };
+                Err(self.error("[u8]", err))
+            }
+        }
+    }
+
+    /// Returns the next `N` bytes as an array without advancing the cursor.
+    #[inline]
+    pub fn peek_array<const N: usize>(&self) -> CursorResult<[u8; N]> {
+        match read_array::<N>(self.remaining_slice()) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) => Err(self.error(any::type_name::<[u8; N]>(), err)),
+        }
+    }
+
+    /// Reads a value of any supported type and advances the cursor.
+    #[inline]
+    pub fn read<T: EndianRead>(&mut self) -> CursorResult<T> {
+        let value = self.peek()?;
+        self.position += T::SIZE;
+        Ok(value)
+    }
+
+    /// Reads a number whose type is only known at runtime and advances the cursor.
+    pub fn read_scalar(&mut self, ty: ScalarType) -> CursorResult<Scalar> {
+        match ty.read(self.remaining_slice(), self.endianness) {
+            Ok(value) => {
+                self.position += ty.size();
+                Ok(value)
+            }
+            Err(err) => Err(self.error(ty.name(), err)),
+        }
+    }
+
+    /// Reads the next `n` bytes as they are and advances the cursor.
+    #[inline]
+    pub fn read_bytes(&mut self, n: usize) -> CursorResult<&'a [u8]> {
+        let bytes = self.peek_bytes(n)?;
+        self.position += n;
+        Ok(bytes)
+    }
+
+    /// Reads the next `N` bytes as an array and advances the cursor.
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> CursorResult<[u8; N]> {
+        let bytes = self.peek_array()?;
+        self.position += N;
+        Ok(bytes)
+    }
+
+    /// Reads a NUL-terminated UTF-8 string like `read_cstr` and advances the cursor past
+    /// the NUL.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let v = b"name\0\x2a";
+    /// let mut cursor = ByteCursor::new(v, ByteOrder::BigEndian);
+    /// assert_eq!("name", cursor.read_cstr(32).unwrap());
+    /// assert_eq!(42, cursor.read_u8().unwrap());
+    /// ```
+    pub fn read_cstr(&mut self, max_len: usize) -> CursorResult<&'a str> {
+        match read_cstr(self.remaining_slice(), max_len) {
+            Ok((value, read)) => {
+                self.position += read;
+                Ok(value)
+            }
+            Err(err) => Err(self.error("str", err)),
+        }
+    }
+
+    /// Reads a UTF-8 string stored after its length like `read_pascal_str`, with the length
+    /// in the byte order of the cursor, and advances the cursor.
+    pub fn read_pascal_str(&mut self, width: usize, max_len: usize) -> CursorResult<&'a str> {
+        match read_pascal_str(self.remaining_slice(), width, max_len, self.endianness) {
+            Ok((value, read)) => {
+                self.position += read;
+                Ok(value)
+            }
+            Err(err) => Err(self.error("str", err)),
+        }
+    }
+
+    /// Builds the error for a failed read of `type_name` at the current position, for
+    /// readers built on top of the cursor.
+    // Errors are built out of line to keep the inlined fast path small.
+    #[cold]
+    pub fn error(&self, type_name: &'static str, error: EndiannessError) -> CursorError {
+        CursorError {
+            offset: self.base + self.position,
+            type_name,
+            error,
+        }
+    }
+
+    cursor_read! {
+        read_u8, u8, "Reads unsigned 8-bit integer and advances the cursor.";
+        read_i8, i8, "Reads signed 8-bit integer and advances the cursor.";
+        read_u16, u16, "Reads unsigned 16-bit integer and advances the cursor.";
+        read_i16, i16, "Reads signed 16-bit integer and advances the cursor.";
+        read_u32, u32, "Reads unsigned 32-bit integer and advances the cursor.";
+        read_i32, i32, "Reads signed 32-bit integer and advances the cursor.";
+        read_u64, u64, "Reads unsigned 64-bit integer and advances the cursor.";
+        read_i64, i64, "Reads signed 64-bit integer and advances the cursor.";
+        read_u128, u128, "Reads unsigned 128-bit integer and advances the cursor.";
+        read_i128, i128, "Reads signed 128-bit integer and advances the cursor.";
+        read_f32, f32, "Reads a single-precision floating point number and advances the cursor.";
+        read_f64, f64, "Reads a double-precision floating point number and advances the cursor.";
+    }
+
+    cursor_peek! {
+        peek_u8, u8, "Reads unsigned 8-bit integer without advancing the cursor.";
+        peek_i8, i8, "Reads signed 8-bit integer without advancing the cursor.";
+        peek_u16, u16, "Reads unsigned 16-bit integer without advancing the cursor.";
+        peek_i16, i16, "Reads signed 16-bit integer without advancing the cursor.";
+        peek_u32, u32, "Reads unsigned 32-bit integer without advancing the cursor.";
+        peek_i32, i32, "Reads signed 32-bit integer without advancing the cursor.";
+        peek_u64, u64, "Reads unsigned 64-bit integer without advancing the cursor.";
+        peek_i64, i64, "Reads signed 64-bit integer without advancing the cursor.";
+        peek_u128, u128, "Reads unsigned 128-bit integer without advancing the cursor.";
+        peek_i128, i128, "Reads signed 128-bit integer without advancing the cursor.";
+        peek_f32, f32, "Reads a single-precision floating point number without advancing the cursor.";
+        peek_f64, f64, "Reads a double-precision floating point number without advancing the cursor.";
+    }
+}
+
+/// A cursor over a stream that is stored in two parts, such as a ring buffer that wraps
+/// around: `first` holds the beginning of the stream and `second` the rest.
+///
+/// Values may straddle the boundary, as long as they are at most 16 bytes long.
+/// Positions count from the beginning of `first`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // A ring buffer whose data starts at index 3 and wraps in the middle of the `u32`.
+/// let ring = [0x01, 0x02, 0xff, 0x56, 0x00, 0x00];
+/// let mut cursor = SplitCursor::new(&ring[3..], &ring[..2], ByteOrder::BigEndian);
+/// assert_eq!(0x56, cursor.read_u8().unwrap());
+/// assert_eq!(0x0102, cursor.read_u32().unwrap());
+/// ```
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone)]
+pub struct SplitCursor<'a> {
+    first: &'a [u8],
+    second: &'a [u8],
+    position: usize,
+    endianness: ByteOrder,
+}
+
+impl<'a> SplitCursor<'a> {
+    /// Creates a cursor at the beginning of `first`.
+    pub fn new(first: &'a [u8], second: &'a [u8], endianness: ByteOrder) -> SplitCursor<'a> {
+        SplitCursor {
+            first,
+            second,
+            position: 0,
+            endianness,
+        }
+    }
+
+    /// Returns the offset of the next byte to be read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.first.len() + self.second.len() - self.position
+    }
+
+    /// Returns the bytes left to read, in two parts.
+    pub fn remaining_slices(&self) -> (&'a [u8], &'a [u8]) {
+        if self.position < self.first.len() {
+            (&self.first[self.position..], self.second)
+        } else {
+            (&[], &self.second[self.position - self.first.len()..])
+        }
+    }
+
+    /// Returns the byte order values are read in.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Changes the byte order for subsequent reads.
+    pub fn set_endianness(&mut self, endianness: ByteOrder) {
+        self.endianness = endianness;
+    }
+
+    /// Reads a value of any supported type and advances the cursor.
+    pub fn read<T: EndianRead>(&mut self) -> CursorResult<T> {
+        let (first, second) = self.remaining_slices();
+        match read_split(first, second, self.endianness) {
+            Ok(value) => {
+                self.position += T::SIZE;
+                Ok(value)
+            }
+            Err(err) => Err(self.error(any::type_name::<T>(), err)),
+        }
+    }
+
+    /// Fills `buf` with the next bytes as they are and advances the cursor.
+    pub fn read_bytes_into(&mut self, buf: &mut [u8]) -> CursorResult<()> {
+        if self.remaining() < buf.len() {
+            let err = EndiannessError::ShortSlice { expected: buf.len(), found: self.remaining() };
+            return Err(self.error("[u8]", err));
+        }
+        let (first, second) = self.remaining_slices();
+        let (head, tail) = buf.split_at_mut(cmp::min(first.len(), buf.len()));
+        head.copy_from_slice(&first[..head.len()]);
+        tail.copy_from_slice(&second[..tail.len()]);
+        self.position += buf.len();
+        Ok(())
+    }
+
+    /// Advances the cursor by `n` bytes. Fails without moving the cursor if fewer remain.
+    pub fn skip(&mut self, n: usize) -> CursorResult<()> {
+        if self.remaining() < n {
+            let err = EndiannessError::ShortSlice { expected: n, found: self.remaining() };
+            return Err(self.error("[u8]", err));
+        }
+        self.position += n;
+        Ok(())
+    }
+
+    #[cold]
+    fn error(&self, type_name: &'static str, error: EndiannessError) -> CursorError {
+        CursorError {
+            offset: self.position,
+            type_name,
+            error,
+        }
+    }
+
+    cursor_read! {
+        read_u8, u8, "Reads unsigned 8-bit integer and advances the cursor.";
+        read_i8, i8, "Reads signed 8-bit integer and advances the cursor.";
+        read_u16, u16, "Reads unsigned 16-bit integer and advances the cursor.";
+        read_i16, i16, "Reads signed 16-bit integer and advances the cursor.";
+        read_u32, u32, "Reads unsigned 32-bit integer and advances the cursor.";
+        read_i32, i32, "Reads signed 32-bit integer and advances the cursor.";
+        read_u64, u64, "Reads unsigned 64-bit integer and advances the cursor.";
+        read_i64, i64, "Reads signed 64-bit integer and advances the cursor.";
+        read_u128, u128, "Reads unsigned 128-bit integer and advances the cursor.";
+        read_i128, i128, "Reads signed 128-bit integer and advances the cursor.";
+        read_f32, f32, "Reads a single-precision floating point number and advances the cursor.";
+        read_f64, f64, "Reads a double-precision floating point number and advances the cursor.";
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteCursor, ByteOrder, EndiannessError, EndiannessResult, Scalar, ScalarType, SplitCursor};
+
+    #[test]
+    fn reads_advance() {
+        let v = [1, 0, 2, 0, 0, 0, 3, 4, 5];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::LittleEndian);
+        assert_eq!(1, cursor.read_u16().unwrap());
+        assert_eq!(2, cursor.read_u32().unwrap());
+        assert_eq!(6, cursor.position());
+        assert_eq!(3, cursor.read_u8().unwrap());
+        assert_eq!([4], cursor.read_array::<1>().unwrap());
+        assert_eq!(&[5], cursor.read_bytes(1).unwrap());
+        assert_eq!(0, cursor.remaining());
+    }
+
+    #[test]
+    fn endianness_can_change() {
+        let v = [0, 1, 1, 0];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        assert_eq!(1, cursor.read_u16().unwrap());
+        cursor.set_endianness(ByteOrder::LittleEndian);
+        assert_eq!(1, cursor.read_u16().unwrap());
+    }
+
+    #[test]
+    fn failed_read_keeps_position() {
+        let v = [0, 0, 0, 0, 0, 0];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        cursor.read_u32().unwrap();
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 2 },
+                   cursor.read_f64().unwrap_err().error());
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   cursor.read_bytes(3).unwrap_err().error());
+        assert_eq!(4, cursor.position());
+        assert_eq!(&[0, 0], cursor.remaining_slice());
+    }
+
+    #[test]
+    fn read_scalar() {
+        let v = [0, 1, 2];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        assert_eq!(Scalar::U16(1), cursor.read_scalar(ScalarType::U16).unwrap());
+        let err = cursor.read_scalar(ScalarType::F32).unwrap_err();
+        assert_eq!((2, "f32"), (err.offset(), err.type_name()));
+        assert_eq!(Scalar::I8(2), cursor.read_scalar(ScalarType::I8).unwrap());
+    }
+
+    #[test]
+    fn peeks_do_not_advance() {
+        let v = [1, 0, 2, 3];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::LittleEndian);
+        assert_eq!(1, cursor.peek_u16().unwrap());
+        assert_eq!(1, cursor.peek_u8().unwrap());
+        assert_eq!(&[1, 0, 2], cursor.peek_bytes(3).unwrap());
+        assert_eq!([1, 0], cursor.peek_array::<2>().unwrap());
+        assert_eq!(0, cursor.position());
+        cursor.read_u16().unwrap();
+        assert_eq!(0x0302, cursor.peek::<u16>().unwrap());
+        let err = cursor.peek_u32().unwrap_err();
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 2 }, err.error());
+        assert_eq!(2, err.offset());
+        assert!(cursor.peek_bytes(3).is_err());
+        assert_eq!(2, cursor.position());
+    }
+
+    #[test]
+    fn skip_seek_align() {
+        let v = [0, 1, 2, 3, 4, 5, 6];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        cursor.skip(3).unwrap();
+        assert_eq!(3, cursor.read_u8().unwrap());
+        cursor.align_to(2).unwrap();
+        assert_eq!(4, cursor.position());
+        cursor.align_to(2).unwrap();
+        assert_eq!(4, cursor.position());
+        cursor.seek(1).unwrap();
+        assert_eq!(1, cursor.read_u8().unwrap());
+        cursor.seek(7).unwrap();
+        assert_eq!(0, cursor.remaining());
+        cursor.seek(0).unwrap();
+        cursor.align_to(1).unwrap();
+        assert_eq!(0, cursor.position());
+    }
+
+    #[test]
+    fn skip_seek_align_errors() {
+        let v = [0, 1, 2, 3, 4];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        cursor.skip(2).unwrap();
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   cursor.skip(4).unwrap_err().error());
+        assert_eq!(EndiannessError::ShortSlice { expected: 6, found: 3 },
+                   cursor.seek(8).unwrap_err().error());
+        assert_eq!(EndiannessError::ShortSlice { expected: 6, found: 3 },
+                   cursor.align_to(8).unwrap_err().error());
+        assert_eq!(EndiannessError::InvalidWidth, cursor.align_to(0).unwrap_err().error());
+        assert_eq!(2, cursor.position());
+    }
+
+    #[test]
+    fn take() {
+        let v = [0, 1, 2, 3, 4, 5];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::LittleEndian);
+        cursor.skip(1).unwrap();
+        let mut section = cursor.take(3).unwrap();
+        assert_eq!(4, cursor.position());
+        assert_eq!(0x0201, section.read_u16().unwrap());
+        assert_eq!(2, section.position());
+        let mut inner = section.take(1).unwrap();
+        assert_eq!(0, section.remaining());
+        assert_eq!(3, inner.read_u8().unwrap());
+        let err = inner.read_u8().unwrap_err();
+        assert_eq!((4, EndiannessError::ShortSlice { expected: 1, found: 0 }), (err.offset(), err.error()));
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   cursor.take(3).unwrap_err().error());
+        assert_eq!(4, cursor.position());
+    }
+
+    #[test]
+    fn checkpoint_and_rollback() {
+        let v = [1, 2, 3, 4];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        cursor.read_u8().unwrap();
+        let checkpoint = cursor.checkpoint();
+        assert_eq!(1, checkpoint.position());
+        cursor.set_endianness(ByteOrder::LittleEndian);
+        assert_eq!(0x0302, cursor.read_u16().unwrap());
+        cursor.rollback(checkpoint).unwrap();
+        assert_eq!(0x0203, cursor.read_u16().unwrap());
+
+        let mut short = ByteCursor::new(&v[..2], ByteOrder::LittleEndian);
+        short.read_u8().unwrap();
+        cursor.skip(1).unwrap();
+        let err = short.rollback(cursor.checkpoint()).unwrap_err();
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 1 }, err.error());
+        assert_eq!((1, ByteOrder::LittleEndian), (short.position(), short.endianness()));
+    }
+
+    #[test]
+    fn transaction() {
+        let v = [1, 2, 3];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::BigEndian);
+        let failed = cursor.transaction(|c| {
+            c.set_endianness(ByteOrder::LittleEndian);
+            c.read_u16()?;
+            c.read_u16()
+        });
+        assert_eq!(2, failed.unwrap_err().offset());
+        assert_eq!((0, ByteOrder::BigEndian), (cursor.position(), cursor.endianness()));
+        assert_eq!(Ok(0x0102), cursor.transaction(|c| c.read_u16()));
+        assert_eq!(2, cursor.position());
+    }
+
+    #[test]
+    fn split_cursor() {
+        let v = [1, 2, 3, 4, 5, 6, 7];
+        for i in 0..v.len() + 1 {
+            let (first, second) = v.split_at(i);
+            let mut cursor = SplitCursor::new(first, second, ByteOrder::BigEndian);
+            assert_eq!(0x0102, cursor.read_u16().unwrap());
+            let mut buf = [0; 2];
+            cursor.read_bytes_into(&mut buf).unwrap();
+            assert_eq!([3, 4], buf);
+            cursor.skip(1).unwrap();
+            assert_eq!(0x0607, cursor.read_u16().unwrap());
+            assert_eq!(0, cursor.remaining());
+            let err = cursor.read_u8().unwrap_err();
+            assert_eq!((7, EndiannessError::ShortSlice { expected: 1, found: 0 }), (err.offset(), err.error()));
+        }
+    }
+
+    #[test]
+    fn split_cursor_failures_keep_position() {
+        let mut cursor = SplitCursor::new(&[1], &[2, 3], ByteOrder::LittleEndian);
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   cursor.read_u32().unwrap_err().error());
+        assert!(cursor.read_bytes_into(&mut [0; 4]).is_err());
+        assert!(cursor.skip(4).is_err());
+        assert_eq!(0, cursor.position());
+        assert_eq!((&[1][..], &[2, 3][..]), cursor.remaining_slices());
+        cursor.skip(2).unwrap();
+        assert_eq!((&[][..], &[3][..]), cursor.remaining_slices());
+    }
+
+    #[test]
+    fn errors_report_offset_and_type() {
+        let v = [0, 0, 0, 0, 0, 0];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::LittleEndian);
+        cursor.read_bytes(5).unwrap();
+        let err = cursor.read_i16().unwrap_err();
+        assert_eq!((5, "i16"), (err.offset(), err.type_name()));
+        let err = cursor.read_bytes(2).unwrap_err();
+        assert_eq!((5, "[u8]"), (err.offset(), err.type_name()));
+        assert_eq!("u128", cursor.read::<u128>().unwrap_err().type_name());
+        assert_eq!("[u8; 4]", cursor.read_array::<4>().unwrap_err().type_name());
+    }
+
+    #[test]
+    fn strings() {
+        let v = b"ab\0\x00\x02cd\xff\0";
+        let mut cursor = ByteCursor::new(v, ByteOrder::BigEndian);
+        assert_eq!("ab", cursor.read_cstr(8).unwrap());
+        assert_eq!("cd", cursor.read_pascal_str(2, 8).unwrap());
+        assert_eq!(7, cursor.position());
+        let err = cursor.read_cstr(8).unwrap_err();
+        assert_eq!((7, "str", EndiannessError::InvalidUtf8 { valid_up_to: 0 }),
+                   (err.offset(), err.type_name(), err.error()));
+        assert_eq!(7, cursor.position());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_message() {
+        let mut cursor = ByteCursor::new(&[1, 2, 3], ByteOrder::BigEndian);
+        cursor.read_u8().unwrap();
+        assert_eq!("Cannot read f32 at offset 1: The slice length is too short: expected 4 bytes, found 2.",
+                   cursor.read_f32().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn converts_into_endianness_error() {
+        fn parse(data: &[u8]) -> EndiannessResult<u32> {
+            let mut cursor = ByteCursor::new(data, ByteOrder::BigEndian);
+            Ok(u32::from(cursor.read_u16()?) + cursor.read_u32()?)
+        }
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 1 }, parse(&[0, 1, 2]).unwrap_err());
+    }
+}
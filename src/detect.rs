@@ -0,0 +1,142 @@
+//! Recognizing the byte order a stream declares about itself: byte order marks, markers
+//! such as TIFF's `II`/`MM`, and the headers of executable formats.
+
+use ByteOrder;
+
+/// Detects a UTF-32 or UTF-16 byte order mark at the beginning of a stream.
+///
+/// Returns the byte order and the length of the mark to skip, 4 for UTF-32 and 2 for
+/// UTF-16, or `None` if the stream does not start with a mark. `FF FE 00 00` is taken as
+/// the UTF-32 little-endian mark, although it is also the UTF-16 mark followed by U+0000.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(Some((ByteOrder::BigEndian, 2)), detect_bom(&[0xfe, 0xff, 0x00, 0x41]));
+/// assert_eq!(Some((ByteOrder::LittleEndian, 4)), detect_bom(&[0xff, 0xfe, 0x00, 0x00]));
+/// assert_eq!(None, detect_bom(b"<?xml"));
+/// ```
+pub fn detect_bom(data: &[u8]) -> Option<(ByteOrder, usize)> {
+    match *data {
+        [0x00, 0x00, 0xfe, 0xff, ..] => Some((ByteOrder::BigEndian, 4)),
+        [0xff, 0xfe, 0x00, 0x00, ..] => Some((ByteOrder::LittleEndian, 4)),
+        [0xfe, 0xff, ..] => Some((ByteOrder::BigEndian, 2)),
+        [0xff, 0xfe, ..] => Some((ByteOrder::LittleEndian, 2)),
+        _ => None,
+    }
+}
+
+/// Detects the `II` or `MM` marker that TIFF, EXIF and many camera raw formats start
+/// with: `II` (Intel) for little-endian and `MM` (Motorola) for big-endian.
+///
+/// Only the two marker bytes are checked; the format-specific magic number after them,
+/// such as 42 for TIFF, is read in the returned byte order.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let header = [b'M', b'M', 0, 42, 0, 0, 0, 8];
+/// let order = detect_ii_mm(&header).unwrap();
+/// assert_eq!(ByteOrder::BigEndian, order);
+/// assert_eq!(42, read_u16(&header[2..], order).unwrap());
+/// ```
+pub fn detect_ii_mm(data: &[u8]) -> Option<ByteOrder> {
+    match *data {
+        [b'I', b'I', ..] => Some(ByteOrder::LittleEndian),
+        [b'M', b'M', ..] => Some(ByteOrder::BigEndian),
+        _ => None,
+    }
+}
+
+/// Detects the data encoding an ELF file declares in `EI_DATA`, the sixth byte of its
+/// identification.
+///
+/// Returns `None` if the stream does not start with the ELF magic or `EI_DATA` is neither
+/// `ELFDATA2LSB` (1) nor `ELFDATA2MSB` (2).
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let ident = [0x7f, b'E', b'L', b'F', 2, 1, 1, 0];
+/// assert_eq!(Some(ByteOrder::LittleEndian), detect_elf(&ident));
+/// ```
+pub fn detect_elf(data: &[u8]) -> Option<ByteOrder> {
+    match *data {
+        [0x7f, b'E', b'L', b'F', _, 1, ..] => Some(ByteOrder::LittleEndian),
+        [0x7f, b'E', b'L', b'F', _, 2, ..] => Some(ByteOrder::BigEndian),
+        _ => None,
+    }
+}
+
+/// Detects the byte order of a Mach-O file from its magic number, for both thin files
+/// (`MH_MAGIC`, `MH_MAGIC_64`) and universal ones (`FAT_MAGIC`, `FAT_MAGIC_64`).
+///
+/// The magic is stored in the byte order of the header, so a big-endian reading of
+/// `FEEDFACE` means big-endian and a reversed one little-endian. Fat headers are
+/// big-endian in practice, but their reversed magic is recognized too. Java class files
+/// also start with `CAFEBABE` and are taken for fat headers.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(Some(ByteOrder::LittleEndian), detect_mach_o(&[0xcf, 0xfa, 0xed, 0xfe]));
+/// assert_eq!(Some(ByteOrder::BigEndian), detect_mach_o(&[0xca, 0xfe, 0xba, 0xbe]));
+/// ```
+pub fn detect_mach_o(data: &[u8]) -> Option<ByteOrder> {
+    const MAGICS: [u32; 4] = [0xfeed_face, 0xfeed_facf, 0xcafe_babe, 0xcafe_babf];
+    let magic = match *data {
+        [a, b, c, d, ..] => u32::from_be_bytes([a, b, c, d]),
+        _ => return None,
+    };
+    if MAGICS.contains(&magic) {
+        Some(ByteOrder::BigEndian)
+    } else if MAGICS.contains(&magic.swap_bytes()) {
+        Some(ByteOrder::LittleEndian)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, detect_bom, detect_elf, detect_ii_mm, detect_mach_o};
+
+    #[test]
+    fn bom() {
+        assert_eq!(Some((ByteOrder::BigEndian, 4)), detect_bom(&[0, 0, 0xfe, 0xff, 0, 0, 0, 0x41]));
+        assert_eq!(Some((ByteOrder::LittleEndian, 2)), detect_bom(&[0xff, 0xfe, 0x41, 0]));
+        assert_eq!(Some((ByteOrder::LittleEndian, 2)), detect_bom(&[0xff, 0xfe]));
+        assert_eq!(Some((ByteOrder::LittleEndian, 2)), detect_bom(&[0xff, 0xfe, 0]));
+        assert_eq!(None, detect_bom(&[0, 0, 0xfe]));
+        assert_eq!(None, detect_bom(&[0xef, 0xbb, 0xbf]));
+        assert_eq!(None, detect_bom(&[]));
+    }
+
+    #[test]
+    fn ii_mm() {
+        assert_eq!(Some(ByteOrder::LittleEndian), detect_ii_mm(b"II*\0"));
+        assert_eq!(Some(ByteOrder::BigEndian), detect_ii_mm(b"MM"));
+        assert_eq!(None, detect_ii_mm(b"IM"));
+        assert_eq!(None, detect_ii_mm(b"I"));
+        assert_eq!(None, detect_ii_mm(b"ii"));
+    }
+
+    #[test]
+    fn elf() {
+        assert_eq!(Some(ByteOrder::BigEndian), detect_elf(&[0x7f, b'E', b'L', b'F', 1, 2]));
+        assert_eq!(None, detect_elf(&[0x7f, b'E', b'L', b'F', 1, 0]));
+        assert_eq!(None, detect_elf(&[0x7f, b'E', b'L', b'F', 1]));
+        assert_eq!(None, detect_elf(&[0x7f, b'E', b'L', b'G', 1, 1]));
+    }
+
+    #[test]
+    fn mach_o() {
+        assert_eq!(Some(ByteOrder::BigEndian), detect_mach_o(&[0xfe, 0xed, 0xfa, 0xce, 0]));
+        assert_eq!(Some(ByteOrder::BigEndian), detect_mach_o(&[0xfe, 0xed, 0xfa, 0xcf]));
+        assert_eq!(Some(ByteOrder::LittleEndian), detect_mach_o(&[0xce, 0xfa, 0xed, 0xfe]));
+        assert_eq!(Some(ByteOrder::BigEndian), detect_mach_o(&[0xca, 0xfe, 0xba, 0xbf]));
+        assert_eq!(Some(ByteOrder::LittleEndian), detect_mach_o(&[0xbe, 0xba, 0xfe, 0xca]));
+        assert_eq!(None, detect_mach_o(&[0xfe, 0xed, 0xfa]));
+        assert_eq!(None, detect_mach_o(&[0x7f, b'E', b'L', b'F']));
+    }
+}
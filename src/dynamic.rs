@@ -0,0 +1,182 @@
+//! Numbers whose type is only known at runtime.
+
+use core::fmt;
+
+use {ByteOrder, EndianRead, EndianWrite, EndiannessError, EndiannessResult};
+
+macro_rules! scalars {
+    ($($variant:ident, $ty:ident, $name:expr;)*) => (
+        /// The type of a number read at runtime.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub enum ScalarType {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+        }
+
+        /// A number of any of the `ScalarType`s.
+        #[derive(Debug, Copy, Clone, PartialEq)]
+        pub enum Scalar {
+            $(
+                #[doc = concat!("A `", $name, "` value.")]
+                $variant($ty),
+            )*
+        }
+
+        impl ScalarType {
+            /// Returns the number of bytes a value of this type takes.
+            pub fn size(self) -> usize {
+                match self {
+                    $(ScalarType::$variant => <$ty as EndianRead>::SIZE,)*
+                }
+            }
+
+            /// Returns the Rust name of the type, such as `u32`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(ScalarType::$variant => $name,)*
+                }
+            }
+
+            /// Reads a value of this type from the front of the stream.
+            pub fn read(self, data: &[u8], endianness: ByteOrder) -> EndiannessResult<Scalar> {
+                match self {
+                    $(ScalarType::$variant => Ok(Scalar::$variant(<$ty>::read(data, endianness)?)),)*
+                }
+            }
+        }
+
+        impl Scalar {
+            /// Returns the type of the value.
+            pub fn scalar_type(&self) -> ScalarType {
+                match *self {
+                    $(Scalar::$variant(_) => ScalarType::$variant,)*
+                }
+            }
+
+            /// Writes the value to the front of the stream, like its `EndianWrite` impl.
+            pub fn write(&self, data: &mut [u8], endianness: ByteOrder) -> EndiannessResult<()> {
+                EndianWrite::write(self, data, endianness)
+            }
+        }
+
+        impl EndianWrite for Scalar {
+            fn encoded_len(&self) -> usize {
+                self.scalar_type().size()
+            }
+
+            fn write(&self, data: &mut [u8], endianness: ByteOrder) -> EndiannessResult<()> {
+                match *self {
+                    $(Scalar::$variant(n) => n.write(data, endianness),)*
+                }
+            }
+        }
+
+        impl fmt::Display for Scalar {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match *self {
+                    $(Scalar::$variant(n) => n.fmt(f),)*
+                }
+            }
+        }
+
+        $(
+            impl From<$ty> for Scalar {
+                fn from(n: $ty) -> Scalar {
+                    Scalar::$variant(n)
+                }
+            }
+        )*
+    );
+}
+
+scalars! {
+    U8, u8, "u8";
+    I8, i8, "i8";
+    U16, u16, "u16";
+    I16, i16, "i16";
+    U32, u32, "u32";
+    I32, i32, "i32";
+    U64, u64, "u64";
+    I64, i64, "i64";
+    U128, u128, "u128";
+    I128, i128, "i128";
+    F32, f32, "f32";
+    F64, f64, "f64";
+}
+
+impl fmt::Display for ScalarType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Reads values of the given types at the given offsets, such as the entries of an index
+/// table, into `dst`.
+///
+/// All requests are checked against the length of the stream once, before anything is
+/// read. `dst` must hold at least as many values as there are requests, otherwise
+/// `LengthMismatch` is returned.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0, 0, 1, 0, 0, 0, 2, 0xff];
+/// let requests = [(2, ScalarType::U16), (4, ScalarType::U32), (7, ScalarType::I8)];
+/// let mut values = [Scalar::U8(0); 3];
+/// gather(&v, &requests, &mut values, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!([Scalar::U16(1), Scalar::U32(0xff020000), Scalar::I8(-1)], values);
+/// ```
+pub fn gather(data: &[u8], requests: &[(usize, ScalarType)], dst: &mut [Scalar], endianness: ByteOrder)
+              -> EndiannessResult<()> {
+    if dst.len() < requests.len() {
+        return Err(EndiannessError::LengthMismatch { expected: requests.len(), found: dst.len() });
+    }
+    // Saturates on overflow, which no slice can satisfy.
+    let expected = requests.iter()
+        .map(|&(offset, ty)| offset.saturating_add(ty.size()))
+        .max()
+        .unwrap_or(0);
+    if data.len() < expected {
+        return Err(EndiannessError::ShortSlice { expected, found: data.len() });
+    }
+    for (&(offset, ty), value) in requests.iter().zip(dst) {
+        *value = ty.read(&data[offset..], endianness)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError, Scalar, ScalarType, gather};
+
+    #[test]
+    fn scalar_round_trip() {
+        let values = [Scalar::U8(1), Scalar::I16(-2), Scalar::U128(1 << 100), Scalar::F32(-127.5)];
+        for value in &values {
+            let ty = value.scalar_type();
+            let mut buf = [0u8; 16];
+            value.write(&mut buf, ByteOrder::BigEndian).unwrap();
+            assert_eq!(*value, ty.read(&buf[..ty.size()], ByteOrder::BigEndian).unwrap());
+        }
+        assert_eq!(Scalar::F32(-127.5), ScalarType::F32.read(&[194, 255, 0, 0], ByteOrder::BigEndian).unwrap());
+        assert_eq!("-127.5 as f32", format!("{} as {}", Scalar::from(-127.5f32), ScalarType::F32));
+    }
+
+    #[test]
+    fn gather_checks_once() {
+        let v = [1, 2, 3, 4];
+        let mut dst = [Scalar::U8(0); 2];
+        let requests = [(0, ScalarType::U8), (1, ScalarType::U32)];
+        assert_eq!(EndiannessError::ShortSlice { expected: 5, found: 4 },
+                   gather(&v, &requests, &mut dst, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!([Scalar::U8(0); 2], dst);
+        assert_eq!(EndiannessError::LengthMismatch { expected: 2, found: 1 },
+                   gather(&v, &requests, &mut dst[..1], ByteOrder::BigEndian).unwrap_err());
+        let requests = [(3, ScalarType::U8), (0, ScalarType::U16), (usize::MAX, ScalarType::U8)];
+        assert!(gather(&v, &requests, &mut [Scalar::U8(0); 3], ByteOrder::BigEndian).is_err());
+        gather(&v, &requests[..2], &mut dst, ByteOrder::BigEndian).unwrap();
+        assert_eq!([Scalar::U8(4), Scalar::U16(0x0102)], dst);
+    }
+}
@@ -0,0 +1,303 @@
+//! Integration with the `embedded-io` `Read` and `Write` traits, for firmware that talks to
+//! serial, SPI or similar transports without `std`.
+//!
+//! Slices implement both `std::io::Read` and `embedded_io::Read`, so the extension traits
+//! are not exported from the crate root. Requires the `embedded-io` feature.
+//!
+//! The `embedded-io-async` traits are not covered: they are declared with `async fn`, and
+//! awaiting those from a future needs async blocks, which the 2015 edition of this crate
+//! lacks. Async firmware can fill a buffer with their `read_exact` and decode it with
+//! `read_u16` and the other slice functions.
+//!
+//! ```rust
+//! # extern crate endianness;
+//! use endianness::ByteOrder;
+//! use endianness::embedded::{EmbeddedReadEndian, EmbeddedWriteEndian};
+//!
+//! # fn main() {
+//! let mut v = [0u8; 6];
+//! {
+//!     let mut writer: &mut [u8] = &mut v;
+//!     writer.write_u16_endian(0x0102, ByteOrder::BigEndian).unwrap();
+//!     writer.write_u32_endian(7, ByteOrder::LittleEndian).unwrap();
+//! }
+//! let mut reader: &[u8] = &v;
+//! assert_eq!(0x0102, reader.read_u16_endian(ByteOrder::BigEndian).unwrap());
+//! assert_eq!(7, reader.read_u32_endian(ByteOrder::LittleEndian).unwrap());
+//! # }
+//! ```
+
+use core::fmt;
+use embedded_io::{ErrorKind, ErrorType, Read, Write};
+
+use {ByteOrder, EndianRead, EndianWrite, EndiannessError};
+
+/// The error of the extension traits: either a decoding error or an error of the transport.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EmbeddedError<E> {
+    /// The value could not be read or written. The end of the stream is reported as
+    /// `ShortSlice` with the number of bytes that were available.
+    Endianness(EndiannessError),
+    /// The transport failed.
+    Io(E),
+}
+
+impl<E> From<EndiannessError> for EmbeddedError<E> {
+    fn from(err: EndiannessError) -> EmbeddedError<E> {
+        EmbeddedError::Endianness(err)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for EmbeddedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EmbeddedError::Endianness(ref err) => err.fmt(f),
+            EmbeddedError::Io(ref err) => write!(f, "An I/O error occurred: {:?}.", err),
+        }
+    }
+}
+
+impl<E: embedded_io::Error> embedded_io::Error for EmbeddedError<E> {
+    fn kind(&self) -> ErrorKind {
+        match *self {
+            EmbeddedError::Endianness(EndiannessError::ShortSlice { .. }) => ErrorKind::Other,
+            EmbeddedError::Endianness(_) => ErrorKind::InvalidData,
+            EmbeddedError::Io(ref err) => err.kind(),
+        }
+    }
+}
+
+/// Result type alias for the extension traits over a transport `R`.
+pub type EmbeddedResult<T, R> = Result<T, EmbeddedError<<R as ErrorType>::Error>>;
+
+// Largest value the extension traits read or write through a stack buffer.
+const MAX_SIZE: usize = 16;
+
+/// Extends any `embedded_io::Read` with methods that read numbers in a given byte order.
+pub trait EmbeddedReadEndian: Read {
+    /// Reads a value of any supported type of up to 16 bytes; larger types fail with
+    /// `InvalidWidth`, since the trait does not allocate.
+    fn read_endian<T: EndianRead>(&mut self, endianness: ByteOrder) -> EmbeddedResult<T, Self> {
+        if T::SIZE > MAX_SIZE {
+            return Err(EndiannessError::InvalidWidth.into());
+        }
+        let mut buf = [0u8; MAX_SIZE];
+        let mut found = 0;
+        while found < T::SIZE {
+            match self.read(&mut buf[found..T::SIZE]) {
+                Ok(0) => return Err(EndiannessError::ShortSlice { expected: T::SIZE, found }.into()),
+                Ok(n) => found += n,
+                Err(err) => return Err(EmbeddedError::Io(err)),
+            }
+        }
+        Ok(T::read(&buf, endianness)?)
+    }
+
+    /// Reads unsigned 8-bit integer.
+    fn read_u8_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<u8, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 8-bit integer.
+    fn read_i8_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<i8, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 16-bit integer.
+    fn read_u16_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<u16, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 16-bit integer.
+    fn read_i16_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<i16, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 32-bit integer.
+    fn read_u32_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<u32, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 32-bit integer.
+    fn read_i32_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<i32, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 64-bit integer.
+    fn read_u64_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<u64, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 64-bit integer.
+    fn read_i64_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<i64, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 128-bit integer.
+    fn read_u128_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<u128, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 128-bit integer.
+    fn read_i128_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<i128, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads a single-precision floating point number.
+    fn read_f32_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<f32, Self> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads a double-precision floating point number.
+    fn read_f64_endian(&mut self, endianness: ByteOrder) -> EmbeddedResult<f64, Self> {
+        self.read_endian(endianness)
+    }
+}
+
+impl<R: Read + ?Sized> EmbeddedReadEndian for R {}
+
+/// Extends any `embedded_io::Write` with methods that write numbers in a given byte order.
+pub trait EmbeddedWriteEndian: Write {
+    /// Writes a value of any supported type of up to 16 bytes; larger types fail with
+    /// `InvalidWidth`, since the trait does not allocate.
+    fn write_endian<T: EndianWrite>(&mut self, value: T, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        let len = value.encoded_len();
+        if len > MAX_SIZE {
+            return Err(EndiannessError::InvalidWidth.into());
+        }
+        let mut buf = [0u8; MAX_SIZE];
+        value.write(&mut buf, endianness)?;
+        self.write_all(&buf[..len]).map_err(EmbeddedError::Io)
+    }
+
+    /// Writes unsigned 8-bit integer.
+    fn write_u8_endian(&mut self, value: u8, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 8-bit integer.
+    fn write_i8_endian(&mut self, value: i8, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 16-bit integer.
+    fn write_u16_endian(&mut self, value: u16, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 16-bit integer.
+    fn write_i16_endian(&mut self, value: i16, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 32-bit integer.
+    fn write_u32_endian(&mut self, value: u32, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 32-bit integer.
+    fn write_i32_endian(&mut self, value: i32, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 64-bit integer.
+    fn write_u64_endian(&mut self, value: u64, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 64-bit integer.
+    fn write_i64_endian(&mut self, value: i64, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 128-bit integer.
+    fn write_u128_endian(&mut self, value: u128, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 128-bit integer.
+    fn write_i128_endian(&mut self, value: i128, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes a single-precision floating point number.
+    fn write_f32_endian(&mut self, value: f32, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes a double-precision floating point number.
+    fn write_f64_endian(&mut self, value: f64, endianness: ByteOrder) -> EmbeddedResult<(), Self> {
+        self.write_endian(value, endianness)
+    }
+}
+
+impl<W: Write + ?Sized> EmbeddedWriteEndian for W {}
+
+#[cfg(test)]
+mod tests {
+    use embedded_io::{ErrorKind, ErrorType, Read, Write};
+
+    use {ByteOrder, EndiannessError};
+    use super::{EmbeddedError, EmbeddedReadEndian, EmbeddedWriteEndian};
+
+    // A transport that delivers one byte at a time and then fails.
+    struct Uart<'a> {
+        rx: &'a [u8],
+    }
+
+    impl<'a> ErrorType for Uart<'a> {
+        type Error = ErrorKind;
+    }
+
+    impl<'a> Read for Uart<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+            match self.rx.split_first() {
+                Some((&b, rest)) => {
+                    buf[0] = b;
+                    self.rx = rest;
+                    Ok(1)
+                }
+                None => Err(ErrorKind::TimedOut),
+            }
+        }
+    }
+
+    impl<'a> Write for Uart<'a> {
+        fn write(&mut self, _buf: &[u8]) -> Result<usize, ErrorKind> {
+            Err(ErrorKind::BrokenPipe)
+        }
+
+        fn flush(&mut self) -> Result<(), ErrorKind> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reads_in_pieces() {
+        let mut uart = Uart { rx: &[0, 0, 1, 0, 255] };
+        assert_eq!(256, uart.read_u32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-1, uart.read_i8_endian(ByteOrder::LittleEndian).unwrap());
+        assert_eq!(EmbeddedError::Io(ErrorKind::TimedOut),
+                   uart.read_u16_endian(ByteOrder::BigEndian).unwrap_err());
+    }
+
+    #[test]
+    fn end_of_stream() {
+        let mut reader: &[u8] = &[1, 2, 3];
+        assert_eq!(EmbeddedError::Endianness(EndiannessError::ShortSlice { expected: 8, found: 3 }),
+                   reader.read_f64_endian(ByteOrder::LittleEndian).unwrap_err());
+    }
+
+    #[test]
+    fn writes() {
+        let mut v = [0u8; 4];
+        {
+            let mut writer: &mut [u8] = &mut v;
+            writer.write_i16_endian(-2, ByteOrder::LittleEndian).unwrap();
+            writer.write_u8_endian(7, ByteOrder::BigEndian).unwrap();
+        }
+        assert_eq!([254, 255, 7, 0], v);
+        assert_eq!(EmbeddedError::Io(ErrorKind::BrokenPipe),
+                   Uart { rx: &[] }.write_u16_endian(1, ByteOrder::BigEndian).unwrap_err());
+    }
+}
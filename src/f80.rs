@@ -0,0 +1,90 @@
+//! x87 80-bit extended precision floating point numbers, as found in AIFF headers and
+//! x86 memory dumps.
+//!
+//! The format has a sign bit, a 15-bit exponent with a bias of 16383 and a 64-bit
+//! significand with an explicit integer bit. Little-endian values store the significand
+//! first; big-endian ones, like the sample rate in AIFF, start with the exponent.
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+use {pow2, read_u16, read_u64};
+
+/// Reads an 80-bit extended precision float and converts it to `f64`.
+///
+/// The conversion loses precision: the 64-bit significand is rounded to the 53 bits of
+/// `f64`, to nearest with ties to even. Exponents beyond the `f64` range become infinity
+/// or zero, and results in the subnormal range may be rounded twice. Infinities and NaNs
+/// are kept.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // 44100 Hz, as stored in an AIFF COMM chunk
+/// let rate = [0x40, 0x0e, 0xac, 0x44, 0, 0, 0, 0, 0, 0];
+/// assert_eq!(44100.0, read_f80(&rate, ByteOrder::BigEndian).unwrap());
+/// ```
+pub fn read_f80(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f64> {
+    if data.len() < 10 {
+        return Err(EndiannessError::ShortSlice { expected: 10, found: data.len() });
+    }
+    let (sign_exponent, significand) = match endianness {
+        ByteOrder::BigEndian => (read_u16(data, endianness)?, read_u64(&data[2..], endianness)?),
+        ByteOrder::LittleEndian => (read_u16(&data[8..], endianness)?, read_u64(data, endianness)?),
+    };
+    let exponent = i32::from(sign_exponent & 0x7fff);
+    let value = if exponent == 0x7fff {
+        if significand << 1 == 0 { f64::INFINITY } else { f64::NAN }
+    } else {
+        // The significand as a number in [0, 2), scaled in two steps for small exponents.
+        let value = significand as f64 * pow2(-63);
+        match exponent - 16383 {
+            e if e > 1023 => f64::INFINITY,
+            e if e < -1100 => 0.0,
+            e if e < -1000 => value * pow2(-1000) * pow2(e + 1000),
+            e => value * pow2(e),
+        }
+    };
+    Ok(if sign_exponent >> 15 == 1 { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError};
+    use super::*;
+
+    #[test]
+    fn values() {
+        let one = [0x3f, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(1.0, read_f80(&one, ByteOrder::BigEndian).unwrap());
+        let minus_three = [0, 0, 0, 0, 0, 0, 0, 0xc0, 0x00, 0xc0];
+        assert_eq!(-3.0, read_f80(&minus_three, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(0.0, read_f80(&[0; 10], ByteOrder::LittleEndian).unwrap());
+        // 1 + 2^-63 does not fit into f64 and rounds to 1.0.
+        let one_ulp = [0x3f, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 1];
+        assert_eq!(1.0, read_f80(&one_ulp, ByteOrder::BigEndian).unwrap());
+        // 1 + 2^-52 + 2^-53 is a tie and rounds to even.
+        let tie = [0x3f, 0xff, 0x80, 0, 0, 0, 0, 0, 0x0c, 0];
+        assert_eq!(1.0 + 2.0 * f64::EPSILON, read_f80(&tie, ByteOrder::BigEndian).unwrap());
+    }
+
+    #[test]
+    fn range() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(f64::INFINITY, read_f80(&[0x7f, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0], order).unwrap());
+        assert_eq!(f64::NEG_INFINITY, read_f80(&[0xff, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0], order).unwrap());
+        assert!(read_f80(&[0x7f, 0xff, 0xc0, 0, 0, 0, 0, 0, 0, 0], order).unwrap().is_nan());
+        // 2^1024 is finite in 80 bits, but not in f64.
+        assert_eq!(f64::INFINITY, read_f80(&[0x43, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0], order).unwrap());
+        // 2^-1074 is the smallest f64 subnormal; 2^-1100 underflows.
+        assert_eq!(f64::from_bits(1), read_f80(&[0x3b, 0xcd, 0x80, 0, 0, 0, 0, 0, 0, 0], order).unwrap());
+        assert_eq!(-0.0, read_f80(&[0xbb, 0xb3, 0x80, 0, 0, 0, 0, 0, 0, 0], order).unwrap());
+        assert_eq!(f64::MIN_POSITIVE, read_f80(&[0x3c, 0x01, 0x80, 0, 0, 0, 0, 0, 0, 0], order).unwrap());
+    }
+
+    #[test]
+    fn short_slice() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 10, found: 9 },
+                   read_f80(&[0; 9], ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 10, found: 9 },
+                   read_f80(&[0; 9], ByteOrder::LittleEndian).unwrap_err());
+    }
+}
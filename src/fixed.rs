@@ -0,0 +1,58 @@
+//! Reads from fixed-size arrays that can be evaluated at compile time.
+
+macro_rules! const_read {
+    ($($be:ident, $le:ident, $ty:ident, $size:expr, $desc:expr;)*) => (
+        $(
+            #[doc = concat!("Reads ", $desc, " from an array in big-endian.")]
+            pub const fn $be(data: &[u8; $size]) -> $ty {
+                $ty::from_be_bytes(*data)
+            }
+
+            #[doc = concat!("Reads ", $desc, " from an array in little-endian.")]
+            pub const fn $le(data: &[u8; $size]) -> $ty {
+                $ty::from_le_bytes(*data)
+            }
+        )*
+    );
+}
+
+const_read! {
+    read_u16_be, read_u16_le, u16, 2, "unsigned 16-bit integer";
+    read_i16_be, read_i16_le, i16, 2, "signed 16-bit integer";
+    read_u32_be, read_u32_le, u32, 4, "unsigned 32-bit integer";
+    read_i32_be, read_i32_le, i32, 4, "signed 32-bit integer";
+    read_u64_be, read_u64_le, u64, 8, "unsigned 64-bit integer";
+    read_i64_be, read_i64_le, i64, 8, "signed 64-bit integer";
+    read_u128_be, read_u128_le, u128, 16, "unsigned 128-bit integer";
+    read_i128_be, read_i128_le, i128, 16, "signed 128-bit integer";
+    read_f32_be, read_f32_le, f32, 4, "a single-precision floating point number";
+    read_f64_be, read_f64_le, f64, 8, "a double-precision floating point number";
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, read_u32, read_i64, read_f32};
+    use super::*;
+
+    const TABLE: [u8; 8] = [194, 255, 0, 0, 0, 0, 0, 1];
+    const WORD: u32 = read_u32_be(&[TABLE[0], TABLE[1], TABLE[2], TABLE[3]]);
+    const FLOAT: f32 = read_f32_be(&[TABLE[0], TABLE[1], TABLE[2], TABLE[3]]);
+    const LONG: i64 = read_i64_le(&TABLE);
+
+    #[test]
+    fn const_evaluation() {
+        assert_eq!(read_u32(&TABLE, ByteOrder::BigEndian).unwrap(), WORD);
+        assert_eq!(read_f32(&TABLE, ByteOrder::BigEndian).unwrap(), FLOAT);
+        assert_eq!(read_i64(&TABLE, ByteOrder::LittleEndian).unwrap(), LONG);
+    }
+
+    #[test]
+    fn byte_orders() {
+        assert_eq!(0x0102, read_u16_be(&[1, 2]));
+        assert_eq!(0x0201, read_u16_le(&[1, 2]));
+        assert_eq!(-2, read_i16_be(&[255, 254]));
+        assert_eq!(-2, read_i16_le(&[254, 255]));
+        assert_eq!(1 << 120, read_u128_be(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(-127.5, read_f64_le(&(-127.5f64).to_le_bytes()));
+    }
+}
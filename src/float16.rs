@@ -0,0 +1,67 @@
+//! Half-precision (IEEE 754 binary16) floating point numbers.
+
+#[cfg(feature = "half")]
+use half::f16;
+
+use {ByteOrder, EndiannessResult};
+use {read_u16, write_u16};
+
+/// Reads the bit pattern of a half-precision floating point number.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // -2.0 in binary16
+/// assert_eq!(0xc000, read_f16_bits(&[0x00, 0xc0], ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_f16_bits(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u16> {
+    read_u16(data, endianness)
+}
+
+/// Writes the bit pattern of a half-precision floating point number.
+pub fn write_f16_bits(data: &mut [u8], bits: u16, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u16(data, bits, endianness)
+}
+
+/// Reads a half-precision floating point number.
+///
+/// Requires the `half` feature.
+#[cfg(feature = "half")]
+pub fn read_f16(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f16> {
+    Ok(f16::from_bits(read_f16_bits(data, endianness)?))
+}
+
+/// Writes a half-precision floating point number.
+///
+/// Requires the `half` feature.
+#[cfg(feature = "half")]
+pub fn write_f16(data: &mut [u8], value: f16, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_f16_bits(data, value.to_bits(), endianness)
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError};
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let mut v = [0u8; 2];
+        write_f16_bits(&mut v, 0x3c00, ByteOrder::BigEndian).unwrap();
+        assert_eq!([0x3c, 0x00], v);
+        assert_eq!(0x3c00, read_f16_bits(&v, ByteOrder::BigEndian).unwrap());
+        assert_eq!(0x003c, read_f16_bits(&v, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   read_f16_bits(&v[..1], ByteOrder::BigEndian).unwrap_err());
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn values() {
+        let mut v = [0u8; 2];
+        write_f16(&mut v, f16::from_f32(-127.5), ByteOrder::LittleEndian).unwrap();
+        assert_eq!(-127.5, read_f16(&v, ByteOrder::LittleEndian).unwrap().to_f32());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   write_f16(&mut v[..1], f16::ONE, ByteOrder::BigEndian).unwrap_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! 8-bit floating point numbers used for machine learning weights.
+//!
+//! Both variants follow the OCP 8-bit floating point specification:
+//!
+//! * E4M3: 4-bit exponent with a bias of 7 and 3-bit mantissa. There are no infinities;
+//!   `S.1111.111` is NaN, which extends the finite range to ±448.
+//! * E5M2: 5-bit exponent with a bias of 15 and 2-bit mantissa, laid out like a
+//!   truncated IEEE 754 binary16 with infinities and NaNs. The finite range is ±57344.
+//!
+//! Both have subnormal numbers and a signed zero. A single byte has no byte order, so
+//! these functions convert values rather than read them from a stream.
+
+use pow2;
+
+// Decodes a finite value with the given number of mantissa bits and exponent bias.
+fn decode(byte: u8, man_bits: u32, bias: i32) -> f32 {
+    let exponent = i32::from(byte & 0x7f) >> man_bits;
+    let mantissa = u32::from(byte) & ((1 << man_bits) - 1);
+    let value = if exponent == 0 {
+        f64::from(mantissa) * pow2(1 - bias - man_bits as i32)
+    } else {
+        f64::from(mantissa | (1 << man_bits)) * pow2(exponent - bias - man_bits as i32)
+    };
+    (if byte & 0x80 != 0 { -value } else { value }) as f32
+}
+
+// Encodes the magnitude of a value that is not NaN, rounding to nearest with ties to
+// even. Returns the code without the sign bit, which may exceed the largest finite one.
+fn encode(value: f32, man_bits: u32, bias: i32) -> u32 {
+    let bits = value.to_bits() & 0x7fff_ffff;
+    let exponent = (bits >> 23) as i32;
+    if exponent == 0 {
+        // Zero and f32 subnormals, which are far below the smallest 8-bit subnormal.
+        return 0;
+    }
+    let target = exponent - 127 + bias;
+    let (prefix, significand, shift) = if target >= 1 {
+        ((target as u32) << man_bits, bits & 0x007f_ffff, 23 - man_bits)
+    } else {
+        let shift = 23 - man_bits + (1 - target) as u32;
+        if shift > 24 {
+            return 0;
+        }
+        (0, bits & 0x007f_ffff | 0x0080_0000, shift)
+    };
+    let code = prefix + (significand >> shift);
+    let rest = significand & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if rest > half || (rest == half && code & 1 == 1) {
+        code + 1
+    } else {
+        code
+    }
+}
+
+fn sign(value: f32) -> u8 {
+    if value.is_sign_negative() { 0x80 } else { 0 }
+}
+
+/// Converts an E4M3 value to `f32`. The conversion is exact.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(1.5, fp8_e4m3_to_f32(0x3c));
+/// assert_eq!(-448.0, fp8_e4m3_to_f32(0xfe));
+/// assert!(fp8_e4m3_to_f32(0x7f).is_nan());
+/// ```
+pub fn fp8_e4m3_to_f32(byte: u8) -> f32 {
+    if byte & 0x7f == 0x7f {
+        f32::NAN
+    } else {
+        decode(byte, 3, 7)
+    }
+}
+
+/// Converts an `f32` to E4M3, rounding to nearest with ties to even.
+///
+/// E4M3 has no infinities, so values that round beyond ±448, infinities and NaN all
+/// become NaN.
+pub fn f32_to_fp8_e4m3(value: f32) -> u8 {
+    if value.is_nan() {
+        return 0x7f;
+    }
+    match encode(value, 3, 7) {
+        code if code > 0x7e => sign(value) | 0x7f,
+        code => sign(value) | code as u8,
+    }
+}
+
+/// Converts an E5M2 value to `f32`. The conversion is exact.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(-0.75, fp8_e5m2_to_f32(0xba));
+/// assert_eq!(f32::INFINITY, fp8_e5m2_to_f32(0x7c));
+/// ```
+pub fn fp8_e5m2_to_f32(byte: u8) -> f32 {
+    match byte & 0x7f {
+        0x7c => if byte & 0x80 != 0 { f32::NEG_INFINITY } else { f32::INFINITY },
+        0x7d..=0x7f => f32::NAN,
+        _ => decode(byte, 2, 15),
+    }
+}
+
+/// Converts an `f32` to E5M2, rounding to nearest with ties to even.
+///
+/// Values that round beyond ±57344 become infinities, like in IEEE 754.
+pub fn f32_to_fp8_e5m2(value: f32) -> u8 {
+    if value.is_nan() {
+        return 0x7e;
+    }
+    match encode(value, 2, 15) {
+        code if code >= 0x7c => sign(value) | 0x7c,
+        code => sign(value) | code as u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn e4m3_values() {
+        assert_eq!(0.0, fp8_e4m3_to_f32(0x00));
+        assert!(fp8_e4m3_to_f32(0x80).is_sign_negative());
+        assert_eq!(1.0, fp8_e4m3_to_f32(0x38));
+        assert_eq!(448.0, fp8_e4m3_to_f32(0x7e));
+        // Smallest subnormal and largest subnormal.
+        assert_eq!(2f32.powi(-9), fp8_e4m3_to_f32(0x01));
+        assert_eq!(7.0 * 2f32.powi(-9), fp8_e4m3_to_f32(0x07));
+        assert!(fp8_e4m3_to_f32(0xff).is_nan());
+    }
+
+    #[test]
+    fn e5m2_values() {
+        assert_eq!(1.0, fp8_e5m2_to_f32(0x3c));
+        assert_eq!(57344.0, fp8_e5m2_to_f32(0x7b));
+        assert_eq!(2f32.powi(-16), fp8_e5m2_to_f32(0x01));
+        assert_eq!(f32::NEG_INFINITY, fp8_e5m2_to_f32(0xfc));
+        assert!(fp8_e5m2_to_f32(0x7d).is_nan());
+        assert!(fp8_e5m2_to_f32(0xff).is_nan());
+    }
+
+    #[test]
+    fn round_trip() {
+        for byte in 0..=255u8 {
+            let value = fp8_e4m3_to_f32(byte);
+            if !value.is_nan() {
+                assert_eq!(byte, f32_to_fp8_e4m3(value));
+            }
+            let value = fp8_e5m2_to_f32(byte);
+            if !value.is_nan() {
+                assert_eq!(byte, f32_to_fp8_e5m2(value));
+            }
+        }
+    }
+
+    #[test]
+    fn rounding() {
+        // 1.0625 is halfway between 1.0 (0x38) and 1.125 (0x39): ties to even.
+        assert_eq!(0x38, f32_to_fp8_e4m3(1.0625));
+        assert_eq!(0x3a, f32_to_fp8_e4m3(1.1875));
+        assert_eq!(0x39, f32_to_fp8_e4m3(1.07));
+        // Rounding up carries into the exponent.
+        assert_eq!(0x40, f32_to_fp8_e4m3(1.99));
+        // Subnormals, and a value rounding up to the smallest normal number.
+        assert_eq!(0x01, f32_to_fp8_e4m3(2f32.powi(-9) * 1.4));
+        assert_eq!(0x00, f32_to_fp8_e4m3(2f32.powi(-10)));
+        assert_eq!(0x02, f32_to_fp8_e4m3(2f32.powi(-9) * 1.5));
+        assert_eq!(0x08, f32_to_fp8_e4m3(7.9 * 2f32.powi(-9)));
+        assert_eq!(0x80, f32_to_fp8_e4m3(-1e-30));
+        assert_eq!(0x01, f32_to_fp8_e5m2(2f32.powi(-16) * 0.75));
+    }
+
+    #[test]
+    fn overflow() {
+        // 464 is halfway between 448 and the NaN code and rounds to even.
+        assert_eq!(0x7e, f32_to_fp8_e4m3(464.0));
+        assert_eq!(0x7f, f32_to_fp8_e4m3(465.0));
+        assert_eq!(0xff, f32_to_fp8_e4m3(f32::NEG_INFINITY));
+        assert_eq!(0x7f, f32_to_fp8_e4m3(f32::NAN));
+        assert_eq!(0x7b, f32_to_fp8_e5m2(61439.0));
+        assert_eq!(0x7c, f32_to_fp8_e5m2(61440.0));
+        assert_eq!(0xfc, f32_to_fp8_e5m2(-1e30));
+        assert_eq!(0x7c, f32_to_fp8_e5m2(f32::INFINITY));
+        assert!(fp8_e5m2_to_f32(f32_to_fp8_e5m2(f32::NAN)).is_nan());
+    }
+}
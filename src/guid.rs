@@ -0,0 +1,110 @@
+//! UUIDs in the RFC 4122 layout and in the mixed-endian layout of Microsoft GUIDs.
+
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+use {EndiannessError, EndiannessResult, read_array};
+
+/// How the fields of a UUID are stored in a stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UuidLayout {
+    /// All fields are big-endian, as RFC 4122 specifies: the bytes are stored in the order
+    /// in which the UUID is written out.
+    Rfc4122,
+    /// The first three fields (`time_low`, `time_mid` and `time_hi_and_version`) are
+    /// little-endian, as in the `GUID` structure of Windows, COM and EFI.
+    Guid,
+}
+
+// Reverses the first three fields if the layout stores them little-endian. The conversion
+// is its own inverse, so it serves both reading and writing.
+fn reorder(mut bytes: [u8; 16], layout: UuidLayout) -> [u8; 16] {
+    if layout == UuidLayout::Guid {
+        bytes[..4].reverse();
+        bytes[4..6].reverse();
+        bytes[6..8].reverse();
+    }
+    bytes
+}
+
+/// Reads a UUID stored in `layout` and returns its bytes in RFC 4122 order.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // {00112233-4455-6677-8899-aabbccddeeff} as a GUID
+/// let v = [0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66,
+///          0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+/// let bytes = read_uuid_bytes(&v, UuidLayout::Guid).unwrap();
+/// assert_eq!([0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77], bytes[..8]);
+///
+/// let mut rfc = [0u8; 16];
+/// write_uuid_bytes(&mut rfc, bytes, UuidLayout::Rfc4122).unwrap();
+/// assert_eq!(bytes, rfc);
+/// ```
+pub fn read_uuid_bytes(data: &[u8], layout: UuidLayout) -> EndiannessResult<[u8; 16]> {
+    Ok(reorder(read_array::<16>(data)?, layout))
+}
+
+/// Writes a UUID given by its bytes in RFC 4122 order, in `layout`.
+pub fn write_uuid_bytes(data: &mut [u8], bytes: [u8; 16], layout: UuidLayout) -> EndiannessResult<()> {
+    match data.get_mut(..16) {
+        Some(dst) => {
+            dst.copy_from_slice(&reorder(bytes, layout));
+            Ok(())
+        }
+        None => Err(EndiannessError::ShortSlice { expected: 16, found: data.len() }),
+    }
+}
+
+/// Reads a UUID stored in `layout`.
+///
+/// Requires the `uuid` feature.
+#[cfg(feature = "uuid")]
+pub fn read_uuid(data: &[u8], layout: UuidLayout) -> EndiannessResult<Uuid> {
+    Ok(Uuid::from_bytes(read_uuid_bytes(data, layout)?))
+}
+
+/// Writes a UUID in `layout`.
+///
+/// Requires the `uuid` feature.
+#[cfg(feature = "uuid")]
+pub fn write_uuid(data: &mut [u8], value: Uuid, layout: UuidLayout) -> EndiannessResult<()> {
+    write_uuid_bytes(data, *value.as_bytes(), layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC: [u8; 16] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+                           0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+    const GUID: [u8; 16] = [0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66,
+                            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+
+    #[test]
+    fn bytes() {
+        assert_eq!(RFC, read_uuid_bytes(&RFC, UuidLayout::Rfc4122).unwrap());
+        assert_eq!(RFC, read_uuid_bytes(&GUID, UuidLayout::Guid).unwrap());
+        let mut v = [0u8; 17];
+        write_uuid_bytes(&mut v, RFC, UuidLayout::Guid).unwrap();
+        assert_eq!(GUID, v[..16]);
+        write_uuid_bytes(&mut v, RFC, UuidLayout::Rfc4122).unwrap();
+        assert_eq!(RFC, v[..16]);
+        assert_eq!(EndiannessError::ShortSlice { expected: 16, found: 15 },
+                   read_uuid_bytes(&RFC[1..], UuidLayout::Guid).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 16, found: 15 },
+                   write_uuid_bytes(&mut v[..15], RFC, UuidLayout::Guid).unwrap_err());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn values() {
+        let uuid = Uuid::from_bytes(RFC);
+        assert_eq!(uuid, read_uuid(&GUID, UuidLayout::Guid).unwrap());
+        assert_eq!(Uuid::from_bytes_le(GUID), read_uuid(&GUID, UuidLayout::Guid).unwrap());
+        let mut v = [0u8; 16];
+        write_uuid(&mut v, uuid, UuidLayout::Guid).unwrap();
+        assert_eq!(GUID, v);
+    }
+}
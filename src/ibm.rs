@@ -0,0 +1,92 @@
+//! IBM System/360 hexadecimal floating point numbers, as found in SEG-Y files and
+//! mainframe exports.
+//!
+//! The format has a sign bit, a 7-bit exponent of 16 with a bias of 64 and a fraction
+//! of 24 (single) or 56 (double) bits without a hidden bit: the value is
+//! `(-1)^sign * 0.fraction * 16^(exponent - 64)`.
+
+use {ByteOrder, EndiannessResult};
+use {pow2, read_u32, read_u64};
+
+// Exact value of a fraction with the given number of bits and the 7-bit exponent,
+// as long as the fraction fits into the f64 mantissa.
+fn scale(fraction: f64, bits: i32, exponent: u64) -> f64 {
+    fraction * pow2(4 * (exponent as i32 - 64) - bits)
+}
+
+/// Reads an IBM single-precision hexadecimal float and converts it to IEEE 754.
+///
+/// The conversion rounds to the nearest `f32`, ties to even. IBM values above
+/// `f32::MAX` become infinity and values too small for `f32` become subnormal numbers
+/// or zero. Unnormalized fractions are accepted.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(-118.625, read_ibm_f32(&[0xc2, 0x76, 0xa0, 0x00], ByteOrder::BigEndian).unwrap());
+/// ```
+pub fn read_ibm_f32(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f32> {
+    let bits = read_u32(data, endianness)?;
+    let exponent = u64::from((bits >> 24) & 0x7f);
+    // 24 bits fit into f64 exactly, so there is only one rounding.
+    let value = scale(f64::from(bits & 0x00ff_ffff), 24, exponent) as f32;
+    Ok(if bits >> 31 == 1 { -value } else { value })
+}
+
+/// Reads an IBM double-precision hexadecimal float and converts it to IEEE 754.
+///
+/// The 56-bit fraction is rounded to the 53-bit `f64` mantissa, to nearest with ties
+/// to even. The whole IBM range fits into `f64`, so there is no overflow or underflow.
+pub fn read_ibm_f64(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f64> {
+    let bits = read_u64(data, endianness)?;
+    let value = scale((bits & 0x00ff_ffff_ffff_ffff) as f64, 56, (bits >> 56) & 0x7f);
+    Ok(if bits >> 63 == 1 { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError};
+    use super::*;
+
+    #[test]
+    fn single() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(1.0, read_ibm_f32(&[0x41, 0x10, 0x00, 0x00], order).unwrap());
+        assert_eq!(0.15625, read_ibm_f32(&[0x40, 0x28, 0x00, 0x00], order).unwrap());
+        assert_eq!(-0.5, read_ibm_f32(&[0x00, 0x00, 0x80, 0xc0], ByteOrder::LittleEndian).unwrap());
+        assert_eq!(0.0, read_ibm_f32(&[0; 4], order).unwrap());
+        // Unnormalized: 0x0.01 * 16^2 is 1.
+        assert_eq!(1.0, read_ibm_f32(&[0x42, 0x01, 0x00, 0x00], order).unwrap());
+    }
+
+    #[test]
+    fn single_range() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(f32::INFINITY, read_ibm_f32(&[0x7f, 0xff, 0xff, 0xff], order).unwrap());
+        assert_eq!(f32::NEG_INFINITY, read_ibm_f32(&[0xff, 0xff, 0xff, 0xff], order).unwrap());
+        assert_eq!(0.0, read_ibm_f32(&[0x00, 0x10, 0x00, 0x00], order).unwrap());
+        assert_eq!(1.0 - 1.0 / 16777216.0, read_ibm_f32(&[0x40, 0xff, 0xff, 0xff], order).unwrap());
+        // 0x0.ffffff * 16^-32 is below f32::MIN_POSITIVE and rounds to 2^-128.
+        assert_eq!(f32::from_bits(1 << 21), read_ibm_f32(&[0x20, 0xff, 0xff, 0xff], order).unwrap());
+    }
+
+    #[test]
+    fn double() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(1.0, read_ibm_f64(&[0x41, 0x10, 0, 0, 0, 0, 0, 0], order).unwrap());
+        assert_eq!(-118.625, read_ibm_f64(&[0xc2, 0x76, 0xa0, 0, 0, 0, 0, 0], order).unwrap());
+        assert_eq!(0.1, read_ibm_f64(&[0x40, 0x19, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a], order)
+                            .unwrap());
+        // The largest value is just below 16^63.
+        assert_eq!(2f64.powi(252), read_ibm_f64(&[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], order)
+                                        .unwrap());
+    }
+
+    #[test]
+    fn short_slice() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_ibm_f32(&[0; 3], ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   read_ibm_f64(&[0; 7], ByteOrder::BigEndian).unwrap_err());
+    }
+}
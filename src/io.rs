@@ -0,0 +1,279 @@
+//! Integration with `std::io` readers and writers.
+
+use std::io;
+
+use {ByteOrder, EndianRead, EndianWrite, EndiannessError, EndiannessResult};
+
+// Fills `buf` like `read_exact`, but reports how many bytes there were if the stream
+// ends early.
+fn fill<R: io::Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> EndiannessResult<()> {
+    let mut found = 0;
+    while found < buf.len() {
+        match reader.read(&mut buf[found..]) {
+            Ok(0) => return Err(EndiannessError::ShortSlice { expected: buf.len(), found }),
+            Ok(n) => found += n,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Extends any `std::io::Read` with methods that read numbers in a given byte order.
+///
+/// I/O errors are converted to `EndiannessError::Io`, except for reaching the end of
+/// the stream before a value is complete, which is reported as `EndiannessError::ShortSlice`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut reader: &[u8] = &[0, 128, 128, 0];
+/// assert_eq!(128, reader.read_u16_endian(ByteOrder::BigEndian).unwrap());
+/// assert_eq!(128, reader.read_u16_endian(ByteOrder::LittleEndian).unwrap());
+/// assert!(reader.read_u8_endian(ByteOrder::BigEndian).is_err());
+/// ```
+pub trait ReadEndian: io::Read {
+    /// Reads a value of any supported type.
+    fn read_endian<T: EndianRead>(&mut self, endianness: ByteOrder) -> EndiannessResult<T> {
+        let mut buf = [0u8; 16];
+        if T::SIZE <= buf.len() {
+            fill(self, &mut buf[..T::SIZE])?;
+            T::read(&buf, endianness)
+        } else {
+            let mut buf = vec![0u8; T::SIZE];
+            fill(self, &mut buf)?;
+            T::read(&buf, endianness)
+        }
+    }
+
+    /// Reads unsigned 8-bit integer.
+    fn read_u8_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u8> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 8-bit integer.
+    fn read_i8_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i8> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 16-bit integer.
+    fn read_u16_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u16> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 16-bit integer.
+    fn read_i16_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i16> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 32-bit integer.
+    fn read_u32_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u32> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 32-bit integer.
+    fn read_i32_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i32> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 64-bit integer.
+    fn read_u64_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u64> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 64-bit integer.
+    fn read_i64_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i64> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads unsigned 128-bit integer.
+    fn read_u128_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<u128> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads signed 128-bit integer.
+    fn read_i128_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<i128> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads a single-precision floating point number.
+    fn read_f32_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<f32> {
+        self.read_endian(endianness)
+    }
+
+    /// Reads a double-precision floating point number.
+    fn read_f64_endian(&mut self, endianness: ByteOrder) -> EndiannessResult<f64> {
+        self.read_endian(endianness)
+    }
+}
+
+impl<R: io::Read + ?Sized> ReadEndian for R {}
+
+/// Extends any `std::io::Write` with methods that write numbers in a given byte order.
+///
+/// I/O errors are converted the same way as for `ReadEndian`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut v = Vec::new();
+/// v.write_u16_endian(128, ByteOrder::BigEndian).unwrap();
+/// v.write_i16_endian(-32768, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!(vec![0, 128, 0, 128], v);
+/// ```
+pub trait WriteEndian: io::Write {
+    /// Writes a value of any supported type.
+    fn write_endian<T: EndianWrite>(&mut self, value: T, endianness: ByteOrder) -> EndiannessResult<()> {
+        let len = value.encoded_len();
+        let mut buf = [0u8; 16];
+        if len <= buf.len() {
+            value.write(&mut buf, endianness)?;
+            self.write_all(&buf[..len])?;
+        } else {
+            let mut buf = vec![0u8; len];
+            value.write(&mut buf, endianness)?;
+            self.write_all(&buf)?;
+        }
+        Ok(())
+    }
+
+    /// Writes unsigned 8-bit integer.
+    fn write_u8_endian(&mut self, value: u8, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 8-bit integer.
+    fn write_i8_endian(&mut self, value: i8, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 16-bit integer.
+    fn write_u16_endian(&mut self, value: u16, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 16-bit integer.
+    fn write_i16_endian(&mut self, value: i16, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 32-bit integer.
+    fn write_u32_endian(&mut self, value: u32, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 32-bit integer.
+    fn write_i32_endian(&mut self, value: i32, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 64-bit integer.
+    fn write_u64_endian(&mut self, value: u64, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 64-bit integer.
+    fn write_i64_endian(&mut self, value: i64, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes unsigned 128-bit integer.
+    fn write_u128_endian(&mut self, value: u128, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes signed 128-bit integer.
+    fn write_i128_endian(&mut self, value: i128, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes a single-precision floating point number.
+    fn write_f32_endian(&mut self, value: f32, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+
+    /// Writes a double-precision floating point number.
+    fn write_f64_endian(&mut self, value: f64, endianness: ByteOrder) -> EndiannessResult<()> {
+        self.write_endian(value, endianness)
+    }
+}
+
+impl<W: io::Write + ?Sized> WriteEndian for W {}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use {ByteOrder, EndiannessError, ReadEndian, WriteEndian};
+
+    struct Failing;
+
+    impl io::Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "failing"))
+        }
+    }
+
+    impl io::Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "failing"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reads_consume_stream() {
+        let mut reader = io::Cursor::new(vec![0, 0, 0, 1, 194, 255, 0, 0, 255]);
+        assert_eq!(1, reader.read_u32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-127.5, reader.read_f32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-1, reader.read_i8_endian(ByteOrder::LittleEndian).unwrap());
+        assert_eq!(9, reader.position());
+    }
+
+    #[test]
+    fn short_read() {
+        let mut reader: &[u8] = &[1, 2, 3];
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 3 },
+                   reader.read_u64_endian(ByteOrder::LittleEndian).unwrap_err());
+    }
+
+    #[test]
+    fn io_error() {
+        assert_eq!(EndiannessError::Io(io::ErrorKind::BrokenPipe),
+                   Failing.read_u16_endian(ByteOrder::BigEndian).unwrap_err());
+    }
+
+    #[test]
+    fn writes_append() {
+        let mut v = Vec::new();
+        v.write_u32_endian(1, ByteOrder::BigEndian).unwrap();
+        v.write_f32_endian(-127.5, ByteOrder::BigEndian).unwrap();
+        v.write_i8_endian(-1, ByteOrder::LittleEndian).unwrap();
+        v.write_u128_endian(1, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(25, v.len());
+        assert_eq!(&[0, 0, 0, 1, 194, 255, 0, 0, 255, 1], &v[..10]);
+
+        let mut reader: &[u8] = &v;
+        assert_eq!(1, reader.read_u32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-127.5, reader.read_f32_endian(ByteOrder::BigEndian).unwrap());
+        assert_eq!(-1, reader.read_i8_endian(ByteOrder::LittleEndian).unwrap());
+        assert_eq!(1, reader.read_u128_endian(ByteOrder::LittleEndian).unwrap());
+    }
+
+    #[test]
+    fn short_write() {
+        let mut v = [0u8; 3];
+        let mut writer: &mut [u8] = &mut v;
+        assert_eq!(EndiannessError::Io(io::ErrorKind::WriteZero),
+                   writer.write_u32_endian(1, ByteOrder::BigEndian).unwrap_err());
+    }
+
+    #[test]
+    fn write_io_error() {
+        assert_eq!(EndiannessError::Io(io::ErrorKind::BrokenPipe),
+                   Failing.write_u16_endian(1, ByteOrder::BigEndian).unwrap_err());
+    }
+}
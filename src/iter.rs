@@ -0,0 +1,216 @@
+//! Lazy iterators over the numbers stored in a stream of bytes.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice::ChunksExact;
+
+use {ByteOrder, EndianRead, EndiannessError, EndiannessResult};
+
+/// An iterator that reads the values of a stream one at a time, without converting the
+/// whole stream up front. Trailing bytes that do not form a whole value are not yielded
+/// and are available from `remainder`.
+///
+/// Reading a complete value of a numeric type cannot fail. For other types, the iterator
+/// panics if `EndianRead::read` rejects a value.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0, 1, 0, 2, 9];
+/// let mut values = iter_u16(&v, ByteOrder::BigEndian);
+/// assert_eq!(2, values.len());
+/// assert_eq!(vec![1, 2], values.by_ref().collect::<Vec<_>>());
+/// assert_eq!(&[9], values.remainder());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Values<'a, T> {
+    chunks: ChunksExact<'a, u8>,
+    endianness: ByteOrder,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: EndianRead> Values<'a, T> {
+    /// Returns the trailing bytes that do not form a whole value.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.chunks.remainder()
+    }
+
+    fn read(&self, chunk: &[u8]) -> T {
+        match T::read(chunk, self.endianness) {
+            Ok(value) => value,
+            Err(err) => panic!("cannot read a value from a complete chunk: {}", err),
+        }
+    }
+}
+
+impl<'a, T: EndianRead> Iterator for Values<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.chunks.next().map(|chunk| self.read(chunk))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.chunks.nth(n).map(|chunk| self.read(chunk))
+    }
+}
+
+impl<'a, T: EndianRead> DoubleEndedIterator for Values<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.chunks.next_back().map(|chunk| self.read(chunk))
+    }
+}
+
+impl<'a, T: EndianRead> ExactSizeIterator for Values<'a, T> {}
+
+impl<'a, T: EndianRead> FusedIterator for Values<'a, T> {}
+
+/// Returns an iterator over the values of any supported type stored in `data`.
+///
+/// # Panics
+///
+/// Panics if `T` is a zero-sized type.
+pub fn iter<T: EndianRead>(data: &[u8], endianness: ByteOrder) -> Values<'_, T> {
+    Values {
+        chunks: data.chunks_exact(T::SIZE),
+        endianness,
+        value: PhantomData,
+    }
+}
+
+macro_rules! iter_values {
+    ($($name:ident, $ty:ident, $desc:expr;)*) => (
+        $(
+            #[doc = concat!("Returns an iterator over the ", $desc, "s stored in `data`.")]
+            pub fn $name(data: &[u8], endianness: ByteOrder) -> Values<'_, $ty> {
+                iter(data, endianness)
+            }
+        )*
+    );
+}
+
+iter_values! {
+    iter_u8, u8, "unsigned 8-bit integer";
+    iter_i8, i8, "signed 8-bit integer";
+    iter_u16, u16, "unsigned 16-bit integer";
+    iter_i16, i16, "signed 16-bit integer";
+    iter_u32, u32, "unsigned 32-bit integer";
+    iter_i32, i32, "signed 32-bit integer";
+    iter_u64, u64, "unsigned 64-bit integer";
+    iter_i64, i64, "signed 64-bit integer";
+    iter_u128, u128, "unsigned 128-bit integer";
+    iter_i128, i128, "signed 128-bit integer";
+    iter_f32, f32, "single-precision floating point number";
+    iter_f64, f64, "double-precision floating point number";
+}
+
+/// An iterator that splits a stream into records of a fixed size and parses each of them.
+///
+/// If the stream does not end on a record boundary, the last item is a `ShortSlice` error
+/// for the partial record. Parsing continues after records that fail to parse.
+#[derive(Debug, Clone)]
+pub struct Records<'a, T, F> {
+    chunks: ChunksExact<'a, u8>,
+    size: usize,
+    parse: F,
+    // Set once the error for a partial record has been returned.
+    finished: bool,
+    record: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, F: FnMut(&'a [u8]) -> EndiannessResult<T>> Iterator for Records<'a, T, F> {
+    type Item = EndiannessResult<T>;
+
+    fn next(&mut self) -> Option<EndiannessResult<T>> {
+        if let Some(chunk) = self.chunks.next() {
+            return Some((self.parse)(chunk));
+        }
+        let rest = self.chunks.remainder();
+        if self.finished || rest.is_empty() {
+            return None;
+        }
+        self.finished = true;
+        Some(Err(EndiannessError::ShortSlice { expected: self.size, found: rest.len() }))
+    }
+}
+
+/// Returns an iterator that hands every `size`-byte record of `data` to `parse`, such as
+/// `|r| Header::read(r, order)` for a type with `#[derive(EndianRead)]`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // Records of a 16-bit id followed by an 8-bit flag.
+/// let v = [0, 1, 1, 0, 2, 0, 0];
+/// let mut ids = records(&v, 3, |r| Ok((read_u16(r, ByteOrder::BigEndian)?, r[2] != 0)));
+/// assert_eq!(Some(Ok((1, true))), ids.next());
+/// assert_eq!(Some(Ok((2, false))), ids.next());
+/// assert_eq!(Some(Err(EndiannessError::ShortSlice { expected: 3, found: 1 })), ids.next());
+/// assert_eq!(None, ids.next());
+/// ```
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+pub fn records<'a, T, F: FnMut(&'a [u8]) -> EndiannessResult<T>>(data: &'a [u8], size: usize, parse: F)
+                                                                   -> Records<'a, T, F> {
+    Records {
+        chunks: data.chunks_exact(size),
+        size,
+        parse,
+        finished: false,
+        record: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError, EndiannessResult, iter, iter_f32, iter_i16, iter_u32, read_u16, records};
+
+    #[test]
+    fn values_in_order() {
+        let v = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
+        let values: Vec<u32> = iter_u32(&v, ByteOrder::BigEndian).collect();
+        assert_eq!(vec![1, 2, 3], values);
+        let values: Vec<u32> = iter_u32(&v, ByteOrder::LittleEndian).rev().collect();
+        assert_eq!(vec![0x03000000, 0x02000000, 0x01000000], values);
+        assert_eq!(Some(-127.5), iter_f32(&[194, 255, 0, 0], ByteOrder::BigEndian).next());
+    }
+
+    #[test]
+    fn size_and_remainder() {
+        let v = [1, 0, 2, 0, 3, 0, 4];
+        let mut values = iter_i16(&v, ByteOrder::LittleEndian);
+        assert_eq!(3, values.len());
+        assert_eq!(Some(2), values.nth(1));
+        assert_eq!(1, values.len());
+        assert_eq!(&[4], values.remainder());
+        assert_eq!(Some(3), values.next());
+        assert_eq!(None, values.next());
+        assert_eq!(0, iter::<u64>(&v, ByteOrder::BigEndian).len());
+    }
+
+    #[test]
+    fn records_with_errors() {
+        let v = [0, 1, 0xff, 0xff, 0, 3, 0];
+        let parse = |r: &[u8]| -> EndiannessResult<u16> {
+            match read_u16(r, ByteOrder::BigEndian)? {
+                0xffff => Err(EndiannessError::Overflow),
+                n => Ok(n),
+            }
+        };
+        let all: Vec<_> = records(&v, 2, parse).collect();
+        assert_eq!(vec![Ok(1), Err(EndiannessError::Overflow), Ok(3),
+                        Err(EndiannessError::ShortSlice { expected: 2, found: 1 })],
+                   all);
+        assert_eq!(2, records(&v[..6], 3, |r| Ok(r[0])).count());
+    }
+}
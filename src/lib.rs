@@ -1,4 +1,4 @@
-//! This crate provides functions to read numbers from a stream of bytes
+//! This crate provides functions to read and write numbers from/to a stream of bytes
 //! either in big-endian or little-endian. Functions return Result type
 //! instead of panic!.
 //!
@@ -32,174 +32,1321 @@
 //! assert_eq!(-127.5, read_f32(&v[0..4], ByteOrder::BigEndian).unwrap());
 //! ```
 //!
+//! Write an unsigned 32-bit integer:
+//! ```rust
+//! use endianness::*;
+//!
+//! let mut v = [0u8; 4];
+//! write_u32(&mut v, 8421376, ByteOrder::LittleEndian).unwrap();
+//! assert_eq!([0, 128, 128, 0], v);
+//! ```
+//!
+//! Decode a lookup table at compile time:
+//! ```rust
+//! use endianness::*;
+//!
+//! const HEADER: &[u8; 4] = &[0, 0, 1, 0];
+//! const SIZE: u32 = read_u32_be(HEADER);
+//! assert_eq!(256, SIZE);
+//! ```
+//!
+//! # Features
+//!
+//! * `std` (enabled by default) — implements `std::error::Error` for the error type and
+//!   provides the `std::io` extension traits and the readers of `std::net` addresses.
+//!   Without it the crate only depends on `core`.
+//! * `simd` — uses SIMD shuffles for the bulk conversion and swapping functions.
+//!   With `std` the AVX2, SSSE3 or NEON kernels are selected at runtime; without it
+//!   they are selected by the target features enabled at compile time.
+//! * `derive` — provides `#[derive(EndianRead, EndianWrite, SwapBytes)]` for structs
+//!   whose fields are read and written in declaration order.
+//! * `half` — reads and writes `half::f16` values with `read_f16`/`write_f16`.
+//! * `bytemuck` — implements `Pod` and `Zeroable` for the wrapper types such as `U32Be`.
+//! * `rayon` — adds `par_read_*_into` functions that split bulk conversions across threads.
+//! * `zerocopy` — derives the zerocopy traits (`FromBytes`, `IntoBytes`, `Unaligned`, …)
+//!   for the wrapper types.
+//! * `arbitrary` — implements `Arbitrary` for `ByteOrder`, so fuzz targets can pick the byte
+//!   order of the parser under test.
+//! * `async` — provides `async_io::AsyncReadEndian`, the counterpart of `ReadEndian` for the
+//!   `futures` `AsyncRead` trait.
+//! * `bytes` — provides `BufEndian` and `BufMutEndian`, which add `get_*_endian` and
+//!   `put_*_endian` methods to the `Buf` and `BufMut` traits of the `bytes` crate.
+//! * `codec` — provides `LengthPrefixCodec`, a tokio-util `Decoder` and `Encoder` for messages
+//!   framed by a length prefix of configurable width and byte order.
+//! * `defmt` — implements `defmt::Format` for `ByteOrder` and the error type, so firmware can
+//!   log them without `core::fmt`.
+//! * `embedded-io` — provides extension traits over the `embedded_io::Read` and `Write` traits
+//!   in the `embedded` module. `embedded-io-async` is not supported.
+//! * `testing` — provides proptest strategies in the `testing` module, for property tests
+//!   of parsers built on this crate.
+//! * `uuid` — reads and writes `uuid::Uuid` values with `read_uuid`/`write_uuid`, in the
+//!   RFC 4122 or the Microsoft GUID layout.
+//! * `serde` — implements `Serialize` and `Deserialize` for `ByteOrder` and the error type,
+//!   and provides `from_bytes` and `to_bytes`, a serde data format with fixed-width numbers
+//!   in a given byte order.
+//!
 
 #![crate_name = "endianness"]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 #![deny(missing_docs, missing_debug_implementations,
         missing_copy_implementations, trivial_casts, trivial_numeric_casts,
-        unused_extern_crates, unused_import_braces, unused_qualifications)]
+        unsafe_code, unused_extern_crates, unused_import_braces, unused_qualifications)]
+
+#[cfg(any(feature = "std", test))]
+extern crate core;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "embedded-io")]
+extern crate embedded_io;
+#[cfg(feature = "derive")]
+extern crate endianness_derive;
+#[cfg(feature = "async")]
+extern crate futures_io;
+#[cfg(feature = "half")]
+extern crate half;
+#[cfg(feature = "testing")]
+extern crate proptest;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "codec")]
+extern crate tokio_util;
+#[cfg(feature = "uuid")]
+extern crate uuid;
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
 
-use std::mem;
-use std::fmt;
+use core::convert::{TryFrom, TryInto};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
 use std::error;
+#[cfg(feature = "std")]
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+
+#[cfg(feature = "async")]
+pub mod async_io;
+mod bcd;
+mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
+mod bulk;
+mod can;
+#[cfg(feature = "codec")]
+mod codec;
+mod cursor;
+mod detect;
+mod dynamic;
+#[cfg(feature = "embedded-io")]
+pub mod embedded;
+mod f80;
+mod fixed;
+mod float16;
+mod fp8;
+mod guid;
+mod ibm;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+mod mac;
+#[cfg(feature = "std")]
+mod net;
+mod ntp;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pdp;
+mod prefixed;
+mod qformat;
+#[cfg(feature = "std")]
+mod schema;
+mod simd;
+mod split;
+mod stream;
+mod strings;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod traits;
+mod varint;
+mod vax;
+mod view;
+#[cfg(feature = "serde")]
+mod wire;
+mod wrappers;
+mod writer;
+
+pub use bcd::{read_bcd, write_bcd};
+pub use bits::{BitBuffer, BitReader, BitWriter};
+#[cfg(feature = "bytes")]
+pub use buf::{BufEndian, BufMutEndian};
+pub use bulk::*;
+pub use can::*;
+#[cfg(feature = "codec")]
+pub use codec::LengthPrefixCodec;
+pub use cursor::{ByteCursor, Checkpoint, CursorError, CursorResult, SplitCursor};
+pub use detect::{detect_bom, detect_elf, detect_ii_mm, detect_mach_o};
+pub use dynamic::{Scalar, ScalarType, gather};
+#[cfg(feature = "derive")]
+pub use endianness_derive::{EndianRead, EndianWrite, SwapBytes};
+pub use f80::read_f80;
+pub use fixed::*;
+pub use float16::{read_f16_bits, write_f16_bits};
+#[cfg(feature = "half")]
+pub use float16::{read_f16, write_f16};
+pub use fp8::{f32_to_fp8_e4m3, f32_to_fp8_e5m2, fp8_e4m3_to_f32, fp8_e5m2_to_f32};
+pub use guid::{UuidLayout, read_uuid_bytes, write_uuid_bytes};
+#[cfg(feature = "uuid")]
+pub use guid::{read_uuid, write_uuid};
+pub use ibm::{read_ibm_f32, read_ibm_f64};
+#[cfg(feature = "std")]
+pub use io::{ReadEndian, WriteEndian};
+pub use iter::*;
+pub use mac::{Eui48, Eui64, read_eui48, read_eui64};
+#[cfg(feature = "std")]
+pub use net::{read_ipv4, read_ipv6, write_ipv4, write_ipv6};
+pub use ntp::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+pub use pdp::*;
+pub use prefixed::read_len_prefixed_bytes;
+#[cfg(feature = "std")]
+pub use prefixed::read_len_prefixed_vec;
+pub use qformat::*;
+#[cfg(feature = "std")]
+pub use schema::{FieldSpec, FieldType, Schema, Value};
+pub use split::{read_chunks, read_split};
+pub use stream::{Decoded, StreamDecoder};
+pub use strings::{read_cstr, read_pascal_str};
+#[cfg(feature = "std")]
+pub use strings::{read_utf16_string, read_utf16_string_lossy, read_utf16_string_with_bom, read_utf32_string};
+pub use traits::{EndianRead, EndianWrite, SwapBytes, read, write};
+pub use varint::*;
+pub use vax::{read_vax_d, read_vax_f, read_vax_g};
+pub use view::{ByteView, cast_slice, cast_slice_mut};
+#[cfg(feature = "serde")]
+pub use wire::{WireError, WireResult, from_bytes};
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use wire::to_bytes;
+pub use wrappers::*;
+pub use writer::ByteWriter;
 
 /// The 'ByteOrder' type. It represents the order of bytes in a stream we read from.
-#[derive(Debug, Copy, Clone)]
+///
+/// It can be parsed from `"big"`, `"be"`, `"big-endian"` and the same forms for little-endian,
+/// in any case, which is handy for configuration files and command line flags.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(ByteOrder::BigEndian, "be".parse().unwrap());
+/// assert_eq!(ByteOrder::LittleEndian, "Little".parse().unwrap());
+/// assert_eq!("big-endian", ByteOrder::BigEndian.to_string());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ByteOrder {
     /// Intel byte order
     LittleEndian,
-    /// Motorola byte order
+    /// Motorola byte order, which is also the network byte order
     BigEndian,
 }
 
+impl ByteOrder {
+    /// The byte order of the target platform, resolved at compile time.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let v = 8421376u32.to_ne_bytes();
+    /// assert_eq!(8421376, read_u32(&v, ByteOrder::NATIVE).unwrap());
+    /// ```
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: ByteOrder = ByteOrder::LittleEndian;
+
+    /// The byte order of the target platform, resolved at compile time.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: ByteOrder = ByteOrder::BigEndian;
+
+    /// The network byte order used by Internet protocols (RFC 1700).
+    ///
+    /// It is guaranteed to be `BigEndian` on every platform.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let port = [0x1f, 0x90];
+    /// assert_eq!(8080, read_u16(&port, ByteOrder::NETWORK).unwrap());
+    /// ```
+    pub const NETWORK: ByteOrder = ByteOrder::BigEndian;
+
+    /// Returns the byte order of the target platform, the same as `NATIVE`.
+    pub const fn native() -> ByteOrder {
+        ByteOrder::NATIVE
+    }
+
+    /// Returns true if the byte order is the one of the target platform, so that reading
+    /// and writing in it needs no byte swapping.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// assert!(ByteOrder::native().is_native());
+    /// assert_eq!(cfg!(target_endian = "big"), ByteOrder::NETWORK.is_native());
+    /// ```
+    pub const fn is_native(self) -> bool {
+        match self {
+            ByteOrder::BigEndian => cfg!(target_endian = "big"),
+            ByteOrder::LittleEndian => cfg!(target_endian = "little"),
+        }
+    }
+}
+
+impl fmt::Display for ByteOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ByteOrder::LittleEndian => f.write_str("little-endian"),
+            ByteOrder::BigEndian => f.write_str("big-endian"),
+        }
+    }
+}
+
+impl FromStr for ByteOrder {
+    type Err = ParseByteOrderError;
+
+    fn from_str(s: &str) -> Result<ByteOrder, ParseByteOrderError> {
+        const LITTLE: [&str; 3] = ["little", "le", "little-endian"];
+        const BIG: [&str; 3] = ["big", "be", "big-endian"];
+        if LITTLE.iter().any(|name| name.eq_ignore_ascii_case(s)) {
+            Ok(ByteOrder::LittleEndian)
+        } else if BIG.iter().any(|name| name.eq_ignore_ascii_case(s)) {
+            Ok(ByteOrder::BigEndian)
+        } else {
+            Err(ParseByteOrderError(()))
+        }
+    }
+}
+
+/// The error returned when a string does not name a byte order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseByteOrderError(());
+
+impl fmt::Display for ParseByteOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The byte order must be big, be, big-endian, little, le or little-endian.")
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ParseByteOrderError {
+    fn description(&self) -> &str {
+        "The string does not name a byte order."
+    }
+}
+
 /// The error type.
+///
+/// New variants may be added in minor releases, so matches on it need a wildcard arm.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum EndiannessError {
-    /// The stream is too small to read the requested type.
-    ShortSlice,
+    /// The stream is too small to read or write the requested type.
+    ShortSlice {
+        /// The number of bytes the operation needs.
+        expected: usize,
+        /// The number of bytes the stream has.
+        found: usize,
+    },
+    /// The slice must be exactly as long as the requested type, but is not.
+    LengthMismatch {
+        /// The number of bytes the type has.
+        expected: usize,
+        /// The number of bytes the slice has.
+        found: usize,
+    },
+    /// The value does not fit into the requested number of bytes.
+    Overflow,
+    /// The requested number of bytes is not supported by the function.
+    InvalidWidth,
+    /// The slice cannot be viewed as a slice of the requested type because of its
+    /// length or alignment.
+    Misaligned,
+    /// The value is not encoded in the shortest form the format requires.
+    NonCanonical,
+    /// The stream contains a digit that is not valid in its encoding.
+    InvalidDigit,
+    /// The floating point number is infinite or NaN.
+    NonFinite,
+    /// The value does not have the type the layout describes.
+    TypeMismatch,
+    /// The string is not valid UTF-8.
+    InvalidUtf8 {
+        /// The number of bytes before the first invalid sequence.
+        valid_up_to: usize,
+    },
+    /// The string is not valid UTF-16: it has an unpaired surrogate.
+    InvalidUtf16 {
+        /// The number of bytes before the unpaired surrogate.
+        valid_up_to: usize,
+    },
+    /// The string is not valid UTF-32: it has a surrogate or a value above U+10FFFF.
+    InvalidUtf32 {
+        /// The number of bytes before the invalid code unit.
+        valid_up_to: usize,
+    },
+    /// The value is not valid for its type, such as a `char` that is not a Unicode
+    /// scalar value.
+    InvalidData,
+    /// An I/O error other than a premature end of the stream.
+    ///
+    /// With `serde`, the kind is serialized by its name and cannot be deserialized.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_io_kind", skip_deserializing))]
+    Io(IoErrorKind),
 }
 
 impl fmt::Display for EndiannessError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            EndiannessError::ShortSlice => write!(f, "The slice length is too short."),
+            EndiannessError::ShortSlice { expected, found } => {
+                write!(f, "The slice length is too short: expected {} bytes, found {}.", expected, found)
+            }
+            EndiannessError::LengthMismatch { expected, found } => {
+                write!(f, "The slice length does not match: expected {} bytes, found {}.", expected, found)
+            }
+            EndiannessError::Overflow => write!(f, "The value is out of range."),
+            EndiannessError::InvalidWidth => write!(f, "The number of bytes is not supported."),
+            EndiannessError::Misaligned => write!(f, "The slice length or alignment does not match the type."),
+            EndiannessError::NonCanonical => write!(f, "The value is not encoded in its shortest form."),
+            EndiannessError::InvalidDigit => write!(f, "The stream contains an invalid digit."),
+            EndiannessError::NonFinite => write!(f, "The number is infinite or NaN."),
+            EndiannessError::TypeMismatch => write!(f, "The value does not match the type of the layout."),
+            EndiannessError::InvalidUtf8 { valid_up_to } => {
+                write!(f, "The string is not valid UTF-8 after {} bytes.", valid_up_to)
+            }
+            EndiannessError::InvalidUtf16 { valid_up_to } => {
+                write!(f, "The string is not valid UTF-16 after {} bytes.", valid_up_to)
+            }
+            EndiannessError::InvalidUtf32 { valid_up_to } => {
+                write!(f, "The string is not valid UTF-32 after {} bytes.", valid_up_to)
+            }
+            EndiannessError::InvalidData => write!(f, "The value is not valid for its type."),
+            #[cfg(feature = "std")]
+            EndiannessError::Io(kind) => write!(f, "An I/O error occurred: {}.", kind),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl error::Error for EndiannessError {
     fn description(&self) -> &str {
         match *self {
-            EndiannessError::ShortSlice => "The slice length is too short.",
+            EndiannessError::ShortSlice { .. } => "The slice length is too short.",
+            EndiannessError::LengthMismatch { .. } => "The slice length does not match the type.",
+            EndiannessError::Overflow => "The value is out of range.",
+            EndiannessError::InvalidWidth => "The number of bytes is not supported.",
+            EndiannessError::Misaligned => "The slice length or alignment does not match the type.",
+            EndiannessError::NonCanonical => "The value is not encoded in its shortest form.",
+            EndiannessError::InvalidDigit => "The stream contains an invalid digit.",
+            EndiannessError::NonFinite => "The number is infinite or NaN.",
+            EndiannessError::TypeMismatch => "The value does not match the type of the layout.",
+            EndiannessError::InvalidUtf8 { .. } => "The string is not valid UTF-8.",
+            EndiannessError::InvalidUtf16 { .. } => "The string is not valid UTF-16.",
+            EndiannessError::InvalidUtf32 { .. } => "The string is not valid UTF-32.",
+            EndiannessError::InvalidData => "The value is not valid for its type.",
+            EndiannessError::Io(_) => "An I/O error occurred.",
+        }
+    }
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            EndiannessError::ShortSlice { .. } => None,
+            EndiannessError::LengthMismatch { .. } => None,
+            EndiannessError::Overflow => None,
+            EndiannessError::InvalidWidth => None,
+            EndiannessError::Misaligned => None,
+            EndiannessError::NonCanonical => None,
+            EndiannessError::InvalidDigit => None,
+            EndiannessError::NonFinite => None,
+            EndiannessError::TypeMismatch => None,
+            EndiannessError::InvalidUtf8 { .. } => None,
+            EndiannessError::InvalidUtf16 { .. } => None,
+            EndiannessError::InvalidUtf32 { .. } => None,
+            EndiannessError::InvalidData => None,
+            EndiannessError::Io(_) => None,
+        }
+    }
+}
+
+/// Converts an I/O error, mapping a premature end of the stream to `ShortSlice`.
+///
+/// The error does not tell how many bytes were missing, so both lengths are zero.
+/// `ReadEndian` reports the actual lengths instead.
+#[cfg(feature = "std")]
+impl From<IoError> for EndiannessError {
+    fn from(err: IoError) -> EndiannessError {
+        match err.kind() {
+            IoErrorKind::UnexpectedEof => EndiannessError::ShortSlice { expected: 0, found: 0 },
+            kind => EndiannessError::Io(kind),
         }
     }
-    fn cause(&self) -> Option<&error::Error> {
+}
+
+// Written by hand because `io::ErrorKind` does not implement `defmt::Format`.
+#[cfg(feature = "defmt")]
+impl defmt::Format for EndiannessError {
+    fn format(&self, f: defmt::Formatter) {
         match *self {
-            EndiannessError::ShortSlice => None,
+            EndiannessError::ShortSlice { expected, found } => {
+                defmt::write!(f, "ShortSlice {{ expected: {=usize}, found: {=usize} }}", expected, found)
+            }
+            EndiannessError::LengthMismatch { expected, found } => {
+                defmt::write!(f, "LengthMismatch {{ expected: {=usize}, found: {=usize} }}", expected, found)
+            }
+            EndiannessError::Overflow => defmt::write!(f, "Overflow"),
+            EndiannessError::InvalidWidth => defmt::write!(f, "InvalidWidth"),
+            EndiannessError::Misaligned => defmt::write!(f, "Misaligned"),
+            EndiannessError::NonCanonical => defmt::write!(f, "NonCanonical"),
+            EndiannessError::InvalidDigit => defmt::write!(f, "InvalidDigit"),
+            EndiannessError::NonFinite => defmt::write!(f, "NonFinite"),
+            EndiannessError::TypeMismatch => defmt::write!(f, "TypeMismatch"),
+            EndiannessError::InvalidUtf8 { valid_up_to } => {
+                defmt::write!(f, "InvalidUtf8 {{ valid_up_to: {=usize} }}", valid_up_to)
+            }
+            EndiannessError::InvalidUtf16 { valid_up_to } => {
+                defmt::write!(f, "InvalidUtf16 {{ valid_up_to: {=usize} }}", valid_up_to)
+            }
+            EndiannessError::InvalidUtf32 { valid_up_to } => {
+                defmt::write!(f, "InvalidUtf32 {{ valid_up_to: {=usize} }}", valid_up_to)
+            }
+            EndiannessError::InvalidData => defmt::write!(f, "InvalidData"),
+            #[cfg(feature = "std")]
+            EndiannessError::Io(kind) => defmt::write!(f, "Io({})", defmt::Debug2Format(&kind)),
         }
     }
 }
 
+// `io::ErrorKind` has no serde support of its own.
+#[cfg(all(feature = "std", feature = "serde"))]
+fn serialize_io_kind<S: serde::Serializer>(kind: &IoErrorKind, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{:?}", kind))
+}
+
 /// Result type alias that fixes Error parameter.
 pub type EndiannessResult<T> = Result<T, EndiannessError>;
 
+/// Reads the first `N` bytes of the stream as an array, such as a magic number or a hash.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0x89, b'P', b'N', b'G', 0x0d];
+/// assert_eq!([0x89, b'P', b'N', b'G'], read_array::<4>(&v).unwrap());
+/// assert!(read_array::<8>(&v).is_err());
+/// ```
+// After the length check, the conversion of such an array with `from_be_bytes`/`from_le_bytes`
+// compiles to a single unaligned load, plus a byte swap if the order differs from the target's.
+#[inline]
+pub fn read_array<const N: usize>(data: &[u8]) -> EndiannessResult<[u8; N]> {
+    match data.get(..N) {
+        Some(bytes) => Ok(bytes.try_into().unwrap()),
+        None => Err(EndiannessError::ShortSlice { expected: N, found: data.len() }),
+    }
+}
+
+// Returns the first `N` bytes of the stream for writing.
+#[inline]
+fn take_mut<const N: usize>(data: &mut [u8]) -> EndiannessResult<&mut [u8; N]> {
+    let found = data.len();
+    match data.get_mut(..N) {
+        Some(bytes) => Ok(bytes.try_into().unwrap()),
+        None => Err(EndiannessError::ShortSlice { expected: N, found }),
+    }
+}
+
+/// Reads unsigned 8-bit integer from a stream of bytes.
+///
+/// The byte order does not matter for a single byte; it is accepted only so that
+/// this function has the same signature as the other readers.
+#[inline]
+pub fn read_u8(data: &[u8], _endianness: ByteOrder) -> EndiannessResult<u8> {
+    match data.first() {
+        Some(&b) => Ok(b),
+        None => Err(EndiannessError::ShortSlice { expected: 1, found: 0 }),
+    }
+}
+
+/// Reads signed 8-bit integer from a stream of bytes.
+#[inline]
+pub fn read_i8(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i8> {
+    Ok(read_u8(data, endianness)? as i8)
+}
+
 /// Reads unsigned 16-bit integer from a stream of bytes.
+#[inline]
 pub fn read_u16(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u16> {
-    if data.len() < 2 {
-        Err(EndiannessError::ShortSlice)
-    } else {
-        match endianness {
-            ByteOrder::BigEndian => Ok(((data[0] as u16) << 8) + (data[1] as u16)),
-            ByteOrder::LittleEndian => Ok(((data[1] as u16) << 8) + (data[0] as u16)),
-        }
+    let bytes = read_array::<2>(data)?;
+    match endianness {
+        ByteOrder::BigEndian => Ok(u16::from_be_bytes(bytes)),
+        ByteOrder::LittleEndian => Ok(u16::from_le_bytes(bytes)),
     }
 }
 
 /// Reads signed 16-bit integer from a stream of bytes.
+#[inline]
 pub fn read_i16(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i16> {
-    Ok(try!(read_u16(data, endianness)) as i16)
+    Ok(read_u16(data, endianness)? as i16)
+}
+
+/// Reads unsigned 24-bit integer from a stream of bytes.
+#[inline]
+pub fn read_u24(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u32> {
+    let b = read_array::<3>(data)?;
+    match endianness {
+        ByteOrder::BigEndian => Ok(u32::from_be_bytes([0, b[0], b[1], b[2]])),
+        ByteOrder::LittleEndian => Ok(u32::from_le_bytes([b[0], b[1], b[2], 0])),
+    }
+}
+
+/// Reads signed 24-bit integer from a stream of bytes and sign-extends it to 32 bits.
+#[inline]
+pub fn read_i24(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i32> {
+    Ok(((read_u24(data, endianness)? << 8) as i32) >> 8)
 }
 
 /// Reads unsigned 32-bit integer from a stream of bytes.
+#[inline]
 pub fn read_u32(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u32> {
-    if data.len() < 4 {
-        Err(EndiannessError::ShortSlice)
-    } else {
-        match endianness {
-            ByteOrder::BigEndian => {
-                Ok(((data[0] as u32) << 24) + ((data[1] as u32) << 16) + ((data[2] as u32) << 8) +
-                   (data[3] as u32))
-            }
-            ByteOrder::LittleEndian => {
-                Ok(((data[3] as u32) << 24) + ((data[2] as u32) << 16) + ((data[1] as u32) << 8) +
-                   (data[0] as u32))
-            }
-        }
+    let bytes = read_array::<4>(data)?;
+    match endianness {
+        ByteOrder::BigEndian => Ok(u32::from_be_bytes(bytes)),
+        ByteOrder::LittleEndian => Ok(u32::from_le_bytes(bytes)),
     }
 }
 
 /// Reads signed 32-bit integer from a stream of bytes.
+#[inline]
 pub fn read_i32(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i32> {
-    Ok(try!(read_u32(data, endianness)) as i32)
+    Ok(read_u32(data, endianness)? as i32)
+}
+
+/// Reads unsigned 48-bit integer from a stream of bytes.
+#[inline]
+pub fn read_u48(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u64> {
+    let b = read_array::<6>(data)?;
+    match endianness {
+        ByteOrder::BigEndian => Ok(u64::from_be_bytes([0, 0, b[0], b[1], b[2], b[3], b[4], b[5]])),
+        ByteOrder::LittleEndian => Ok(u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], 0, 0])),
+    }
+}
+
+/// Reads signed 48-bit integer from a stream of bytes and sign-extends it to 64 bits.
+#[inline]
+pub fn read_i48(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i64> {
+    Ok(((read_u48(data, endianness)? << 16) as i64) >> 16)
 }
 
 /// Reads unsigned 64-bit integer from a stream of bytes.
+#[inline]
 pub fn read_u64(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u64> {
-    if data.len() < 8 {
-        Err(EndiannessError::ShortSlice)
+    let bytes = read_array::<8>(data)?;
+    match endianness {
+        ByteOrder::BigEndian => Ok(u64::from_be_bytes(bytes)),
+        ByteOrder::LittleEndian => Ok(u64::from_le_bytes(bytes)),
+    }
+}
+
+/// Reads signed 64-bit integer from a stream of bytes.
+#[inline]
+pub fn read_i64(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i64> {
+    Ok(read_u64(data, endianness)? as i64)
+}
+
+/// Reads unsigned 128-bit integer from a stream of bytes.
+#[inline]
+pub fn read_u128(data: &[u8], endianness: ByteOrder) -> EndiannessResult<u128> {
+    let bytes = read_array::<16>(data)?;
+    match endianness {
+        ByteOrder::BigEndian => Ok(u128::from_be_bytes(bytes)),
+        ByteOrder::LittleEndian => Ok(u128::from_le_bytes(bytes)),
+    }
+}
+
+/// Reads signed 128-bit integer from a stream of bytes.
+#[inline]
+pub fn read_i128(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i128> {
+    Ok(read_u128(data, endianness)? as i128)
+}
+
+/// Reads a single-precision floating point number.
+#[inline]
+pub fn read_f32(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f32> {
+    let u = read_u32(data, endianness)?;
+    Ok(f32::from_bits(u))
+}
+
+/// Reads a double-precision floating point number.
+#[inline]
+pub fn read_f64(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f64> {
+    let u = read_u64(data, endianness)?;
+    Ok(f64::from_bits(u))
+}
+
+/// Reads a single-precision floating point number and rejects infinities and NaNs
+/// with `NonFinite`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(-127.5, read_f32_finite(&[194, 255, 0, 0], ByteOrder::BigEndian).unwrap());
+/// assert_eq!(EndiannessError::NonFinite,
+///            read_f32_finite(&[127, 192, 0, 0], ByteOrder::BigEndian).unwrap_err());
+/// ```
+pub fn read_f32_finite(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f32> {
+    let n = read_f32(data, endianness)?;
+    if n.is_finite() { Ok(n) } else { Err(EndiannessError::NonFinite) }
+}
+
+/// Reads a double-precision floating point number and rejects infinities and NaNs
+/// with `NonFinite`.
+pub fn read_f64_finite(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f64> {
+    let n = read_f64(data, endianness)?;
+    if n.is_finite() { Ok(n) } else { Err(EndiannessError::NonFinite) }
+}
+
+/// Reads a character stored as a 32-bit code point and rejects surrogates and values
+/// above U+10FFFF with `InvalidData`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!('\u{1f600}', read_char(&[0x00, 0xf6, 0x01, 0x00], ByteOrder::LittleEndian).unwrap());
+/// assert_eq!(EndiannessError::InvalidData,
+///            read_char(&[0x00, 0x00, 0xd8, 0x00], ByteOrder::BigEndian).unwrap_err());
+/// ```
+pub fn read_char(data: &[u8], endianness: ByteOrder) -> EndiannessResult<char> {
+    let n = read_u32(data, endianness)?;
+    core::char::from_u32(n).ok_or(EndiannessError::InvalidData)
+}
+
+/// How `read_bool` treats bytes other than 0 and 1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BoolMode {
+    /// Only 0 and 1 are accepted; other values fail with `InvalidData`.
+    Strict,
+    /// Any value other than 0 is `true`, as in C.
+    Lenient,
+}
+
+/// Reads a boolean stored as one byte.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(Ok(true), read_bool(&[1], BoolMode::Strict));
+/// assert_eq!(EndiannessError::InvalidData, read_bool(&[0xff], BoolMode::Strict).unwrap_err());
+/// assert_eq!(Ok(true), read_bool(&[0xff], BoolMode::Lenient));
+/// ```
+pub fn read_bool(data: &[u8], mode: BoolMode) -> EndiannessResult<bool> {
+    match (read_u8(data, ByteOrder::BigEndian)?, mode) {
+        (0, _) => Ok(false),
+        (1, _) | (_, BoolMode::Lenient) => Ok(true),
+        (_, BoolMode::Strict) => Err(EndiannessError::InvalidData),
+    }
+}
+
+/// Reads unsigned integer of `nbytes` bytes from a stream of bytes.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = vec![1, 2, 3, 4, 5];
+/// assert_eq!(0x0102030405, read_uint(&v, 5, ByteOrder::BigEndian).unwrap());
+/// assert_eq!(0x030201, read_uint(&v, 3, ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_uint(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<u64> {
+    if nbytes == 0 || nbytes > 8 {
+        Err(EndiannessError::InvalidWidth)
     } else {
+        Ok(read_uint128(data, nbytes, endianness)? as u64)
+    }
+}
+
+/// Reads unsigned integer of `nbytes` bytes from a stream of bytes.
+///
+/// `nbytes` must be between 1 and 16, otherwise `InvalidWidth` is returned.
+pub fn read_uint128(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<u128> {
+    if nbytes == 0 || nbytes > 16 {
+        Err(EndiannessError::InvalidWidth)
+    } else if data.len() < nbytes {
+        Err(EndiannessError::ShortSlice { expected: nbytes, found: data.len() })
+    } else {
+        // The bytes are padded with zeros to a full u128 on the most significant side.
+        let mut buf = [0u8; 16];
         match endianness {
             ByteOrder::BigEndian => {
-                Ok(((data[0] as u64) << 56) + ((data[1] as u64) << 48) +
-                   ((data[2] as u64) << 40) + ((data[3] as u64) << 32) +
-                   ((data[4] as u64) << 24) + ((data[5] as u64) << 16) +
-                   ((data[6] as u64) << 8) + (data[7] as u64))
+                buf[16 - nbytes..].copy_from_slice(&data[..nbytes]);
+                Ok(u128::from_be_bytes(buf))
             }
             ByteOrder::LittleEndian => {
-                Ok(((data[7] as u64) << 56) + ((data[6] as u64) << 48) +
-                   ((data[5] as u64) << 40) + ((data[4] as u64) << 32) +
-                   ((data[3] as u64) << 24) + ((data[2] as u64) << 16) +
-                   ((data[1] as u64) << 8) + (data[0] as u64))
+                buf[..nbytes].copy_from_slice(&data[..nbytes]);
+                Ok(u128::from_le_bytes(buf))
             }
         }
     }
 }
 
-/// Reads signed 64-bit integer from a stream of bytes.
-pub fn read_i64(data: &[u8], endianness: ByteOrder) -> EndiannessResult<i64> {
-    Ok(try!(read_u64(data, endianness)) as i64)
+/// Reads signed integer of `nbytes` bytes from a stream of bytes and sign-extends it to 64 bits.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = vec![0xff, 0xfe, 0x7f];
+/// assert_eq!(-2, read_int(&v, 2, ByteOrder::BigEndian).unwrap());
+/// assert_eq!(0x7ffeff, read_int(&v, 3, ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_int(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<i64> {
+    let n = read_uint(data, nbytes, endianness)?;
+    let shift = 64 - 8 * nbytes as u32;
+    Ok(((n << shift) as i64) >> shift)
 }
 
-/// Reads a single-precision floating point number.
-pub fn read_f32(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f32> {
-    let u = try!(read_u32(data, endianness));
-    Ok(unsafe { mem::transmute(u) })
+/// Reads signed integer of `nbytes` bytes from a stream of bytes and sign-extends it to 128 bits.
+///
+/// `nbytes` must be between 1 and 16, otherwise `InvalidWidth` is returned.
+pub fn read_int128(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<i128> {
+    let n = read_uint128(data, nbytes, endianness)?;
+    let shift = 128 - 8 * nbytes as u32;
+    Ok(((n << shift) as i128) >> shift)
 }
 
-/// Reads a double-precision floating point number.
-pub fn read_f64(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f64> {
-    let u = try!(read_u64(data, endianness));
-    Ok(unsafe { mem::transmute(u) })
+/// Reads an offset or size stored as an unsigned integer of `nbytes` bytes, whatever the
+/// pointer width of the host.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned. Fails with
+/// `Overflow` if the value does not fit into `usize`, which can only happen on hosts with
+/// pointers narrower than `nbytes`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0, 0, 0, 0, 0, 0, 0x10, 0];
+/// assert_eq!(0x1000, read_usize(&v, 8, ByteOrder::BigEndian).unwrap());
+/// ```
+pub fn read_usize(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<usize> {
+    let n = read_uint(data, nbytes, endianness)?;
+    usize::try_from(n).map_err(|_| EndiannessError::Overflow)
+}
+
+/// Reads a signed offset stored as an integer of `nbytes` bytes, whatever the pointer
+/// width of the host.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned. Fails with
+/// `Overflow` if the value does not fit into `isize`.
+pub fn read_isize(data: &[u8], nbytes: usize, endianness: ByteOrder) -> EndiannessResult<isize> {
+    let n = read_int(data, nbytes, endianness)?;
+    isize::try_from(n).map_err(|_| EndiannessError::Overflow)
+}
+
+/// Reads unsigned 32-bit integer as a `usize`, such as an offset or a length.
+///
+/// Fails with `Overflow` on hosts with 16-bit pointers if the value does not fit.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [5, 0, 0, 0, 1, 7, 7];
+/// let offset = read_u32_as_usize(&v, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!(&[7, 7], &v[offset..]);
+/// ```
+#[inline]
+pub fn read_u32_as_usize(data: &[u8], endianness: ByteOrder) -> EndiannessResult<usize> {
+    read_usize(data, 4, endianness)
+}
+
+/// Reads unsigned 64-bit integer as a `usize`, such as an offset or a length.
+///
+/// Fails with `Overflow` on hosts with 32-bit pointers if the value does not fit.
+#[inline]
+pub fn read_u64_as_usize(data: &[u8], endianness: ByteOrder) -> EndiannessResult<usize> {
+    read_usize(data, 8, endianness)
+}
+
+/// Writes unsigned 8-bit integer to a stream of bytes.
+///
+/// The byte order does not matter for a single byte; it is accepted only so that
+/// this function has the same signature as the other writers.
+#[inline]
+pub fn write_u8(data: &mut [u8], value: u8, _endianness: ByteOrder) -> EndiannessResult<()> {
+    match data.first_mut() {
+        Some(b) => {
+            *b = value;
+            Ok(())
+        }
+        None => Err(EndiannessError::ShortSlice { expected: 1, found: 0 }),
+    }
+}
+
+/// Writes signed 8-bit integer to a stream of bytes.
+#[inline]
+pub fn write_i8(data: &mut [u8], value: i8, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u8(data, value as u8, endianness)
+}
+
+/// Writes unsigned 16-bit integer to a stream of bytes.
+#[inline]
+pub fn write_u16(data: &mut [u8], value: u16, endianness: ByteOrder) -> EndiannessResult<()> {
+    let bytes = take_mut::<2>(data)?;
+    *bytes = match endianness {
+        ByteOrder::BigEndian => value.to_be_bytes(),
+        ByteOrder::LittleEndian => value.to_le_bytes(),
+    };
+    Ok(())
+}
+
+/// Writes signed 16-bit integer to a stream of bytes.
+#[inline]
+pub fn write_i16(data: &mut [u8], value: i16, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u16(data, value as u16, endianness)
+}
+
+/// Writes unsigned 24-bit integer to a stream of bytes.
+///
+/// Fails with `Overflow` if the value does not fit into 24 bits.
+#[inline]
+pub fn write_u24(data: &mut [u8], value: u32, endianness: ByteOrder) -> EndiannessResult<()> {
+    let bytes = take_mut::<3>(data)?;
+    if value > 0xff_ffff {
+        return Err(EndiannessError::Overflow);
+    }
+    match endianness {
+        ByteOrder::BigEndian => bytes.copy_from_slice(&value.to_be_bytes()[1..]),
+        ByteOrder::LittleEndian => bytes.copy_from_slice(&value.to_le_bytes()[..3]),
+    }
+    Ok(())
+}
+
+/// Writes signed 24-bit integer to a stream of bytes.
+///
+/// Fails with `Overflow` if the value does not fit into 24 bits.
+#[inline]
+pub fn write_i24(data: &mut [u8], value: i32, endianness: ByteOrder) -> EndiannessResult<()> {
+    if !(-0x80_0000..=0x7f_ffff).contains(&value) {
+        Err(EndiannessError::Overflow)
+    } else {
+        write_u24(data, value as u32 & 0xff_ffff, endianness)
+    }
+}
+
+/// Writes unsigned 32-bit integer to a stream of bytes.
+#[inline]
+pub fn write_u32(data: &mut [u8], value: u32, endianness: ByteOrder) -> EndiannessResult<()> {
+    let bytes = take_mut::<4>(data)?;
+    *bytes = match endianness {
+        ByteOrder::BigEndian => value.to_be_bytes(),
+        ByteOrder::LittleEndian => value.to_le_bytes(),
+    };
+    Ok(())
+}
+
+/// Writes signed 32-bit integer to a stream of bytes.
+#[inline]
+pub fn write_i32(data: &mut [u8], value: i32, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u32(data, value as u32, endianness)
+}
+
+/// Writes unsigned 48-bit integer to a stream of bytes.
+///
+/// Fails with `Overflow` if the value does not fit into 48 bits.
+#[inline]
+pub fn write_u48(data: &mut [u8], value: u64, endianness: ByteOrder) -> EndiannessResult<()> {
+    let bytes = take_mut::<6>(data)?;
+    if value > 0xffff_ffff_ffff {
+        return Err(EndiannessError::Overflow);
+    }
+    match endianness {
+        ByteOrder::BigEndian => bytes.copy_from_slice(&value.to_be_bytes()[2..]),
+        ByteOrder::LittleEndian => bytes.copy_from_slice(&value.to_le_bytes()[..6]),
+    }
+    Ok(())
+}
+
+/// Writes signed 48-bit integer to a stream of bytes.
+///
+/// Fails with `Overflow` if the value does not fit into 48 bits.
+#[inline]
+pub fn write_i48(data: &mut [u8], value: i64, endianness: ByteOrder) -> EndiannessResult<()> {
+    if !(-0x8000_0000_0000..=0x7fff_ffff_ffff).contains(&value) {
+        Err(EndiannessError::Overflow)
+    } else {
+        write_u48(data, value as u64 & 0xffff_ffff_ffff, endianness)
+    }
+}
+
+/// Writes unsigned 64-bit integer to a stream of bytes.
+#[inline]
+pub fn write_u64(data: &mut [u8], value: u64, endianness: ByteOrder) -> EndiannessResult<()> {
+    let bytes = take_mut::<8>(data)?;
+    *bytes = match endianness {
+        ByteOrder::BigEndian => value.to_be_bytes(),
+        ByteOrder::LittleEndian => value.to_le_bytes(),
+    };
+    Ok(())
+}
+
+/// Writes signed 64-bit integer to a stream of bytes.
+#[inline]
+pub fn write_i64(data: &mut [u8], value: i64, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u64(data, value as u64, endianness)
+}
+
+/// Writes unsigned 128-bit integer to a stream of bytes.
+#[inline]
+pub fn write_u128(data: &mut [u8], value: u128, endianness: ByteOrder) -> EndiannessResult<()> {
+    let bytes = take_mut::<16>(data)?;
+    *bytes = match endianness {
+        ByteOrder::BigEndian => value.to_be_bytes(),
+        ByteOrder::LittleEndian => value.to_le_bytes(),
+    };
+    Ok(())
+}
+
+/// Writes signed 128-bit integer to a stream of bytes.
+#[inline]
+pub fn write_i128(data: &mut [u8], value: i128, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u128(data, value as u128, endianness)
+}
+
+/// Writes a single-precision floating point number.
+#[inline]
+pub fn write_f32(data: &mut [u8], value: f32, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u32(data, value.to_bits(), endianness)
+}
+
+/// Writes a double-precision floating point number.
+#[inline]
+pub fn write_f64(data: &mut [u8], value: f64, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u64(data, value.to_bits(), endianness)
+}
+
+/// Writes a boolean as one byte, 0 or 1.
+pub fn write_bool(data: &mut [u8], value: bool) -> EndiannessResult<()> {
+    write_u8(data, value.into(), ByteOrder::BigEndian)
+}
+
+/// Writes a character as a 32-bit code point.
+pub fn write_char(data: &mut [u8], value: char, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_u32(data, value.into(), endianness)
+}
+
+/// Writes the low `nbytes` bytes of an unsigned integer to a stream of bytes.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned.
+/// Fails with `Overflow` if the value does not fit into `nbytes` bytes.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut v = [0u8; 3];
+/// write_uint(&mut v, 0x010203, 3, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!([3, 2, 1], v);
+/// assert!(write_uint(&mut v, 0x01020304, 3, ByteOrder::LittleEndian).is_err());
+/// ```
+pub fn write_uint(data: &mut [u8], value: u64, nbytes: usize, endianness: ByteOrder)
+                  -> EndiannessResult<()> {
+    if nbytes == 0 || nbytes > 8 {
+        Err(EndiannessError::InvalidWidth)
+    } else {
+        write_uint128(data, value as u128, nbytes, endianness)
+    }
+}
+
+/// Writes the low `nbytes` bytes of an unsigned integer to a stream of bytes.
+///
+/// `nbytes` must be between 1 and 16, otherwise `InvalidWidth` is returned.
+/// Fails with `Overflow` if the value does not fit into `nbytes` bytes.
+pub fn write_uint128(data: &mut [u8], value: u128, nbytes: usize, endianness: ByteOrder)
+                     -> EndiannessResult<()> {
+    if nbytes == 0 || nbytes > 16 {
+        Err(EndiannessError::InvalidWidth)
+    } else if data.len() < nbytes {
+        Err(EndiannessError::ShortSlice { expected: nbytes, found: data.len() })
+    } else if nbytes < 16 && value >> (8 * nbytes) != 0 {
+        Err(EndiannessError::Overflow)
+    } else {
+        match endianness {
+            ByteOrder::BigEndian => data[..nbytes].copy_from_slice(&value.to_be_bytes()[16 - nbytes..]),
+            ByteOrder::LittleEndian => data[..nbytes].copy_from_slice(&value.to_le_bytes()[..nbytes]),
+        }
+        Ok(())
+    }
+}
+
+/// Writes the low `nbytes` bytes of a signed integer to a stream of bytes.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned.
+/// Fails with `Overflow` if the value does not fit into `nbytes` bytes.
+pub fn write_int(data: &mut [u8], value: i64, nbytes: usize, endianness: ByteOrder)
+                 -> EndiannessResult<()> {
+    if nbytes == 0 || nbytes > 8 {
+        Err(EndiannessError::InvalidWidth)
+    } else {
+        write_int128(data, value as i128, nbytes, endianness)
+    }
+}
+
+/// Writes the low `nbytes` bytes of a signed integer to a stream of bytes.
+///
+/// `nbytes` must be between 1 and 16, otherwise `InvalidWidth` is returned.
+/// Fails with `Overflow` if the value does not fit into `nbytes` bytes.
+pub fn write_int128(data: &mut [u8], value: i128, nbytes: usize, endianness: ByteOrder)
+                    -> EndiannessResult<()> {
+    if nbytes == 0 || nbytes > 16 {
+        Err(EndiannessError::InvalidWidth)
+    } else {
+        let shift = 128 - 8 * nbytes as u32;
+        if (value << shift) >> shift != value {
+            Err(EndiannessError::Overflow)
+        } else {
+            write_uint128(data, (value as u128) & (u128::MAX >> shift), nbytes, endianness)
+        }
+    }
+}
+
+/// Writes an offset or size as an unsigned integer of `nbytes` bytes.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned.
+/// Fails with `Overflow` if the value does not fit into `nbytes` bytes.
+pub fn write_usize(data: &mut [u8], value: usize, nbytes: usize, endianness: ByteOrder)
+                   -> EndiannessResult<()> {
+    write_uint(data, value as u64, nbytes, endianness)
+}
+
+/// Writes a signed offset as an integer of `nbytes` bytes.
+///
+/// `nbytes` must be between 1 and 8, otherwise `InvalidWidth` is returned.
+/// Fails with `Overflow` if the value does not fit into `nbytes` bytes.
+pub fn write_isize(data: &mut [u8], value: isize, nbytes: usize, endianness: ByteOrder)
+                   -> EndiannessResult<()> {
+    write_int(data, value as i64, nbytes, endianness)
+}
+
+// Returns 2^n; `n` must be a valid exponent of a normal f64.
+fn pow2(n: i32) -> f64 {
+    f64::from_bits(((1023 + n) as u64) << 52)
 }
 
 #[cfg(test)]
-#[allow(unsafe_code)]
 mod tests {
     // Macro to test that all of the functions return an error type
     // when given a slice that is too short for them.
     macro_rules! short_slice {
-        ($name:ident, $read:ident) => (
+        ($name:ident, $read:ident, $size:expr) => (
             mod $name {
                 use {ByteOrder, EndiannessError, $read};
 
                 #[test]
                 fn read_big_endian() {
-                    assert_eq!(EndiannessError::ShortSlice, $read(&[], ByteOrder::BigEndian).unwrap_err());
+                    assert_eq!(EndiannessError::ShortSlice { expected: $size, found: $size - 1 },
+                               $read(&[0; $size - 1], ByteOrder::BigEndian).unwrap_err());
                 }
 
                 #[test]
                 fn read_little_endian() {
-                    assert_eq!(EndiannessError::ShortSlice, $read(&[], ByteOrder::LittleEndian).unwrap_err());
+                    assert_eq!(EndiannessError::ShortSlice { expected: $size, found: 0 },
+                               $read(&[], ByteOrder::LittleEndian).unwrap_err());
+                }
+            }
+        );
+    }
+
+    mod error {
+        use {ByteOrder, read_u32};
+
+        #[test]
+        fn short_slice_message() {
+            let err = read_u32(&[1, 2], ByteOrder::BigEndian).unwrap_err();
+            assert_eq!("The slice length is too short: expected 4 bytes, found 2.", err.to_string());
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    mod arbitrary {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        use ByteOrder;
+
+        #[test]
+        fn byte_order() {
+            let mut seen = [false; 2];
+            for b in 0..=255u8 {
+                let bytes = [b, b, b, b];
+                match ByteOrder::arbitrary(&mut Unstructured::new(&bytes)).unwrap() {
+                    ByteOrder::LittleEndian => seen[0] = true,
+                    ByteOrder::BigEndian => seen[1] = true,
+                }
+            }
+            assert_eq!([true, true], seen);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        extern crate serde_json;
+
+        #[cfg(feature = "std")]
+        use std::io::ErrorKind;
+
+        use {ByteOrder, EndiannessError};
+
+        #[test]
+        fn byte_order() {
+            assert_eq!("\"BigEndian\"", serde_json::to_string(&ByteOrder::BigEndian).unwrap());
+            assert_eq!(ByteOrder::LittleEndian, serde_json::from_str("\"LittleEndian\"").unwrap());
+        }
+
+        #[test]
+        fn error() {
+            let err = EndiannessError::ShortSlice { expected: 4, found: 2 };
+            let json = serde_json::to_string(&err).unwrap();
+            assert_eq!(r#"{"ShortSlice":{"expected":4,"found":2}}"#, json);
+            assert_eq!(err, serde_json::from_str(&json).unwrap());
+            assert_eq!(EndiannessError::Overflow, serde_json::from_str("\"Overflow\"").unwrap());
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn io_error() {
+            let err = EndiannessError::Io(ErrorKind::PermissionDenied);
+            assert_eq!(r#"{"Io":"PermissionDenied"}"#, serde_json::to_string(&err).unwrap());
+            assert!(serde_json::from_str::<EndiannessError>(r#"{"Io":"PermissionDenied"}"#).is_err());
+        }
+    }
+
+    mod byte_order {
+        use {ByteOrder, read_u16};
+
+        #[test]
+        fn native() {
+            let v = 0x0102u16.to_ne_bytes();
+            assert_eq!(0x0102, read_u16(&v, ByteOrder::NATIVE).unwrap());
+            assert_eq!(ByteOrder::NATIVE, ByteOrder::native());
+        }
+
+        #[test]
+        fn is_native() {
+            assert!(ByteOrder::NATIVE.is_native());
+            let other = match ByteOrder::NATIVE {
+                ByteOrder::BigEndian => ByteOrder::LittleEndian,
+                ByteOrder::LittleEndian => ByteOrder::BigEndian,
+            };
+            assert!(!other.is_native());
+        }
+
+        #[test]
+        fn network() {
+            assert_eq!(0x0102, read_u16(&[1, 2], ByteOrder::NETWORK).unwrap());
+        }
+
+        #[test]
+        fn parse() {
+            for s in &["big", "be", "big-endian", "BE", "Big-Endian"] {
+                assert_eq!(Ok(ByteOrder::BigEndian), s.parse());
+            }
+            for s in &["little", "le", "little-endian", "LE", "LITTLE"] {
+                assert_eq!(Ok(ByteOrder::LittleEndian), s.parse());
+            }
+            for s in &["", "b", "bigendian", " le", "network"] {
+                assert!(s.parse::<ByteOrder>().is_err());
+            }
+        }
+
+        #[test]
+        fn display_round_trips() {
+            for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+                assert_eq!(Ok(order), order.to_string().parse());
+            }
+            assert_eq!("little-endian", ByteOrder::LittleEndian.to_string());
+        }
+    }
+
+    short_slice!(short_slice_u8, read_u8, 1);
+    short_slice!(short_slice_i8, read_i8, 1);
+    short_slice!(short_slice_u16, read_u16, 2);
+    short_slice!(short_slice_i16, read_i16, 2);
+    short_slice!(short_slice_u24, read_u24, 3);
+    short_slice!(short_slice_i24, read_i24, 3);
+    short_slice!(short_slice_u32, read_u32, 4);
+    short_slice!(short_slice_i32, read_i32, 4);
+    short_slice!(short_slice_u48, read_u48, 6);
+    short_slice!(short_slice_i48, read_i48, 6);
+    short_slice!(short_slice_u64, read_u64, 8);
+    short_slice!(short_slice_i64, read_i64, 8);
+    short_slice!(short_slice_f32, read_f32, 4);
+    short_slice!(short_slice_f64, read_f64, 8);
+    short_slice!(short_slice_u128, read_u128, 16);
+    short_slice!(short_slice_i128, read_i128, 16);
+
+    // Same as short_slice!, but for the write functions.
+    macro_rules! short_slice_write {
+        ($name:ident, $write:ident, $size:expr) => (
+            mod $name {
+                use {ByteOrder, EndiannessError, $write};
+
+                #[test]
+                fn write_big_endian() {
+                    assert_eq!(EndiannessError::ShortSlice { expected: $size, found: $size - 1 },
+                               $write(&mut [0; $size - 1], Default::default(), ByteOrder::BigEndian)
+                                   .unwrap_err());
+                }
+
+                #[test]
+                fn write_little_endian() {
+                    assert_eq!(EndiannessError::ShortSlice { expected: $size, found: 0 },
+                               $write(&mut [], Default::default(), ByteOrder::LittleEndian).unwrap_err());
                 }
             }
         );
     }
 
-    short_slice!(short_slice_u16, read_u16);
-    short_slice!(short_slice_i16, read_i16);
-    short_slice!(short_slice_u32, read_u32);
-    short_slice!(short_slice_i32, read_i32);
-    short_slice!(short_slice_u64, read_u64);
-    short_slice!(short_slice_i64, read_i64);
-    short_slice!(short_slice_f32, read_f32);
-    short_slice!(short_slice_f64, read_f64);
+    short_slice_write!(short_slice_write_u8, write_u8, 1);
+    short_slice_write!(short_slice_write_i8, write_i8, 1);
+    short_slice_write!(short_slice_write_u16, write_u16, 2);
+    short_slice_write!(short_slice_write_u24, write_u24, 3);
+    short_slice_write!(short_slice_write_i24, write_i24, 3);
+    short_slice_write!(short_slice_write_u32, write_u32, 4);
+    short_slice_write!(short_slice_write_u48, write_u48, 6);
+    short_slice_write!(short_slice_write_i48, write_i48, 6);
+    short_slice_write!(short_slice_write_u64, write_u64, 8);
+    short_slice_write!(short_slice_write_i16, write_i16, 2);
+    short_slice_write!(short_slice_write_i32, write_i32, 4);
+    short_slice_write!(short_slice_write_i64, write_i64, 8);
+    short_slice_write!(short_slice_write_f32, write_f32, 4);
+    short_slice_write!(short_slice_write_f64, write_f64, 8);
+    short_slice_write!(short_slice_write_u128, write_u128, 16);
+    short_slice_write!(short_slice_write_i128, write_i128, 16);
 
     // A macro to perform generative testing using the following invariant:
     // for any integer N that was transmuted to a stream of bytes read functions must return N.
     macro_rules! read_correctness {
         ($name:ident, $ty:ty, $size: expr, $read:ident, $max:expr) => (
             mod $name {
-                use std::mem;
                 use {ByteOrder, $read};
 
                 extern crate quickcheck;
@@ -210,14 +1357,14 @@ mod tests {
                 fn read_big_endian() {
                     #[cfg(target_endian = "little")]
                     fn prop(n: $ty) -> bool {
-                        let mut data = unsafe { mem::transmute::<_, [u8; $size]>(n) };
+                        let mut data: [u8; $size] = n.to_ne_bytes();
                         data.reverse();
                         n == $read(&data, ByteOrder::BigEndian).unwrap()
                     }
 
                     #[cfg(target_endian = "big")]
                     fn prop(n: $ty) -> bool {
-                        let data = unsafe { mem::transmute::<_, [u8; $size]>(n) };
+                        let data: [u8; $size] = n.to_ne_bytes();
                         n == $read(&data, ByteOrder::BigEndian).unwrap()
                     }
 
@@ -230,13 +1377,13 @@ mod tests {
                 fn read_little_endian() {
                     #[cfg(target_endian = "little")]
                     fn prop(n: $ty) -> bool {
-                        let data = unsafe { mem::transmute::<_, [u8; $size]>(n) };
+                        let data: [u8; $size] = n.to_ne_bytes();
                         n == $read(&data, ByteOrder::LittleEndian).unwrap()
                     }
 
                     #[cfg(target_endian = "big")]
                     fn prop(n: $ty) -> bool {
-                        let mut data = unsafe { mem::transmute::<_, [u8; $size]>(n) };
+                        let mut data: [u8; $size] = n.to_ne_bytes();
                         data.reverse();
                         n == $read(&data, ByteOrder::LittleEndian).unwrap()
                     }
@@ -255,12 +1402,570 @@ mod tests {
         );
     }
 
-    read_correctness!(test_u16, u16, 2, read_u16, ::std::u16::MAX);
-    read_correctness!(test_i16, i16, 2, read_i16, ::std::i16::MAX);
-    read_correctness!(test_u32, u32, 4, read_u32, ::std::u32::MAX);
-    read_correctness!(test_i32, i32, 4, read_i32, ::std::i32::MAX);
-    read_correctness!(test_u64, u64, 8, read_u64, ::std::u64::MAX);
-    read_correctness!(test_i64, i64, 8, read_i64, ::std::i64::MAX);
-    read_correctness!(test_f32, f32, 4, read_f32, ::std::u32::MAX);
-    read_correctness!(test_f64, f64, 8, read_f64, ::std::u64::MAX);
+    read_correctness!(test_u8, u8, 1, read_u8, u8::MAX);
+    read_correctness!(test_i8, i8, 1, read_i8, i8::MAX);
+    read_correctness!(test_u16, u16, 2, read_u16, u16::MAX);
+    read_correctness!(test_i16, i16, 2, read_i16, i16::MAX);
+    read_correctness!(test_u32, u32, 4, read_u32, u32::MAX);
+    read_correctness!(test_i32, i32, 4, read_i32, i32::MAX);
+    read_correctness!(test_u64, u64, 8, read_u64, u64::MAX);
+    read_correctness!(test_i64, i64, 8, read_i64, i64::MAX);
+    read_correctness!(test_f32, f32, 4, read_f32, u32::MAX);
+    read_correctness!(test_f64, f64, 8, read_f64, u64::MAX);
+
+    // A macro to perform generative testing of the write functions: for any integer N
+    // the written bytes must match the ones produced by the standard library.
+    macro_rules! write_correctness {
+        ($name:ident, $ty:ty, $size: expr, $write:ident, $max:expr) => (
+            mod $name {
+                use {ByteOrder, $write};
+
+                extern crate quickcheck;
+                extern crate rand;
+                use self::quickcheck::{QuickCheck, StdGen, Testable};
+
+                #[test]
+                fn write_big_endian() {
+                    fn prop(n: $ty) -> bool {
+                        let mut data = [0u8; $size];
+                        $write(&mut data, n, ByteOrder::BigEndian).unwrap();
+                        data == n.to_be_bytes()
+                    }
+
+                    let f: fn($ty) -> bool = prop;
+                    quick_check(f);
+                }
+
+                #[test]
+                fn write_little_endian() {
+                    fn prop(n: $ty) -> bool {
+                        let mut data = [0u8; $size];
+                        $write(&mut data, n, ByteOrder::LittleEndian).unwrap();
+                        data == n.to_le_bytes()
+                    }
+
+                    let f: fn($ty) -> bool = prop;
+                    quick_check(f);
+                }
+
+                fn quick_check<T: Testable>(prop: T) {
+                    QuickCheck::new()
+                        .gen(StdGen::new(rand::thread_rng(), $max as usize))
+                        .quickcheck(prop);
+                }
+            }
+        );
+    }
+
+    write_correctness!(test_write_u8, u8, 1, write_u8, u8::MAX);
+    write_correctness!(test_write_i8, i8, 1, write_i8, i8::MAX);
+    write_correctness!(test_write_u16, u16, 2, write_u16, u16::MAX);
+    write_correctness!(test_write_u32, u32, 4, write_u32, u32::MAX);
+    write_correctness!(test_write_u64, u64, 8, write_u64, u64::MAX);
+    write_correctness!(test_write_i16, i16, 2, write_i16, i16::MAX);
+    write_correctness!(test_write_i32, i32, 4, write_i32, i32::MAX);
+    write_correctness!(test_write_i64, i64, 8, write_i64, i64::MAX);
+    write_correctness!(test_write_f32, f32, 4, write_f32, u32::MAX);
+    write_correctness!(test_write_f64, f64, 8, write_f64, u64::MAX);
+
+    // quickcheck has no Arbitrary implementation for 128-bit integers,
+    // so values are assembled from two random 64-bit halves.
+    macro_rules! correctness_128 {
+        ($name:ident, $ty:ty, $read:ident, $write:ident) => (
+            mod $name {
+                use {ByteOrder, $read, $write};
+
+                extern crate quickcheck;
+                extern crate rand;
+                use self::quickcheck::{QuickCheck, StdGen, Testable};
+
+                fn value(high: u64, low: u64) -> $ty {
+                    let mut bytes = [0u8; 16];
+                    bytes[..8].copy_from_slice(&high.to_be_bytes());
+                    bytes[8..].copy_from_slice(&low.to_be_bytes());
+                    <$ty>::from_be_bytes(bytes)
+                }
+
+                #[test]
+                fn read_big_endian() {
+                    fn prop(high: u64, low: u64) -> bool {
+                        let n = value(high, low);
+                        n == $read(&n.to_be_bytes(), ByteOrder::BigEndian).unwrap()
+                    }
+
+                    let f: fn(u64, u64) -> bool = prop;
+                    quick_check(f);
+                }
+
+                #[test]
+                fn read_little_endian() {
+                    fn prop(high: u64, low: u64) -> bool {
+                        let n = value(high, low);
+                        n == $read(&n.to_le_bytes(), ByteOrder::LittleEndian).unwrap()
+                    }
+
+                    let f: fn(u64, u64) -> bool = prop;
+                    quick_check(f);
+                }
+
+                #[test]
+                fn write_big_endian() {
+                    fn prop(high: u64, low: u64) -> bool {
+                        let n = value(high, low);
+                        let mut data = [0u8; 16];
+                        $write(&mut data, n, ByteOrder::BigEndian).unwrap();
+                        data == n.to_be_bytes()
+                    }
+
+                    let f: fn(u64, u64) -> bool = prop;
+                    quick_check(f);
+                }
+
+                #[test]
+                fn write_little_endian() {
+                    fn prop(high: u64, low: u64) -> bool {
+                        let n = value(high, low);
+                        let mut data = [0u8; 16];
+                        $write(&mut data, n, ByteOrder::LittleEndian).unwrap();
+                        data == n.to_le_bytes()
+                    }
+
+                    let f: fn(u64, u64) -> bool = prop;
+                    quick_check(f);
+                }
+
+                fn quick_check<T: Testable>(prop: T) {
+                    QuickCheck::new()
+                        .gen(StdGen::new(rand::thread_rng(), u64::MAX as usize))
+                        .quickcheck(prop);
+                }
+            }
+        );
+    }
+
+    correctness_128!(test_u128, u128, read_u128, write_u128);
+    correctness_128!(test_i128, i128, read_i128, write_i128);
+
+    // Values narrower than their container type are tested with random integers
+    // truncated (and sign-extended for signed types) to the narrow width.
+    macro_rules! narrow_correctness {
+        ($name:ident, $uty:ty, $ity:ty, $bytes:expr, $read_u:ident, $read_i:ident,
+         $write_u:ident, $write_i:ident) => (
+            mod $name {
+                use std::mem;
+                use {ByteOrder, $read_u, $read_i, $write_u, $write_i};
+
+                extern crate quickcheck;
+                extern crate rand;
+                use self::quickcheck::{QuickCheck, StdGen, Testable};
+
+                const SHIFT: usize = (mem::size_of::<$uty>() - $bytes) * 8;
+                const OFFSET: usize = mem::size_of::<$uty>() - $bytes;
+
+                #[test]
+                fn unsigned() {
+                    fn prop(n: $uty) -> bool {
+                        let n = (n << SHIFT) >> SHIFT;
+                        let mut data = [0u8; $bytes];
+                        $write_u(&mut data, n, ByteOrder::BigEndian).unwrap();
+                        let big = data == n.to_be_bytes()[OFFSET..] &&
+                                  n == $read_u(&data, ByteOrder::BigEndian).unwrap();
+                        $write_u(&mut data, n, ByteOrder::LittleEndian).unwrap();
+                        let little = data == n.to_le_bytes()[..$bytes] &&
+                                     n == $read_u(&data, ByteOrder::LittleEndian).unwrap();
+                        big && little
+                    }
+
+                    let f: fn($uty) -> bool = prop;
+                    quick_check(f, <$uty>::MAX as usize);
+                }
+
+                #[test]
+                fn signed() {
+                    fn prop(n: $ity) -> bool {
+                        let n = (n << SHIFT) >> SHIFT;
+                        let mut data = [0u8; $bytes];
+                        $write_i(&mut data, n, ByteOrder::BigEndian).unwrap();
+                        let big = data == n.to_be_bytes()[OFFSET..] &&
+                                  n == $read_i(&data, ByteOrder::BigEndian).unwrap();
+                        $write_i(&mut data, n, ByteOrder::LittleEndian).unwrap();
+                        let little = data == n.to_le_bytes()[..$bytes] &&
+                                     n == $read_i(&data, ByteOrder::LittleEndian).unwrap();
+                        big && little
+                    }
+
+                    let f: fn($ity) -> bool = prop;
+                    quick_check(f, <$ity>::MAX as usize);
+                }
+
+                fn quick_check<T: Testable>(prop: T, size: usize) {
+                    QuickCheck::new()
+                        .gen(StdGen::new(rand::thread_rng(), size))
+                        .quickcheck(prop);
+                }
+            }
+        );
+    }
+
+    narrow_correctness!(test_24, u32, i32, 3, read_u24, read_i24, write_u24, write_i24);
+    narrow_correctness!(test_48, u64, i64, 6, read_u48, read_i48, write_u48, write_i48);
+
+    mod narrow {
+        use {ByteOrder, EndiannessError};
+        use {read_i24, read_i48, write_u24, write_i24, write_u48, write_i48};
+
+        #[test]
+        fn sign_extension() {
+            assert_eq!(-1, read_i24(&[0xff, 0xff, 0xff], ByteOrder::BigEndian).unwrap());
+            assert_eq!(-0x80_0000, read_i24(&[0x80, 0, 0], ByteOrder::BigEndian).unwrap());
+            assert_eq!(-0x80_0000, read_i24(&[0, 0, 0x80], ByteOrder::LittleEndian).unwrap());
+            assert_eq!(0x7f_ffff, read_i24(&[0xff, 0xff, 0x7f], ByteOrder::LittleEndian).unwrap());
+            assert_eq!(-1, read_i48(&[0xff; 6], ByteOrder::LittleEndian).unwrap());
+            assert_eq!(-0x8000_0000_0000,
+                       read_i48(&[0x80, 0, 0, 0, 0, 0], ByteOrder::BigEndian).unwrap());
+        }
+
+        #[test]
+        fn overflow() {
+            let mut data = [0u8; 6];
+            assert_eq!(EndiannessError::Overflow,
+                       write_u24(&mut data, 0x100_0000, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_i24(&mut data, 0x80_0000, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_i24(&mut data, -0x80_0001, ByteOrder::LittleEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_u48(&mut data, 1 << 48, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_i48(&mut data, -(1 << 47) - 1, ByteOrder::LittleEndian).unwrap_err());
+        }
+    }
+
+    mod pointer_sized {
+        use {ByteOrder, EndiannessError};
+        use {read_isize, read_u32_as_usize, read_u64_as_usize, read_usize, write_isize, write_usize};
+
+        #[test]
+        fn round_trip() {
+            let mut v = [0u8; 8];
+            write_usize(&mut v, 0x0102_0304, 4, ByteOrder::LittleEndian).unwrap();
+            assert_eq!([4, 3, 2, 1], v[..4]);
+            assert_eq!(0x0102_0304, read_usize(&v, 4, ByteOrder::LittleEndian).unwrap());
+            write_isize(&mut v, -2, 8, ByteOrder::BigEndian).unwrap();
+            assert_eq!(-2, read_isize(&v, 8, ByteOrder::BigEndian).unwrap());
+            assert_eq!(-2, read_isize(&v[6..], 2, ByteOrder::BigEndian).unwrap());
+        }
+
+        #[test]
+        fn errors() {
+            let mut v = [0xff; 8];
+            assert_eq!(EndiannessError::InvalidWidth, read_usize(&v, 9, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 4 },
+                       read_usize(&v[..4], 8, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_usize(&mut v, 0x1_0000, 2, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_isize(&mut v, -129, 1, ByteOrder::BigEndian).unwrap_err());
+        }
+
+        #[test]
+        fn fixed_widths() {
+            let v = [0x10, 0, 0, 0, 0, 0, 0, 0];
+            assert_eq!(0x10, read_u32_as_usize(&v, ByteOrder::LittleEndian).unwrap());
+            assert_eq!(0x10, read_u64_as_usize(&v, ByteOrder::LittleEndian).unwrap());
+            assert_eq!(0x1000_0000, read_u32_as_usize(&v, ByteOrder::BigEndian).unwrap());
+            assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                       read_u64_as_usize(&v[1..], ByteOrder::BigEndian).unwrap_err());
+        }
+
+        #[cfg(target_pointer_width = "32")]
+        #[test]
+        fn wider_than_host() {
+            let v = [0, 0, 0, 1, 0, 0, 0, 0];
+            assert_eq!(EndiannessError::Overflow, read_usize(&v, 8, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow, read_isize(&v, 8, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow, read_u64_as_usize(&v, ByteOrder::BigEndian).unwrap_err());
+        }
+    }
+
+    mod array {
+        use {EndiannessError, read_array};
+
+        #[test]
+        fn reads_prefix() {
+            let v = [1, 2, 3, 4, 5];
+            assert_eq!([1, 2, 3], read_array::<3>(&v).unwrap());
+            assert_eq!(v, read_array::<5>(&v).unwrap());
+            assert_eq!([0u8; 0], read_array::<0>(&[]).unwrap());
+            assert_eq!(EndiannessError::ShortSlice { expected: 6, found: 5 },
+                       read_array::<6>(&v).unwrap_err());
+        }
+    }
+
+    mod bools {
+        use {BoolMode, EndiannessError, read_bool, write_bool};
+
+        #[test]
+        fn modes() {
+            for &mode in &[BoolMode::Strict, BoolMode::Lenient] {
+                assert_eq!(Ok(false), read_bool(&[0, 1], mode));
+                assert_eq!(Ok(true), read_bool(&[1, 0], mode));
+                assert_eq!(EndiannessError::ShortSlice { expected: 1, found: 0 }, read_bool(&[], mode).unwrap_err());
+            }
+            for n in 2..=255u8 {
+                assert_eq!(EndiannessError::InvalidData, read_bool(&[n], BoolMode::Strict).unwrap_err());
+                assert_eq!(Ok(true), read_bool(&[n], BoolMode::Lenient));
+            }
+        }
+
+        #[test]
+        fn write() {
+            let mut v = [7u8; 2];
+            write_bool(&mut v, true).unwrap();
+            write_bool(&mut v[1..], false).unwrap();
+            assert_eq!([1, 0], v);
+            assert_eq!(EndiannessError::ShortSlice { expected: 1, found: 0 }, write_bool(&mut [], true).unwrap_err());
+        }
+    }
+
+    mod chars {
+        use {ByteOrder, EndiannessError, read_char, write_char};
+
+        #[test]
+        fn round_trip() {
+            let mut v = [0u8; 4];
+            for &c in &['\0', 'A', '\u{d7ff}', '\u{e000}', '\u{10ffff}'] {
+                write_char(&mut v, c, ByteOrder::BigEndian).unwrap();
+                assert_eq!(u32::from(c).to_be_bytes(), v);
+                assert_eq!(c, read_char(&v, ByteOrder::BigEndian).unwrap());
+            }
+        }
+
+        #[test]
+        fn invalid() {
+            for &n in &[0xd800u32, 0xdfff, 0x110000, u32::MAX] {
+                assert_eq!(EndiannessError::InvalidData,
+                           read_char(&n.to_le_bytes(), ByteOrder::LittleEndian).unwrap_err());
+            }
+            assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                       read_char(&[0, 0, 0], ByteOrder::LittleEndian).unwrap_err());
+        }
+    }
+
+    mod finite {
+        use {ByteOrder, EndiannessError, read_f32_finite, read_f64_finite};
+
+        #[test]
+        fn finite_values() {
+            let v = f32::MAX.to_le_bytes();
+            assert_eq!(f32::MAX, read_f32_finite(&v, ByteOrder::LittleEndian).unwrap());
+            let v = f64::from_bits(1).to_be_bytes();
+            assert_eq!(f64::from_bits(1), read_f64_finite(&v, ByteOrder::BigEndian).unwrap());
+            assert_eq!(-0.0, read_f64_finite(&[0, 0, 0, 0, 0, 0, 0, 0x80], ByteOrder::LittleEndian)
+                                 .unwrap());
+        }
+
+        #[test]
+        fn non_finite() {
+            for &n in &[f32::INFINITY, f32::NEG_INFINITY, f32::NAN] {
+                assert_eq!(EndiannessError::NonFinite,
+                           read_f32_finite(&n.to_be_bytes(), ByteOrder::BigEndian).unwrap_err());
+            }
+            for &n in &[f64::INFINITY, f64::NEG_INFINITY, -f64::NAN] {
+                assert_eq!(EndiannessError::NonFinite,
+                           read_f64_finite(&n.to_le_bytes(), ByteOrder::LittleEndian).unwrap_err());
+            }
+            assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                       read_f32_finite(&[0; 3], ByteOrder::BigEndian).unwrap_err());
+        }
+    }
+
+    mod test_uint {
+        use {ByteOrder, EndiannessError, read_uint, read_uint128};
+
+        extern crate quickcheck;
+        extern crate rand;
+        use self::quickcheck::{QuickCheck, StdGen};
+
+        #[test]
+        fn all_widths() {
+            fn prop(n: u64) -> bool {
+                (1..9).all(|nbytes| {
+                    let shift = (8 - nbytes) * 8;
+                    let n = (n << shift) >> shift;
+                    let big = &n.to_be_bytes()[8 - nbytes..];
+                    let little = &n.to_le_bytes()[..nbytes];
+                    n == read_uint(big, nbytes, ByteOrder::BigEndian).unwrap() &&
+                    n == read_uint(little, nbytes, ByteOrder::LittleEndian).unwrap() &&
+                    n as u128 == read_uint128(big, nbytes, ByteOrder::BigEndian).unwrap() &&
+                    n as u128 == read_uint128(little, nbytes, ByteOrder::LittleEndian).unwrap()
+                })
+            }
+
+            let f: fn(u64) -> bool = prop;
+            QuickCheck::new()
+                .gen(StdGen::new(rand::thread_rng(), u64::MAX as usize))
+                .quickcheck(f);
+        }
+
+        #[test]
+        fn wide() {
+            let v: Vec<u8> = (1..17).collect();
+            assert_eq!(0x0102030405060708090a0b0c0d0e0f10,
+                       read_uint128(&v, 16, ByteOrder::BigEndian).unwrap());
+            assert_eq!(0x0c0b0a090807060504030201,
+                       read_uint128(&v, 12, ByteOrder::LittleEndian).unwrap());
+        }
+
+        #[test]
+        fn errors() {
+            let v = [0u8; 17];
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_uint(&v, 0, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_uint(&v, 9, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_uint128(&v, 17, ByteOrder::LittleEndian).unwrap_err());
+            assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                       read_uint(&v[..2], 3, ByteOrder::LittleEndian).unwrap_err());
+        }
+    }
+
+    mod test_int {
+        use {ByteOrder, EndiannessError, read_int, read_int128};
+
+        extern crate quickcheck;
+        extern crate rand;
+        use self::quickcheck::{QuickCheck, StdGen};
+
+        #[test]
+        fn all_widths() {
+            fn prop(n: i64) -> bool {
+                (1..9).all(|nbytes| {
+                    let shift = (8 - nbytes) * 8;
+                    let n = (n << shift) >> shift;
+                    let big = &n.to_be_bytes()[8 - nbytes..];
+                    let little = &n.to_le_bytes()[..nbytes];
+                    n == read_int(big, nbytes, ByteOrder::BigEndian).unwrap() &&
+                    n == read_int(little, nbytes, ByteOrder::LittleEndian).unwrap() &&
+                    n as i128 == read_int128(big, nbytes, ByteOrder::BigEndian).unwrap() &&
+                    n as i128 == read_int128(little, nbytes, ByteOrder::LittleEndian).unwrap()
+                })
+            }
+
+            let f: fn(i64) -> bool = prop;
+            QuickCheck::new()
+                .gen(StdGen::new(rand::thread_rng(), i64::MAX as usize))
+                .quickcheck(f);
+        }
+
+        #[test]
+        fn wide() {
+            let mut v = [0xffu8; 16];
+            v[0] = 0x80;
+            assert_eq!(i128::MIN | ((1 << 120) - 1),
+                       read_int128(&v, 16, ByteOrder::BigEndian).unwrap());
+            assert_eq!(-128, read_int128(&v, 9, ByteOrder::LittleEndian).unwrap());
+        }
+
+        #[test]
+        fn errors() {
+            let v = [0u8; 17];
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_int(&v, 0, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_int(&v, 9, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       read_int128(&v, 17, ByteOrder::LittleEndian).unwrap_err());
+            assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                       read_int(&v[..2], 3, ByteOrder::LittleEndian).unwrap_err());
+        }
+    }
+
+    mod test_write_uint {
+        use {ByteOrder, EndiannessError};
+        use {read_uint, read_int, write_uint, write_uint128, write_int, write_int128};
+
+        extern crate quickcheck;
+        extern crate rand;
+        use self::quickcheck::{QuickCheck, StdGen};
+
+        #[test]
+        fn unsigned_round_trip() {
+            fn prop(n: u64) -> bool {
+                (1..9).all(|nbytes| {
+                    let shift = (8 - nbytes) * 8;
+                    let n = (n << shift) >> shift;
+                    let mut data = [0u8; 8];
+                    write_uint(&mut data, n, nbytes, ByteOrder::BigEndian).unwrap();
+                    let big = data[..nbytes] == n.to_be_bytes()[8 - nbytes..] &&
+                              n == read_uint(&data, nbytes, ByteOrder::BigEndian).unwrap();
+                    write_uint(&mut data, n, nbytes, ByteOrder::LittleEndian).unwrap();
+                    let little = data[..nbytes] == n.to_le_bytes()[..nbytes] &&
+                                 n == read_uint(&data, nbytes, ByteOrder::LittleEndian).unwrap();
+                    big && little
+                })
+            }
+
+            let f: fn(u64) -> bool = prop;
+            QuickCheck::new()
+                .gen(StdGen::new(rand::thread_rng(), u64::MAX as usize))
+                .quickcheck(f);
+        }
+
+        #[test]
+        fn signed_round_trip() {
+            fn prop(n: i64) -> bool {
+                (1..9).all(|nbytes| {
+                    let shift = (8 - nbytes) * 8;
+                    let n = (n << shift) >> shift;
+                    let mut data = [0u8; 8];
+                    write_int(&mut data, n, nbytes, ByteOrder::BigEndian).unwrap();
+                    let big = data[..nbytes] == n.to_be_bytes()[8 - nbytes..] &&
+                              n == read_int(&data, nbytes, ByteOrder::BigEndian).unwrap();
+                    write_int(&mut data, n, nbytes, ByteOrder::LittleEndian).unwrap();
+                    let little = data[..nbytes] == n.to_le_bytes()[..nbytes] &&
+                                 n == read_int(&data, nbytes, ByteOrder::LittleEndian).unwrap();
+                    big && little
+                })
+            }
+
+            let f: fn(i64) -> bool = prop;
+            QuickCheck::new()
+                .gen(StdGen::new(rand::thread_rng(), i64::MAX as usize))
+                .quickcheck(f);
+        }
+
+        #[test]
+        fn wide() {
+            let mut v = [0u8; 16];
+            write_uint128(&mut v, u128::MAX, 16, ByteOrder::BigEndian).unwrap();
+            assert_eq!([0xff; 16], v);
+            write_int128(&mut v, -2, 16, ByteOrder::LittleEndian).unwrap();
+            assert_eq!(0xfe, v[0]);
+            write_int128(&mut v, i128::MIN >> 40, 11, ByteOrder::BigEndian).unwrap();
+            assert_eq!([0x80, 0, 0], v[..3]);
+        }
+
+        #[test]
+        fn errors() {
+            let mut v = [0u8; 17];
+            assert_eq!(EndiannessError::InvalidWidth,
+                       write_uint(&mut v, 0, 0, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       write_int(&mut v, 0, 9, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::InvalidWidth,
+                       write_uint128(&mut v, 0, 17, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                       write_uint(&mut v[..2], 0, 3, ByteOrder::LittleEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_uint(&mut v, 0x100, 1, ByteOrder::LittleEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_int(&mut v, 128, 1, ByteOrder::LittleEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_int(&mut v, -129, 1, ByteOrder::BigEndian).unwrap_err());
+            assert_eq!(EndiannessError::Overflow,
+                       write_int128(&mut v, i128::MAX, 15, ByteOrder::BigEndian).unwrap_err());
+        }
+    }
 }
@@ -0,0 +1,75 @@
+//! Hardware addresses, stored in transmission order regardless of the byte order of the
+//! surrounding format.
+
+use core::fmt;
+
+use {EndiannessResult, read_array};
+
+macro_rules! eui {
+    ($name:ident, $size:expr, $read:ident, $doc:expr, $example:expr, $read_doc:expr) => (
+        #[doc = $doc]
+        ///
+        #[doc = concat!("Displays as colon-separated lowercase hex, like `", $example, "`.")]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(pub [u8; $size]);
+
+        impl $name {
+            /// Returns the bytes of the address.
+            pub fn octets(&self) -> [u8; $size] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $size]> for $name {
+            fn from(octets: [u8; $size]) -> $name {
+                $name(octets)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for (i, octet) in self.0.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(":")?;
+                    }
+                    write!(f, "{:02x}", octet)?;
+                }
+                Ok(())
+            }
+        }
+
+        #[doc = $read_doc]
+        pub fn $read(data: &[u8]) -> EndiannessResult<$name> {
+            read_array::<$size>(data).map($name)
+        }
+    );
+}
+
+eui!(Eui48, 6, read_eui48, "A 48-bit hardware address, such as an Ethernet MAC address.",
+     "00:1a:2b:3c:4d:5e", "Reads an EUI-48 address from the first 6 bytes of a stream.");
+eui!(Eui64, 8, read_eui64, "A 64-bit hardware address, such as an IEEE 802.15.4 or FireWire address.",
+     "02:12:4b:00:0a:bc:de:f0", "Reads an EUI-64 address from the first 8 bytes of a stream.");
+
+#[cfg(test)]
+mod tests {
+    use {Eui48, Eui64, EndiannessError, read_eui48, read_eui64};
+
+    #[test]
+    fn eui48() {
+        let v = [0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0xff, 0x01];
+        let addr = read_eui48(&v).unwrap();
+        assert_eq!(Eui48([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0xff]), addr);
+        assert_eq!("00:1a:2b:3c:4d:ff", addr.to_string());
+        assert_eq!(EndiannessError::ShortSlice { expected: 6, found: 5 }, read_eui48(&v[..5]).unwrap_err());
+    }
+
+    #[test]
+    fn eui64() {
+        let v = [2, 0x12, 0x4b, 0, 0x0a, 0xbc, 0xde, 0xf0];
+        let addr = read_eui64(&v).unwrap();
+        assert_eq!(Eui64::from(v), addr);
+        assert_eq!(v, addr.octets());
+        assert_eq!("02:12:4b:00:0a:bc:de:f0", addr.to_string());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 0 }, read_eui64(&[]).unwrap_err());
+    }
+}
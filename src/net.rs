@@ -0,0 +1,71 @@
+//! IP addresses, which are always stored in network byte order (big-endian).
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use {ByteOrder, EndiannessResult, read_array, write_u128, write_u32};
+
+/// Reads an IPv4 address from the first 4 bytes of a stream.
+///
+/// ```rust
+/// use endianness::*;
+/// use std::net::Ipv4Addr;
+///
+/// assert_eq!(Ipv4Addr::new(192, 168, 0, 1), read_ipv4(&[192, 168, 0, 1, 80]).unwrap());
+/// ```
+pub fn read_ipv4(data: &[u8]) -> EndiannessResult<Ipv4Addr> {
+    read_array::<4>(data).map(Ipv4Addr::from)
+}
+
+/// Writes an IPv4 address to the first 4 bytes of a stream.
+pub fn write_ipv4(data: &mut [u8], addr: Ipv4Addr) -> EndiannessResult<()> {
+    write_u32(data, addr.into(), ByteOrder::BigEndian)
+}
+
+/// Reads an IPv6 address from the first 16 bytes of a stream.
+///
+/// ```rust
+/// use endianness::*;
+/// use std::net::Ipv6Addr;
+///
+/// let mut v = [0u8; 16];
+/// v[15] = 1;
+/// assert_eq!(Ipv6Addr::LOCALHOST, read_ipv6(&v).unwrap());
+/// ```
+pub fn read_ipv6(data: &[u8]) -> EndiannessResult<Ipv6Addr> {
+    read_array::<16>(data).map(Ipv6Addr::from)
+}
+
+/// Writes an IPv6 address to the first 16 bytes of a stream.
+pub fn write_ipv6(data: &mut [u8], addr: Ipv6Addr) -> EndiannessResult<()> {
+    write_u128(data, addr.into(), ByteOrder::BigEndian)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use EndiannessError;
+    use {read_ipv4, read_ipv6, write_ipv4, write_ipv6};
+
+    #[test]
+    fn ipv4() {
+        let mut v = [0u8; 5];
+        write_ipv4(&mut v, Ipv4Addr::new(10, 0, 0, 254)).unwrap();
+        assert_eq!([10, 0, 0, 254, 0], v);
+        assert_eq!(Ipv4Addr::new(10, 0, 0, 254), read_ipv4(&v).unwrap());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 }, read_ipv4(&v[..3]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   write_ipv4(&mut v[..3], Ipv4Addr::LOCALHOST).unwrap_err());
+    }
+
+    #[test]
+    fn ipv6() {
+        let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x42);
+        let mut v = [0u8; 16];
+        write_ipv6(&mut v, addr).unwrap();
+        assert_eq!([0x20, 0x01, 0x0d, 0xb8], v[..4]);
+        assert_eq!(0x42, v[15]);
+        assert_eq!(addr, read_ipv6(&v).unwrap());
+        assert_eq!(EndiannessError::ShortSlice { expected: 16, found: 15 }, read_ipv6(&v[1..]).unwrap_err());
+    }
+}
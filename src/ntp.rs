@@ -0,0 +1,84 @@
+//! NTP timestamps.
+//!
+//! An NTP timestamp is a big-endian 32.32 fixed-point number: the whole seconds since
+//! 1900-01-01 00:00 UTC followed by the fraction of a second in units of 2^-32 s.
+
+use core::time::Duration;
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+use {read_u64, write_u64};
+
+/// Seconds between the NTP epoch (1900) and the Unix epoch (1970).
+pub const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Reads an NTP timestamp and returns the time since the NTP epoch.
+///
+/// The fraction is truncated to whole nanoseconds.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let time = read_ntp_timestamp(&[0xe0, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00]).unwrap();
+/// assert_eq!(3_758_096_384, time.as_secs());
+/// assert_eq!(500_000_000, time.subsec_nanos());
+/// ```
+pub fn read_ntp_timestamp(data: &[u8]) -> EndiannessResult<Duration> {
+    let value = read_u64(data, ByteOrder::BigEndian)?;
+    let nanos = ((value & 0xffff_ffff) * NANOS_PER_SEC) >> 32;
+    Ok(Duration::new(value >> 32, nanos as u32))
+}
+
+/// Writes an NTP timestamp for the given time since the NTP epoch.
+///
+/// The fraction is rounded up, so reading the timestamp back yields the same number of
+/// nanoseconds. Returns `Overflow` if the seconds do not fit into 32 bits.
+pub fn write_ntp_timestamp(data: &mut [u8], time: Duration) -> EndiannessResult<()> {
+    if time.as_secs() > u64::from(u32::MAX) {
+        return Err(EndiannessError::Overflow);
+    }
+    let fraction = (u64::from(time.subsec_nanos()) << 32).div_ceil(NANOS_PER_SEC);
+    write_u64(data, (time.as_secs() << 32) | fraction, ByteOrder::BigEndian)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use EndiannessError;
+    use super::*;
+
+    #[test]
+    fn fraction() {
+        let time = read_ntp_timestamp(&[0, 0, 0, 1, 0x40, 0, 0, 0]).unwrap();
+        assert_eq!(Duration::new(1, 250_000_000), time);
+        // The smallest fraction is below a nanosecond.
+        let time = read_ntp_timestamp(&[0, 0, 0, 0, 0, 0, 0, 1]).unwrap();
+        assert_eq!(Duration::new(0, 0), time);
+        let time = read_ntp_timestamp(&[0xff; 8]).unwrap();
+        assert_eq!(Duration::new(0xffff_ffff, 999_999_999), time);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut v = [0u8; 8];
+        for &nanos in &[0, 1, 2, 499_999_999, 500_000_000, 999_999_999] {
+            let time = Duration::new(NTP_UNIX_OFFSET + 1_700_000_000, nanos);
+            write_ntp_timestamp(&mut v, time).unwrap();
+            assert_eq!(time, read_ntp_timestamp(&v).unwrap());
+        }
+        write_ntp_timestamp(&mut v, Duration::new(2, 500_000_000)).unwrap();
+        assert_eq!([0, 0, 0, 2, 0x80, 0, 0, 0], v);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   read_ntp_timestamp(&[0; 7]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   write_ntp_timestamp(&mut [0; 7], Duration::new(0, 0)).unwrap_err());
+        assert_eq!(EndiannessError::Overflow,
+                   write_ntp_timestamp(&mut [0; 8], Duration::new(1 << 32, 0)).unwrap_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! Bulk conversions split across threads with rayon.
+//!
+//! Both slices are cut into chunks of roughly `CHUNK_BYTES` bytes, and every chunk is
+//! converted with the sequential `read_*_into` function on the rayon thread pool.
+
+use rayon::prelude::*;
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+use {read_u16_into, read_i16_into, read_u32_into, read_i32_into, read_u64_into, read_i64_into,
+     read_u128_into, read_i128_into, read_f32_into, read_f64_into};
+
+// Large enough to amortize scheduling, small enough to keep every thread busy.
+const CHUNK_BYTES: usize = 64 * 1024;
+
+macro_rules! par_read_into {
+    ($($name:ident, $read_into:ident, $ty:ident, $size:expr, $desc:expr;)*) => (
+        $(
+            #[doc = concat!("Reads ", $desc, "s from a stream of bytes until `dst` is filled, ")]
+            #[doc = "using all threads of the rayon pool."]
+            ///
+            /// Requires the `rayon` feature.
+            pub fn $name(src: &[u8], dst: &mut [$ty], endianness: ByteOrder) -> EndiannessResult<()> {
+                let expected = dst.len() * $size;
+                if src.len() < expected {
+                    return Err(EndiannessError::ShortSlice { expected, found: src.len() });
+                }
+                let chunk = CHUNK_BYTES / $size;
+                dst.par_chunks_mut(chunk)
+                    .zip(src.par_chunks(chunk * $size))
+                    .try_for_each(|(dst, src)| $read_into(src, dst, endianness))
+            }
+        )*
+    );
+}
+
+par_read_into! {
+    par_read_u16_into, read_u16_into, u16, 2, "unsigned 16-bit integer";
+    par_read_i16_into, read_i16_into, i16, 2, "signed 16-bit integer";
+    par_read_u32_into, read_u32_into, u32, 4, "unsigned 32-bit integer";
+    par_read_i32_into, read_i32_into, i32, 4, "signed 32-bit integer";
+    par_read_u64_into, read_u64_into, u64, 8, "unsigned 64-bit integer";
+    par_read_i64_into, read_i64_into, i64, 8, "signed 64-bit integer";
+    par_read_u128_into, read_u128_into, u128, 16, "unsigned 128-bit integer";
+    par_read_i128_into, read_i128_into, i128, 16, "signed 128-bit integer";
+    par_read_f32_into, read_f32_into, f32, 4, "single-precision floating point number";
+    par_read_f64_into, read_f64_into, f64, 8, "double-precision floating point number";
+}
+
+#[cfg(test)]
+mod tests {
+    // Parallel functions must agree with the sequential ones over several chunks.
+    macro_rules! parallel_correctness {
+        ($name:ident, $ty:ident, $size:expr, $par_read_into:ident, $read_into:ident) => (
+            mod $name {
+                use {ByteOrder, EndiannessError, $par_read_into, $read_into};
+                use super::super::CHUNK_BYTES;
+
+                fn check(endianness: ByteOrder) {
+                    let len = CHUNK_BYTES * 3 / $size + 7;
+                    let src: Vec<u8> = (0..len * $size + 1).map(|i| (i * 37 + i / 251) as u8).collect();
+                    let mut expected: Vec<$ty> = vec![Default::default(); len];
+                    let mut actual: Vec<$ty> = vec![Default::default(); len];
+                    $read_into(&src, &mut expected, endianness).unwrap();
+                    $par_read_into(&src, &mut actual, endianness).unwrap();
+                    assert!(expected.iter().zip(&actual).all(|(e, a)| e.to_ne_bytes() == a.to_ne_bytes()));
+                }
+
+                #[test]
+                fn big_endian() {
+                    check(ByteOrder::BigEndian);
+                }
+
+                #[test]
+                fn little_endian() {
+                    check(ByteOrder::LittleEndian);
+                }
+
+                #[test]
+                fn short_slice() {
+                    let src = vec![0u8; CHUNK_BYTES * 2 - 1];
+                    let mut dst: Vec<$ty> = vec![Default::default(); CHUNK_BYTES * 2 / $size];
+                    assert_eq!(EndiannessError::ShortSlice { expected: CHUNK_BYTES * 2, found: src.len() },
+                               $par_read_into(&src, &mut dst, ByteOrder::BigEndian).unwrap_err());
+                }
+            }
+        );
+    }
+
+    parallel_correctness!(test_u16, u16, 2, par_read_u16_into, read_u16_into);
+    parallel_correctness!(test_i32, i32, 4, par_read_i32_into, read_i32_into);
+    parallel_correctness!(test_u64, u64, 8, par_read_u64_into, read_u64_into);
+    parallel_correctness!(test_i128, i128, 16, par_read_i128_into, read_i128_into);
+    parallel_correctness!(test_f32, f32, 4, par_read_f32_into, read_f32_into);
+    parallel_correctness!(test_f64, f64, 8, par_read_f64_into, read_f64_into);
+}
@@ -0,0 +1,130 @@
+//! PDP-11 (middle-endian) byte order for 32-bit and 64-bit values.
+//!
+//! The PDP-11 stores 16-bit words in little-endian, but lays out the words of longer
+//! values most significant first. The 32-bit value `0x0A0B0C0D` is therefore stored as
+//! `0B 0A 0D 0C`, which is often written as "BADC".
+
+use {ByteOrder, EndiannessResult};
+use {read_u32, read_u64, write_u32, write_u64};
+
+// Swaps the two bytes of every 16-bit word; converts between big-endian and PDP-11 layouts.
+fn swap_words_u32(value: u32) -> u32 {
+    ((value >> 8) & 0x00ff_00ff) | ((value & 0x00ff_00ff) << 8)
+}
+
+fn swap_words_u64(value: u64) -> u64 {
+    ((value >> 8) & 0x00ff_00ff_00ff_00ff) | ((value & 0x00ff_00ff_00ff_00ff) << 8)
+}
+
+/// Reads unsigned 32-bit integer in PDP-11 byte order.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(0x0a0b0c0d, read_u32_pdp(&[0x0b, 0x0a, 0x0d, 0x0c]).unwrap());
+/// ```
+pub fn read_u32_pdp(data: &[u8]) -> EndiannessResult<u32> {
+    Ok(swap_words_u32(read_u32(data, ByteOrder::BigEndian)?))
+}
+
+/// Reads signed 32-bit integer in PDP-11 byte order.
+pub fn read_i32_pdp(data: &[u8]) -> EndiannessResult<i32> {
+    Ok(read_u32_pdp(data)? as i32)
+}
+
+/// Reads a single-precision floating point number in PDP-11 byte order.
+pub fn read_f32_pdp(data: &[u8]) -> EndiannessResult<f32> {
+    Ok(f32::from_bits(read_u32_pdp(data)?))
+}
+
+/// Reads unsigned 64-bit integer in PDP-11 byte order.
+pub fn read_u64_pdp(data: &[u8]) -> EndiannessResult<u64> {
+    Ok(swap_words_u64(read_u64(data, ByteOrder::BigEndian)?))
+}
+
+/// Reads signed 64-bit integer in PDP-11 byte order.
+pub fn read_i64_pdp(data: &[u8]) -> EndiannessResult<i64> {
+    Ok(read_u64_pdp(data)? as i64)
+}
+
+/// Reads a double-precision floating point number in PDP-11 byte order.
+pub fn read_f64_pdp(data: &[u8]) -> EndiannessResult<f64> {
+    Ok(f64::from_bits(read_u64_pdp(data)?))
+}
+
+/// Writes unsigned 32-bit integer in PDP-11 byte order.
+pub fn write_u32_pdp(data: &mut [u8], value: u32) -> EndiannessResult<()> {
+    write_u32(data, swap_words_u32(value), ByteOrder::BigEndian)
+}
+
+/// Writes signed 32-bit integer in PDP-11 byte order.
+pub fn write_i32_pdp(data: &mut [u8], value: i32) -> EndiannessResult<()> {
+    write_u32_pdp(data, value as u32)
+}
+
+/// Writes a single-precision floating point number in PDP-11 byte order.
+pub fn write_f32_pdp(data: &mut [u8], value: f32) -> EndiannessResult<()> {
+    write_u32_pdp(data, value.to_bits())
+}
+
+/// Writes unsigned 64-bit integer in PDP-11 byte order.
+pub fn write_u64_pdp(data: &mut [u8], value: u64) -> EndiannessResult<()> {
+    write_u64(data, swap_words_u64(value), ByteOrder::BigEndian)
+}
+
+/// Writes signed 64-bit integer in PDP-11 byte order.
+pub fn write_i64_pdp(data: &mut [u8], value: i64) -> EndiannessResult<()> {
+    write_u64_pdp(data, value as u64)
+}
+
+/// Writes a double-precision floating point number in PDP-11 byte order.
+pub fn write_f64_pdp(data: &mut [u8], value: f64) -> EndiannessResult<()> {
+    write_u64_pdp(data, value.to_bits())
+}
+
+#[cfg(test)]
+mod tests {
+    use EndiannessError;
+    use super::*;
+
+    #[test]
+    fn layout_32() {
+        let mut v = [0u8; 4];
+        write_u32_pdp(&mut v, 0x0a0b0c0d).unwrap();
+        assert_eq!([0x0b, 0x0a, 0x0d, 0x0c], v);
+        assert_eq!(0x0a0b0c0d, read_u32_pdp(&v).unwrap());
+    }
+
+    #[test]
+    fn layout_64() {
+        let mut v = [0u8; 8];
+        write_u64_pdp(&mut v, 0x0102030405060708).unwrap();
+        assert_eq!([2, 1, 4, 3, 6, 5, 8, 7], v);
+        assert_eq!(0x0102030405060708, read_u64_pdp(&v).unwrap());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut v = [0u8; 8];
+        write_i32_pdp(&mut v, -2).unwrap();
+        assert_eq!(-2, read_i32_pdp(&v).unwrap());
+        write_f32_pdp(&mut v, -127.5).unwrap();
+        assert_eq!(-127.5, read_f32_pdp(&v).unwrap());
+        write_i64_pdp(&mut v, -3).unwrap();
+        assert_eq!(-3, read_i64_pdp(&v).unwrap());
+        write_f64_pdp(&mut v, 1.0e-300).unwrap();
+        assert_eq!(1.0e-300, read_f64_pdp(&v).unwrap());
+    }
+
+    #[test]
+    fn short_slice() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_u32_pdp(&[0; 3]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   read_f64_pdp(&[0; 7]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   write_i32_pdp(&mut [0; 3], 0).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   write_u64_pdp(&mut [0; 7], 0).unwrap_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Values stored after their length.
+//!
+//! The length is an unsigned integer of 1 to 8 bytes in the given byte order. It is
+//! checked against a maximum chosen by the caller, and against the length of the stream,
+//! before anything is allocated, so a corrupt or hostile length cannot exhaust memory.
+
+#[cfg(feature = "std")]
+use EndianRead;
+use {ByteOrder, EndiannessError, EndiannessResult, read_uint};
+
+// Reads the length prefix and returns the length and the stream after the prefix.
+fn read_len(data: &[u8], width: usize, max_len: usize, endianness: ByteOrder) -> EndiannessResult<(usize, &[u8])> {
+    let len = read_uint(data, width, endianness)?;
+    if len > max_len as u64 {
+        return Err(EndiannessError::Overflow);
+    }
+    Ok((len as usize, &data[width..]))
+}
+
+/// Reads a byte string stored after its length, a `width`-byte unsigned integer.
+///
+/// Returns the bytes and the number of bytes read, prefix included. Returns `InvalidWidth`
+/// if `width` is not between 1 and 8, `Overflow` if the length exceeds `max_len`, and
+/// `ShortSlice` if the stream is shorter than the prefix and the bytes it announces.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0, 3, b'a', b'b', b'c', 9];
+/// assert_eq!((&b"abc"[..], 5), read_len_prefixed_bytes(&v, 2, 16, ByteOrder::BigEndian).unwrap());
+/// assert_eq!(EndiannessError::Overflow, read_len_prefixed_bytes(&v, 2, 2, ByteOrder::BigEndian).unwrap_err());
+/// ```
+pub fn read_len_prefixed_bytes(data: &[u8], width: usize, max_len: usize, endianness: ByteOrder)
+                               -> EndiannessResult<(&[u8], usize)> {
+    let (len, rest) = read_len(data, width, max_len, endianness)?;
+    match rest.get(..len) {
+        Some(bytes) => Ok((bytes, width + len)),
+        None => Err(EndiannessError::ShortSlice { expected: len.saturating_add(width), found: data.len() }),
+    }
+}
+
+/// Reads a vector of values stored after their count, a `width`-byte unsigned integer.
+/// The count and the values are in the same byte order.
+///
+/// Returns the values and the number of bytes read, prefix included. Fails like
+/// `read_len_prefixed_bytes`, with `max_len` limiting the number of values, and with the
+/// error of the first value that cannot be read. Requires the `std` feature.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [2, 1, 0, 2, 0];
+/// let (values, read) = read_len_prefixed_vec::<u16>(&v, 1, 64, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!((vec![1, 2], 5), (values, read));
+/// ```
+#[cfg(feature = "std")]
+pub fn read_len_prefixed_vec<T: EndianRead>(data: &[u8], width: usize, max_len: usize, endianness: ByteOrder)
+                                            -> EndiannessResult<(Vec<T>, usize)> {
+    let (len, rest) = read_len(data, width, max_len, endianness)?;
+    let size = len.checked_mul(T::SIZE).ok_or(EndiannessError::Overflow)?;
+    if rest.len() < size {
+        let expected = size.saturating_add(width);
+        return Err(EndiannessError::ShortSlice { expected, found: data.len() });
+    }
+    let mut values = Vec::with_capacity(len);
+    for i in 0..len {
+        values.push(T::read(&rest[i * T::SIZE..], endianness)?);
+    }
+    Ok((values, width + size))
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError, read_len_prefixed_bytes};
+    #[cfg(feature = "std")]
+    use read_len_prefixed_vec;
+
+    #[test]
+    fn bytes() {
+        let v = [5, 0, 0, 0, 1, 2, 3, 4, 5, 6];
+        assert_eq!((&v[4..9], 9), read_len_prefixed_bytes(&v, 4, 5, ByteOrder::LittleEndian).unwrap());
+        assert_eq!((&v[..0], 4), read_len_prefixed_bytes(&[0; 4], 4, 0, ByteOrder::BigEndian).unwrap());
+        assert_eq!(EndiannessError::Overflow,
+                   read_len_prefixed_bytes(&v, 4, 4, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 9, found: 8 },
+                   read_len_prefixed_bytes(&v[..8], 4, 5, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_len_prefixed_bytes(&v[..3], 4, 5, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth,
+                   read_len_prefixed_bytes(&v, 9, 5, ByteOrder::LittleEndian).unwrap_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn vec() {
+        let v = [0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 7];
+        assert_eq!((vec![1u32, 2], 10), read_len_prefixed_vec(&v, 2, 2, ByteOrder::BigEndian).unwrap());
+        assert_eq!(EndiannessError::Overflow,
+                   read_len_prefixed_vec::<u32>(&v, 2, 1, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 10, found: 9 },
+                   read_len_prefixed_vec::<u32>(&v[..9], 2, 2, ByteOrder::BigEndian).unwrap_err());
+        // A huge count fails on the length check, before anything is allocated.
+        let huge = [0xff; 8];
+        assert_eq!(EndiannessError::ShortSlice { expected: usize::MAX, found: 8 },
+                   read_len_prefixed_vec::<u8>(&huge, 8, usize::MAX, ByteOrder::BigEndian).unwrap_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Fixed-point numbers in the Q format used by DSP data.
+//!
+//! A Qm.n number is a two's complement integer of m + n + 1 bits whose lowest n bits are
+//! the fraction, so its value is the integer divided by 2^n. UQm.n numbers are the
+//! unsigned counterpart without the sign bit. Q15 and Q31 are the common 16 and 32-bit
+//! formats with a range of [-1, 1).
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+use {pow2, read_int, read_uint, write_int, write_uint};
+
+fn check_fraction(frac_bits: u32) -> EndiannessResult<i32> {
+    if frac_bits > 512 {
+        Err(EndiannessError::InvalidWidth)
+    } else {
+        Ok(frac_bits as i32)
+    }
+}
+
+// Scales a value to the raw integer, rounding half away from zero, and checks its range.
+fn to_raw(value: f64, frac_bits: i32, min: i128, max: i128) -> EndiannessResult<i128> {
+    if value.is_nan() {
+        return Err(EndiannessError::Overflow);
+    }
+    let scaled = value * pow2(frac_bits);
+    // Casts saturate, so anything far out of range still ends up outside [min, max].
+    let raw = if scaled < 0.0 { (scaled - 0.5) as i128 } else { (scaled + 0.5) as i128 };
+    if raw < min || raw > max {
+        Err(EndiannessError::Overflow)
+    } else {
+        Ok(raw)
+    }
+}
+
+/// Reads a signed Qm.n fixed-point number of `nbytes` bytes with `frac_bits` fraction bits.
+///
+/// `nbytes` must be between 1 and 8, and `frac_bits` at most 512, otherwise
+/// `InvalidWidth` is returned. The result is exact as long as the integer fits into the
+/// 53-bit mantissa of `f64`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// // Q8.8: 0x0180 is 1.5
+/// assert_eq!(1.5, read_fixed(&[0x01, 0x80], 2, 8, ByteOrder::BigEndian).unwrap());
+/// ```
+pub fn read_fixed(data: &[u8], nbytes: usize, frac_bits: u32, endianness: ByteOrder)
+                  -> EndiannessResult<f64> {
+    let frac_bits = check_fraction(frac_bits)?;
+    Ok(read_int(data, nbytes, endianness)? as f64 * pow2(-frac_bits))
+}
+
+/// Reads an unsigned UQm.n fixed-point number of `nbytes` bytes with `frac_bits`
+/// fraction bits.
+pub fn read_ufixed(data: &[u8], nbytes: usize, frac_bits: u32, endianness: ByteOrder)
+                   -> EndiannessResult<f64> {
+    let frac_bits = check_fraction(frac_bits)?;
+    Ok(read_uint(data, nbytes, endianness)? as f64 * pow2(-frac_bits))
+}
+
+/// Writes a signed Qm.n fixed-point number of `nbytes` bytes with `frac_bits` fraction bits.
+///
+/// The value is rounded to the nearest representable number, with ties away from zero.
+/// Returns `Overflow` if the result is out of range or the value is NaN.
+pub fn write_fixed(data: &mut [u8], value: f64, nbytes: usize, frac_bits: u32,
+                   endianness: ByteOrder) -> EndiannessResult<()> {
+    if nbytes == 0 || nbytes > 8 {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    let frac_bits = check_fraction(frac_bits)?;
+    let max = (1i128 << (8 * nbytes - 1)) - 1;
+    let raw = to_raw(value, frac_bits, -max - 1, max)?;
+    write_int(data, raw as i64, nbytes, endianness)
+}
+
+/// Writes an unsigned UQm.n fixed-point number of `nbytes` bytes with `frac_bits`
+/// fraction bits, rounding like `write_fixed`.
+pub fn write_ufixed(data: &mut [u8], value: f64, nbytes: usize, frac_bits: u32,
+                    endianness: ByteOrder) -> EndiannessResult<()> {
+    if nbytes == 0 || nbytes > 8 {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    let frac_bits = check_fraction(frac_bits)?;
+    let raw = to_raw(value, frac_bits, 0, (1i128 << (8 * nbytes)) - 1)?;
+    write_uint(data, raw as u64, nbytes, endianness)
+}
+
+/// Reads a Q15 fixed-point number, a 16-bit value in the range [-1, 1).
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(-0.5, read_q15(&[0x00, 0xc0], ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_q15(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f32> {
+    Ok(read_fixed(data, 2, 15, endianness)? as f32)
+}
+
+/// Reads a Q31 fixed-point number, a 32-bit value in the range [-1, 1).
+pub fn read_q31(data: &[u8], endianness: ByteOrder) -> EndiannessResult<f64> {
+    read_fixed(data, 4, 31, endianness)
+}
+
+/// Writes a Q15 fixed-point number, rounding like `write_fixed`.
+///
+/// Returns `Overflow` for values outside [-1, 1 - 2^-15].
+pub fn write_q15(data: &mut [u8], value: f32, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_fixed(data, f64::from(value), 2, 15, endianness)
+}
+
+/// Writes a Q31 fixed-point number, rounding like `write_fixed`.
+///
+/// Returns `Overflow` for values outside [-1, 1 - 2^-31].
+pub fn write_q31(data: &mut [u8], value: f64, endianness: ByteOrder) -> EndiannessResult<()> {
+    write_fixed(data, value, 4, 31, endianness)
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError};
+    use super::*;
+
+    #[test]
+    fn q15_q31() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(-1.0, read_q15(&[0x80, 0x00], order).unwrap());
+        assert_eq!(0.5, read_q15(&[0x40, 0x00], order).unwrap());
+        assert_eq!(1.0 - 1.0 / 32768.0, read_q15(&[0x7f, 0xff], order).unwrap());
+        assert_eq!(-1.0 / 2147483648.0, read_q31(&[0xff, 0xff, 0xff, 0xff], order).unwrap());
+
+        let mut v = [0u8; 4];
+        write_q15(&mut v, 0.25, ByteOrder::LittleEndian).unwrap();
+        assert_eq!([0x00, 0x20], v[..2]);
+        write_q31(&mut v, -1.0, order).unwrap();
+        assert_eq!([0x80, 0, 0, 0], v);
+        assert_eq!(EndiannessError::Overflow, write_q15(&mut v, 1.0, order).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, write_q31(&mut v, f64::NAN, order).unwrap_err());
+    }
+
+    #[test]
+    fn arbitrary_formats() {
+        let order = ByteOrder::LittleEndian;
+        // Q3.4 in one byte: -2.25 is -36 = 0xdc.
+        assert_eq!(-2.25, read_fixed(&[0xdc], 1, 4, order).unwrap());
+        // UQ16.16: 0x0001_8000 is 1.5
+        assert_eq!(1.5, read_ufixed(&[0x00, 0x80, 0x01, 0x00], 4, 16, order).unwrap());
+        // Integers are Q formats without a fraction.
+        assert_eq!(-3.0, read_fixed(&[0xfd, 0xff, 0xff], 3, 0, order).unwrap());
+        // More fraction bits than value bits are allowed.
+        assert_eq!(1.0 / 1024.0, read_ufixed(&[0x01], 1, 10, order).unwrap());
+
+        let mut v = [0u8; 8];
+        for &value in &[0.0, 1.5, -1.5, 1234.0625, -0.0078125] {
+            write_fixed(&mut v, value, 8, 32, order).unwrap();
+            assert_eq!(value, read_fixed(&v, 8, 32, order).unwrap());
+            write_fixed(&mut v, value, 3, 7, ByteOrder::BigEndian).unwrap();
+            assert_eq!(value, read_fixed(&v, 3, 7, ByteOrder::BigEndian).unwrap());
+        }
+    }
+
+    #[test]
+    fn rounding() {
+        let order = ByteOrder::BigEndian;
+        let mut v = [0u8; 2];
+        write_fixed(&mut v, 0.3, 2, 8, order).unwrap();
+        assert_eq!([0x00, 0x4d], v);
+        write_fixed(&mut v, -0.001953125, 2, 8, order).unwrap();
+        assert_eq!([0xff, 0xff], v);
+        write_ufixed(&mut v, 255.998, 2, 8, order).unwrap();
+        assert_eq!([0xff, 0xff], v);
+        assert_eq!(EndiannessError::Overflow, write_ufixed(&mut v, 255.999, 2, 8, order).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, write_ufixed(&mut v, -0.5, 2, 8, order).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, write_fixed(&mut v, 1e300, 2, 8, order).unwrap_err());
+    }
+
+    #[test]
+    fn errors() {
+        let order = ByteOrder::BigEndian;
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_q31(&[0; 3], order).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth, read_fixed(&[0; 9], 9, 8, order).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth, read_ufixed(&[0; 2], 2, 513, order).unwrap_err());
+        assert_eq!(EndiannessError::InvalidWidth, write_fixed(&mut [0; 2], 0.0, 0, 8, order).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   write_q15(&mut [0; 1], 0.0, order).unwrap_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! Layouts described at runtime, for tools that handle user-supplied formats: parsing a
+//! buffer into a `Value` tree and writing such a tree back.
+//!
+//! Requires the `std` feature.
+
+use std::string::String;
+use std::vec::Vec;
+
+use {ByteCursor, ByteOrder, ByteWriter, CursorResult, EndiannessError, EndiannessResult, Scalar, ScalarType};
+
+/// The type of a field in a `Schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    /// A number.
+    Scalar(ScalarType),
+    /// The given number of bytes, kept as they are.
+    Bytes(usize),
+    /// A nested record. Its fields have their own byte orders.
+    Record(Schema),
+}
+
+/// The description of one field of a `Schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSpec {
+    /// The name of the field in the parsed `Value::Record`.
+    pub name: String,
+    /// The type of the field.
+    pub ty: FieldType,
+    /// The byte order of a `Scalar` field; ignored for other types.
+    pub endianness: ByteOrder,
+    /// The number of consecutive elements for an array field, or `None` for a single value.
+    pub count: Option<usize>,
+}
+
+impl FieldSpec {
+    /// Describes a single value.
+    pub fn new<S: Into<String>>(name: S, ty: FieldType, endianness: ByteOrder) -> FieldSpec {
+        FieldSpec {
+            name: name.into(),
+            ty,
+            endianness,
+            count: None,
+        }
+    }
+
+    /// Describes an array of `count` values.
+    pub fn array<S: Into<String>>(name: S, ty: FieldType, endianness: ByteOrder, count: usize) -> FieldSpec {
+        FieldSpec {
+            count: Some(count),
+            ..FieldSpec::new(name, ty, endianness)
+        }
+    }
+}
+
+/// A value parsed according to a `Schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A number.
+    Scalar(Scalar),
+    /// Bytes kept as they are.
+    Bytes(Vec<u8>),
+    /// The elements of an array field.
+    Array(Vec<Value>),
+    /// The fields of a record, in order, by name.
+    Record(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the field of a record with the given name, or `None` for other values.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match *self {
+            Value::Record(ref fields) => fields.iter().find(|f| f.0 == name).map(|f| &f.1),
+            _ => None,
+        }
+    }
+
+    /// Returns the number held by a `Scalar` value.
+    pub fn as_scalar(&self) -> Option<Scalar> {
+        match *self {
+            Value::Scalar(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+/// A record layout: fields that follow each other without padding.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let schema = Schema::new(vec![
+///     FieldSpec::new("magic", FieldType::Bytes(2), ByteOrder::BigEndian),
+///     FieldSpec::new("count", FieldType::Scalar(ScalarType::U16), ByteOrder::LittleEndian),
+///     FieldSpec::array("ids", FieldType::Scalar(ScalarType::U8), ByteOrder::BigEndian, 2),
+/// ]);
+/// assert_eq!(Some(6), schema.size());
+///
+/// let value = schema.parse(&[b'M', b'Z', 2, 0, 7, 9]).unwrap();
+/// assert_eq!(Some(&Value::Bytes(b"MZ".to_vec())), value.get("magic"));
+/// assert_eq!(Some(Scalar::U16(2)), value.get("count").and_then(Value::as_scalar));
+/// assert_eq!(Some(&Value::Array(vec![Value::Scalar(Scalar::U8(7)), Value::Scalar(Scalar::U8(9))])),
+///            value.get("ids"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Schema {
+    /// The fields in the order they are stored.
+    pub fields: Vec<FieldSpec>,
+}
+
+impl Schema {
+    /// Creates a schema with the given fields.
+    pub fn new(fields: Vec<FieldSpec>) -> Schema {
+        Schema { fields }
+    }
+
+    /// Returns the number of bytes a record takes, or `None` if it overflows `usize`.
+    pub fn size(&self) -> Option<usize> {
+        self.fields.iter().try_fold(0usize, |total, field| {
+            total.checked_add(element_size(&field.ty)?.checked_mul(field.count.unwrap_or(1))?)
+        })
+    }
+
+    /// Parses a record from the front of `data` into a `Value::Record`.
+    ///
+    /// Errors report the offset and type of the field that could not be read. An array
+    /// field fails as a whole before any element is read if the rest of the stream cannot
+    /// hold it (`ShortSlice`) or its elements take no bytes (`InvalidWidth`), so that a
+    /// corrupt count cannot make the parser allocate or loop without bound.
+    pub fn parse(&self, data: &[u8]) -> CursorResult<Value> {
+        self.parse_from(&mut ByteCursor::new(data, ByteOrder::BigEndian))
+    }
+
+    /// Parses a record at the position of `cursor` and advances past it.
+    ///
+    /// The byte order of the cursor is restored afterwards.
+    pub fn parse_from(&self, cursor: &mut ByteCursor) -> CursorResult<Value> {
+        let endianness = cursor.endianness();
+        let result = self.parse_fields(cursor);
+        cursor.set_endianness(endianness);
+        result
+    }
+
+    /// Writes `value`, which must be a `Value::Record` with the fields of the schema in
+    /// order, to the front of `data` and returns the number of bytes written.
+    ///
+    /// Returns `TypeMismatch` if a field has another name or type than the schema says,
+    /// and `LengthMismatch` if a record, array or byte string has another length. On
+    /// error, `data` may be partially written.
+    ///
+    /// ```rust
+    /// use endianness::*;
+    ///
+    /// let schema = Schema::new(vec![
+    ///     FieldSpec::new("id", FieldType::Scalar(ScalarType::U16), ByteOrder::BigEndian),
+    ///     FieldSpec::new("tag", FieldType::Bytes(2), ByteOrder::BigEndian),
+    /// ]);
+    /// let value = schema.parse(&[0, 7, b'o', b'k']).unwrap();
+    /// assert_eq!(vec![0, 7, b'o', b'k'], schema.to_bytes(&value).unwrap());
+    ///
+    /// let wrong = Value::Record(vec![("id".to_string(), Value::Scalar(Scalar::U32(7))),
+    ///                                ("tag".to_string(), Value::Bytes(b"ok".to_vec()))]);
+    /// assert_eq!(EndiannessError::TypeMismatch, schema.to_bytes(&wrong).unwrap_err());
+    /// ```
+    pub fn write(&self, value: &Value, data: &mut [u8]) -> EndiannessResult<usize> {
+        let mut writer = ByteWriter::new(data, ByteOrder::BigEndian);
+        self.write_fields(value, &mut writer)?;
+        Ok(writer.position())
+    }
+
+    /// Writes `value` into a new vector of `size()` bytes, like `write`.
+    ///
+    /// Returns `Overflow` if the size of the schema overflows `usize`.
+    pub fn to_bytes(&self, value: &Value) -> EndiannessResult<Vec<u8>> {
+        let mut data = vec![0; self.size().ok_or(EndiannessError::Overflow)?];
+        self.write(value, &mut data)?;
+        Ok(data)
+    }
+
+    fn write_fields(&self, value: &Value, writer: &mut ByteWriter) -> EndiannessResult<()> {
+        let fields = match *value {
+            Value::Record(ref fields) => fields,
+            _ => return Err(EndiannessError::TypeMismatch),
+        };
+        if fields.len() != self.fields.len() {
+            return Err(EndiannessError::LengthMismatch { expected: self.fields.len(), found: fields.len() });
+        }
+        for (spec, (name, value)) in self.fields.iter().zip(fields) {
+            if *name != spec.name {
+                return Err(EndiannessError::TypeMismatch);
+            }
+            match (spec.count, value) {
+                (None, value) => write_value(spec, value, writer)?,
+                (Some(count), Value::Array(elements)) => {
+                    if elements.len() != count {
+                        return Err(EndiannessError::LengthMismatch { expected: count, found: elements.len() });
+                    }
+                    for element in elements {
+                        write_value(spec, element, writer)?;
+                    }
+                }
+                (Some(_), _) => return Err(EndiannessError::TypeMismatch),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_fields(&self, cursor: &mut ByteCursor) -> CursorResult<Value> {
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            let value = match field.count {
+                None => parse_value(field, cursor)?,
+                Some(count) => {
+                    let len = match element_size(&field.ty) {
+                        _ if count == 0 => 0,
+                        Some(0) => return Err(cursor.error("array", EndiannessError::InvalidWidth)),
+                        size => size.and_then(|size| size.checked_mul(count)).unwrap_or(usize::MAX),
+                    };
+                    if len > cursor.remaining() {
+                        let err = EndiannessError::ShortSlice { expected: len, found: cursor.remaining() };
+                        return Err(cursor.error("array", err));
+                    }
+                    let mut elements = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        elements.push(parse_value(field, cursor)?);
+                    }
+                    Value::Array(elements)
+                }
+            };
+            fields.push((field.name.clone(), value));
+        }
+        Ok(Value::Record(fields))
+    }
+}
+
+// The number of bytes one value of `ty` takes, or `None` if it overflows `usize`.
+fn element_size(ty: &FieldType) -> Option<usize> {
+    match *ty {
+        FieldType::Scalar(ty) => Some(ty.size()),
+        FieldType::Bytes(n) => Some(n),
+        FieldType::Record(ref schema) => schema.size(),
+    }
+}
+
+fn parse_value(field: &FieldSpec, cursor: &mut ByteCursor) -> CursorResult<Value> {
+    match field.ty {
+        FieldType::Scalar(ty) => {
+            cursor.set_endianness(field.endianness);
+            Ok(Value::Scalar(cursor.read_scalar(ty)?))
+        }
+        FieldType::Bytes(n) => Ok(Value::Bytes(cursor.read_bytes(n)?.to_vec())),
+        FieldType::Record(ref schema) => schema.parse_fields(cursor),
+    }
+}
+
+fn write_value(field: &FieldSpec, value: &Value, writer: &mut ByteWriter) -> EndiannessResult<()> {
+    match (&field.ty, value) {
+        (&FieldType::Scalar(ty), &Value::Scalar(n)) if n.scalar_type() == ty => {
+            writer.set_endianness(field.endianness);
+            writer.write(n)
+        }
+        (&FieldType::Bytes(len), Value::Bytes(bytes)) => {
+            if bytes.len() != len {
+                return Err(EndiannessError::LengthMismatch { expected: len, found: bytes.len() });
+            }
+            writer.write_bytes(bytes)
+        }
+        (FieldType::Record(schema), value) => schema.write_fields(value, writer),
+        _ => Err(EndiannessError::TypeMismatch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteCursor, ByteOrder, EndiannessError, FieldSpec, FieldType, Scalar, ScalarType, Schema, Value};
+
+    fn point() -> Schema {
+        Schema::new(vec![
+            FieldSpec::new("x", FieldType::Scalar(ScalarType::I16), ByteOrder::BigEndian),
+            FieldSpec::new("y", FieldType::Scalar(ScalarType::I16), ByteOrder::LittleEndian),
+        ])
+    }
+
+    #[test]
+    fn nested() {
+        let schema = Schema::new(vec![
+            FieldSpec::new("n", FieldType::Scalar(ScalarType::U8), ByteOrder::BigEndian),
+            FieldSpec::array("points", FieldType::Record(point()), ByteOrder::BigEndian, 2),
+        ]);
+        assert_eq!(Some(9), schema.size());
+        let value = schema.parse(&[2, 0, 1, 2, 0, 255, 255, 3, 0, 42]).unwrap();
+        let points = match value.get("points") {
+            Some(Value::Array(points)) => points,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(Some(Scalar::I16(1)), points[0].get("x").and_then(Value::as_scalar));
+        assert_eq!(Some(Scalar::I16(2)), points[0].get("y").and_then(Value::as_scalar));
+        assert_eq!(Some(Scalar::I16(-1)), points[1].get("x").and_then(Value::as_scalar));
+        assert_eq!(Some(Scalar::I16(3)), points[1].get("y").and_then(Value::as_scalar));
+        assert_eq!(None, value.get("z"));
+    }
+
+    #[test]
+    fn errors_name_the_field_type() {
+        let err = point().parse(&[0, 1, 2]).unwrap_err();
+        assert_eq!((2, "i16"), (err.offset(), err.type_name()));
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 }, err.error());
+    }
+
+    #[test]
+    fn array_counts_are_bounded() {
+        let data = [0u8; 8];
+        let huge = Schema::new(vec![
+            FieldSpec::new("tag", FieldType::Bytes(1), ByteOrder::BigEndian),
+            FieldSpec::array("ids", FieldType::Scalar(ScalarType::U16), ByteOrder::BigEndian, usize::MAX),
+        ]);
+        let err = huge.parse(&data).unwrap_err();
+        assert_eq!((1, "array"), (err.offset(), err.type_name()));
+        assert_eq!(EndiannessError::ShortSlice { expected: usize::MAX, found: 7 }, err.error());
+
+        let empty = Schema::new(vec![FieldSpec::array("pad", FieldType::Bytes(0), ByteOrder::BigEndian, usize::MAX)]);
+        assert_eq!(EndiannessError::InvalidWidth, empty.parse(&data).unwrap_err().error());
+        let record = FieldType::Record(Schema::default());
+        let empty = Schema::new(vec![FieldSpec::array("none", record, ByteOrder::BigEndian, usize::MAX)]);
+        assert_eq!(EndiannessError::InvalidWidth, empty.parse(&data).unwrap_err().error());
+
+        let none = Schema::new(vec![FieldSpec::array("pad", FieldType::Bytes(0), ByteOrder::BigEndian, 0)]);
+        assert_eq!(Some(&Value::Array(vec![])), none.parse(&[]).unwrap().get("pad"));
+    }
+
+    #[test]
+    fn cursor_position_and_order() {
+        let v = [9, 0, 1, 2, 0, 7];
+        let mut cursor = ByteCursor::new(&v, ByteOrder::LittleEndian);
+        cursor.skip(1).unwrap();
+        point().parse_from(&mut cursor).unwrap();
+        assert_eq!((5, ByteOrder::LittleEndian), (cursor.position(), cursor.endianness()));
+        let huge = FieldSpec::array("all", FieldType::Bytes(usize::MAX), ByteOrder::BigEndian, 2);
+        assert_eq!(None, Schema::new(vec![huge]).size());
+    }
+
+    #[test]
+    fn write_round_trip() {
+        let schema = Schema::new(vec![
+            FieldSpec::new("tag", FieldType::Bytes(1), ByteOrder::BigEndian),
+            FieldSpec::array("points", FieldType::Record(point()), ByteOrder::BigEndian, 2),
+            FieldSpec::new("crc", FieldType::Scalar(ScalarType::U32), ByteOrder::LittleEndian),
+        ]);
+        let data = [b'p', 0, 1, 2, 0, 255, 255, 3, 0, 1, 2, 3, 4];
+        let value = schema.parse(&data).unwrap();
+        assert_eq!(data.to_vec(), schema.to_bytes(&value).unwrap());
+        let mut buf = [0u8; 16];
+        assert_eq!(13, schema.write(&value, &mut buf).unwrap());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   schema.write(&value, &mut buf[..12]).unwrap_err());
+    }
+
+    #[test]
+    fn write_validates() {
+        let schema = point();
+        let field = |name: &str, value| (name.to_string(), value);
+        let ok = Value::Record(vec![field("x", Value::Scalar(Scalar::I16(1))), field("y", Value::Scalar(Scalar::I16(2)))]);
+        assert_eq!(vec![0, 1, 2, 0], schema.to_bytes(&ok).unwrap());
+
+        let renamed = Value::Record(vec![field("x", Value::Scalar(Scalar::I16(1))), field("z", Value::Scalar(Scalar::I16(2)))]);
+        assert_eq!(EndiannessError::TypeMismatch, schema.to_bytes(&renamed).unwrap_err());
+        let short = Value::Record(vec![field("x", Value::Scalar(Scalar::I16(1)))]);
+        assert_eq!(EndiannessError::LengthMismatch { expected: 2, found: 1 }, schema.to_bytes(&short).unwrap_err());
+        assert_eq!(EndiannessError::TypeMismatch, schema.to_bytes(&Value::Bytes(vec![])).unwrap_err());
+
+        let bytes = Schema::new(vec![FieldSpec::array("b", FieldType::Bytes(2), ByteOrder::BigEndian, 1)]);
+        let one = |v: Vec<u8>| Value::Record(vec![field("b", Value::Array(vec![Value::Bytes(v)]))]);
+        assert_eq!(vec![1, 2], bytes.to_bytes(&one(vec![1, 2])).unwrap());
+        assert_eq!(EndiannessError::LengthMismatch { expected: 2, found: 3 },
+                   bytes.to_bytes(&one(vec![1, 2, 3])).unwrap_err());
+        let not_array = Value::Record(vec![field("b", Value::Bytes(vec![1, 2]))]);
+        assert_eq!(EndiannessError::TypeMismatch, bytes.to_bytes(&not_array).unwrap_err());
+    }
+}
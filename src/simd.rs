@@ -0,0 +1,266 @@
+//! SIMD kernels for reversing the byte order of many numbers at once.
+//!
+//! The kernels only process whole 16-byte blocks and report how many elements they
+//! converted; the callers finish the tail with scalar code. Without the `simd` feature,
+//! or on CPUs without a kernel, nothing is processed here.
+//!
+//! With `std`, the best kernel (AVX2, SSSE3 or NEON) is picked at runtime. Without it,
+//! only the target features enabled at compile time are used.
+//!
+//! This module and `view` are the only places where the crate uses `unsafe`.
+
+#![allow(unsafe_code)]
+
+use core::cmp;
+
+/// Numbers for which every bit pattern is a valid value.
+pub trait Number: Copy {}
+
+impl Number for u16 {}
+impl Number for i16 {}
+impl Number for u32 {}
+impl Number for i32 {}
+impl Number for u64 {}
+impl Number for i64 {}
+impl Number for u128 {}
+impl Number for i128 {}
+impl Number for f32 {}
+impl Number for f64 {}
+
+/// Converts a prefix of `src` into `dst` with the byte order of every element reversed.
+/// Returns the number of elements written.
+pub fn swap_into<T: Number>(src: &[u8], dst: &mut [T]) -> usize {
+    let width = size_of::<T>();
+    let len = cmp::min(src.len(), size_of_val(dst));
+    // Safety: the kernels read and write at most `len` bytes, which fit both slices,
+    // and any bit pattern is a valid `T`.
+    let done = unsafe { imp::swap(src.as_ptr(), dst.as_mut_ptr() as *mut u8, len, width) };
+    done / width
+}
+
+/// Reverses the byte order of a prefix of `data` in place.
+/// Returns the number of elements converted.
+pub fn swap_in_place<T: Number>(data: &mut [T]) -> usize {
+    let width = size_of::<T>();
+    let len = size_of_val(data);
+    let ptr = data.as_mut_ptr() as *mut u8;
+    // Safety: as above; every block is loaded before it is stored back.
+    let done = unsafe { imp::swap(ptr, ptr, len, width) };
+    done / width
+}
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+mod imp {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    // Shuffle masks reversing the bytes of every element within a 16-byte block.
+    const MASK_16: [u8; 16] = [1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14];
+    const MASK_32: [u8; 16] = [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12];
+    const MASK_64: [u8; 16] = [7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8];
+    const MASK_128: [u8; 16] = [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0];
+
+    fn mask(width: usize) -> Option<&'static [u8; 16]> {
+        match width {
+            2 => Some(&MASK_16),
+            4 => Some(&MASK_32),
+            8 => Some(&MASK_64),
+            16 => Some(&MASK_128),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn has_avx2() -> bool {
+        is_x86_feature_detected!("avx2")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn has_avx2() -> bool {
+        cfg!(target_feature = "avx2")
+    }
+
+    #[cfg(feature = "std")]
+    fn has_ssse3() -> bool {
+        is_x86_feature_detected!("ssse3")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn has_ssse3() -> bool {
+        cfg!(target_feature = "ssse3")
+    }
+
+    pub unsafe fn swap(src: *const u8, dst: *mut u8, len: usize, width: usize) -> usize {
+        match mask(width) {
+            Some(mask) if has_avx2() => swap_avx2(src, dst, len, mask),
+            Some(mask) if has_ssse3() => swap_ssse3(src, dst, len, mask),
+            _ => 0,
+        }
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn swap_ssse3(src: *const u8, dst: *mut u8, len: usize, mask: &[u8; 16]) -> usize {
+        let mask = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+        let mut i = 0;
+        while i + 16 <= len {
+            let block = _mm_loadu_si128(src.add(i) as *const __m128i);
+            _mm_storeu_si128(dst.add(i) as *mut __m128i, _mm_shuffle_epi8(block, mask));
+            i += 16;
+        }
+        i
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn swap_avx2(src: *const u8, dst: *mut u8, len: usize, mask: &[u8; 16]) -> usize {
+        let narrow = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+        let wide = _mm256_broadcastsi128_si256(narrow);
+        let mut i = 0;
+        while i + 32 <= len {
+            let block = _mm256_loadu_si256(src.add(i) as *const __m256i);
+            _mm256_storeu_si256(dst.add(i) as *mut __m256i, _mm256_shuffle_epi8(block, wide));
+            i += 32;
+        }
+        if i + 16 <= len {
+            let block = _mm_loadu_si128(src.add(i) as *const __m128i);
+            _mm_storeu_si128(dst.add(i) as *mut __m128i, _mm_shuffle_epi8(block, narrow));
+            i += 16;
+        }
+        i
+    }
+
+    #[cfg(all(test, feature = "std"))]
+    mod tests {
+        use super::{mask, swap_avx2, swap_ssse3};
+
+        // Both kernels are checked, whichever one the dispatcher would pick.
+        #[test]
+        fn kernels_match_scalar() {
+            for &width in &[2usize, 4, 8, 16] {
+                for len in 0..100 {
+                    let src: Vec<u8> = (0..len).map(|i| (i * 13) as u8).collect();
+                    let done = len / 16 * 16;
+                    let mut expected = src.clone();
+                    for chunk in expected[..done].chunks_mut(width) {
+                        chunk.reverse();
+                    }
+                    let mask = mask(width).unwrap();
+                    if is_x86_feature_detected!("ssse3") {
+                        let mut dst = src.clone();
+                        assert_eq!(done, unsafe { swap_ssse3(src.as_ptr(), dst.as_mut_ptr(), len, mask) });
+                        assert_eq!(expected, dst);
+                    }
+                    if is_x86_feature_detected!("avx2") {
+                        let mut dst = src.clone();
+                        assert_eq!(done, unsafe { swap_avx2(src.as_ptr(), dst.as_mut_ptr(), len, mask) });
+                        assert_eq!(expected, dst);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod imp {
+    use core::arch::aarch64::*;
+
+    #[cfg(feature = "std")]
+    fn has_neon() -> bool {
+        is_aarch64_feature_detected!("neon")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn has_neon() -> bool {
+        cfg!(target_feature = "neon")
+    }
+
+    pub unsafe fn swap(src: *const u8, dst: *mut u8, len: usize, width: usize) -> usize {
+        if has_neon() {
+            swap_neon(src, dst, len, width)
+        } else {
+            0
+        }
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn swap_neon(src: *const u8, dst: *mut u8, len: usize, width: usize) -> usize {
+        let mut i = 0;
+        while i + 16 <= len {
+            let block = vld1q_u8(src.add(i));
+            let swapped = match width {
+                2 => vrev16q_u8(block),
+                4 => vrev32q_u8(block),
+                8 => vrev64q_u8(block),
+                16 => {
+                    let reversed = vrev64q_u8(block);
+                    vextq_u8(reversed, reversed, 8)
+                }
+                _ => return 0,
+            };
+            vst1q_u8(dst.add(i), swapped);
+            i += 16;
+        }
+        i
+    }
+}
+
+#[cfg(not(all(feature = "simd",
+              any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))))]
+mod imp {
+    pub unsafe fn swap(_src: *const u8, _dst: *mut u8, _len: usize, _width: usize) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryInto;
+
+    use super::{swap_into, swap_in_place};
+
+    // Whatever prefix the kernels convert must match the scalar conversion.
+    macro_rules! kernel_correctness {
+        ($name:ident, $ty:ident) => (
+            #[test]
+            fn $name() {
+                let width = size_of::<$ty>();
+                for len in 0..40 {
+                    let src: Vec<u8> = (0..len * width).map(|i| (i * 7) as u8).collect();
+                    let native: Vec<$ty> = src.chunks(width)
+                        .map(|c| $ty::from_ne_bytes(c.try_into().unwrap()))
+                        .collect();
+                    let mut dst: Vec<$ty> = vec![Default::default(); len];
+                    let mut in_place = native.clone();
+                    let done = swap_into(&src, &mut dst);
+                    assert_eq!(done, swap_in_place(&mut in_place));
+                    for i in 0..done {
+                        assert_eq!(native[i].swap_bytes(), dst[i]);
+                        assert_eq!(native[i].swap_bytes(), in_place[i]);
+                    }
+                }
+            }
+        );
+    }
+
+    kernel_correctness!(kernel_u16, u16);
+    kernel_correctness!(kernel_u32, u32);
+    kernel_correctness!(kernel_u64, u64);
+    kernel_correctness!(kernel_u128, u128);
+
+    // The kernels use unaligned loads and stores; this runs them on buffers that start
+    // at every offset within a block, which is mostly of interest under Miri.
+    #[test]
+    fn unaligned_buffers() {
+        let src: Vec<u8> = (0..80).collect();
+        for offset in 0..16 {
+            let mut dst = [0u32; 16];
+            let done = swap_into(&src[offset..offset + 64], &mut dst);
+            for (i, n) in dst[..done].iter().enumerate() {
+                let bytes = &src[offset + 4 * i..offset + 4 * i + 4];
+                assert_eq!(u32::from_ne_bytes(bytes.try_into().unwrap()).swap_bytes(), *n);
+            }
+        }
+    }
+}
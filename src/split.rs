@@ -0,0 +1,90 @@
+//! Reads of values that are split across several buffers, such as the two halves of a
+//! ring buffer or the segments of scatter I/O.
+
+use core::cmp;
+
+use {ByteOrder, EndianRead, EndiannessError, EndiannessResult};
+
+// Largest value that can be stitched together from several buffers.
+const MAX_SIZE: usize = 16;
+
+/// Reads a value from the front of a stream made of `first` followed by `second`.
+///
+/// Values of up to 16 bytes can straddle the boundary; larger ones return `InvalidWidth`
+/// unless they lie entirely within `first`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let (head, tail) = ([0xde, 0xad], [0xbe, 0xef, 0]);
+/// assert_eq!(0xdeadbeef, read_split::<u32>(&head, &tail, ByteOrder::BigEndian).unwrap());
+/// ```
+pub fn read_split<T: EndianRead>(first: &[u8], second: &[u8], endianness: ByteOrder) -> EndiannessResult<T> {
+    read_chunks([first, second], endianness)
+}
+
+/// Reads a value from the front of a stream made of the given buffers in order.
+///
+/// Values of up to 16 bytes can span any number of buffers; larger ones return
+/// `InvalidWidth` unless they lie entirely within the first buffer.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let chunks: [&[u8]; 4] = [&[1], &[], &[2, 3], &[4, 5]];
+/// assert_eq!(0x04030201, read_chunks::<u32, _>(chunks.iter().cloned(), ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read_chunks<'a, T: EndianRead, I: IntoIterator<Item = &'a [u8]>>(chunks: I, endianness: ByteOrder)
+                                                                          -> EndiannessResult<T> {
+    let mut chunks = chunks.into_iter();
+    let first = chunks.next().unwrap_or(&[]);
+    if first.len() >= T::SIZE {
+        return T::read(first, endianness);
+    }
+    if T::SIZE > MAX_SIZE {
+        return Err(EndiannessError::InvalidWidth);
+    }
+    let mut buf = [0u8; MAX_SIZE];
+    buf[..first.len()].copy_from_slice(first);
+    let mut found = first.len();
+    for chunk in chunks {
+        let n = cmp::min(T::SIZE - found, chunk.len());
+        buf[found..found + n].copy_from_slice(&chunk[..n]);
+        found += n;
+        if found == T::SIZE {
+            return T::read(&buf, endianness);
+        }
+    }
+    Err(EndiannessError::ShortSlice { expected: T::SIZE, found })
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError, read_chunks, read_split};
+
+    #[test]
+    fn every_split_point() {
+        let v = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        for i in 0..v.len() + 1 {
+            let (first, second) = v.split_at(i);
+            assert_eq!(0x0102030405060708, read_split::<u64>(first, second, ByteOrder::BigEndian).unwrap());
+        }
+        assert_eq!(-127.5, read_split::<f32>(&[194, 255], &[0, 0], ByteOrder::BigEndian).unwrap());
+    }
+
+    #[test]
+    fn many_chunks() {
+        let chunks: Vec<&[u8]> = vec![&[], &[1], &[2], &[], &[3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17]];
+        assert_eq!(0x100f0e0d0c0b0a090807060504030201,
+                   read_chunks::<u128, _>(chunks, ByteOrder::LittleEndian).unwrap());
+        let empty: [&[u8]; 0] = [];
+        assert_eq!(EndiannessError::ShortSlice { expected: 1, found: 0 },
+                   read_chunks::<u8, _>(empty.iter().cloned(), ByteOrder::LittleEndian).unwrap_err());
+    }
+
+    #[test]
+    fn short() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_split::<u32>(&[1], &[2, 3], ByteOrder::BigEndian).unwrap_err());
+    }
+}
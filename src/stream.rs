@@ -0,0 +1,168 @@
+//! A push-style decoder for values that arrive in arbitrary chunks.
+
+use core::cmp;
+
+use {ByteOrder, EndianRead, EndiannessError, EndiannessResult};
+
+// Largest value the decoder can buffer.
+const MAX_SIZE: usize = 16;
+
+/// The outcome of feeding a chunk to a `StreamDecoder`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Decoded<T> {
+    /// The value is complete.
+    Complete(T),
+    /// The chunk ended before the value; this many more bytes are needed.
+    Incomplete(usize),
+}
+
+/// Decodes values from a stream that is delivered in chunks of any size, such as the
+/// reads of a TCP socket. Bytes of a value split across chunks are buffered inside the
+/// decoder, so callers never have to join chunks themselves.
+///
+/// Each call to `feed` takes the bytes it needs from the front of the chunk and advances
+/// it, so one chunk can be fed repeatedly until it is empty. After `Incomplete`, the next
+/// call must ask for the same type. Values of up to 16 bytes are supported.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut decoder = StreamDecoder::new(ByteOrder::BigEndian);
+/// let mut chunk: &[u8] = &[0, 0, 1];
+/// assert_eq!(Decoded::Complete(0), decoder.feed::<u16>(&mut chunk).unwrap());
+/// assert_eq!(Decoded::Incomplete(3), decoder.feed::<u32>(&mut chunk).unwrap());
+/// let mut chunk: &[u8] = &[2, 3, 4];
+/// assert_eq!(Decoded::Complete(0x01020304), decoder.feed::<u32>(&mut chunk).unwrap());
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct StreamDecoder {
+    buf: [u8; MAX_SIZE],
+    buffered: usize,
+    endianness: ByteOrder,
+}
+
+impl StreamDecoder {
+    /// Creates a decoder with nothing buffered.
+    pub fn new(endianness: ByteOrder) -> StreamDecoder {
+        StreamDecoder {
+            buf: [0; MAX_SIZE],
+            buffered: 0,
+            endianness,
+        }
+    }
+
+    /// Returns the byte order of the values.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Sets the byte order of the following values.
+    pub fn set_endianness(&mut self, endianness: ByteOrder) {
+        self.endianness = endianness;
+    }
+
+    /// Returns the number of bytes of an incomplete value held by the decoder.
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Drops the bytes of an incomplete value.
+    pub fn reset(&mut self) {
+        self.buffered = 0;
+    }
+
+    /// Takes the bytes of the next value from the front of `chunk` and advances it.
+    ///
+    /// Returns `InvalidWidth` for types larger than 16 bytes, and for types smaller than
+    /// the bytes already buffered for another type.
+    pub fn feed<T: EndianRead>(&mut self, chunk: &mut &[u8]) -> EndiannessResult<Decoded<T>> {
+        if T::SIZE > MAX_SIZE || self.buffered > T::SIZE {
+            return Err(EndiannessError::InvalidWidth);
+        }
+        // Values that do not span chunks are read in place.
+        if self.buffered == 0 && chunk.len() >= T::SIZE {
+            let value = T::read(chunk, self.endianness)?;
+            *chunk = &chunk[T::SIZE..];
+            return Ok(Decoded::Complete(value));
+        }
+        let n = cmp::min(T::SIZE - self.buffered, chunk.len());
+        self.buf[self.buffered..self.buffered + n].copy_from_slice(&chunk[..n]);
+        self.buffered += n;
+        *chunk = &chunk[n..];
+        if self.buffered < T::SIZE {
+            return Ok(Decoded::Incomplete(T::SIZE - self.buffered));
+        }
+        self.buffered = 0;
+        Ok(Decoded::Complete(T::read(&self.buf, self.endianness)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndianRead, EndiannessError, EndiannessResult};
+    use super::{Decoded, StreamDecoder};
+
+    struct Large;
+
+    impl EndianRead for Large {
+        const SIZE: usize = 17;
+
+        fn read(_data: &[u8], _endianness: ByteOrder) -> EndiannessResult<Large> {
+            Ok(Large)
+        }
+    }
+
+    #[test]
+    fn any_chunking() {
+        let stream = [0, 0, 0, 1, 194, 255, 0, 0, 255, 2, 0];
+        for size in 1..stream.len() + 1 {
+            let mut decoder = StreamDecoder::new(ByteOrder::BigEndian);
+            let mut values = (None, None, None, None);
+            for mut chunk in stream.chunks(size) {
+                while !chunk.is_empty() {
+                    if values.0.is_none() {
+                        if let Decoded::Complete(v) = decoder.feed::<u32>(&mut chunk).unwrap() {
+                            values.0 = Some(v);
+                        }
+                    } else if values.1.is_none() {
+                        if let Decoded::Complete(v) = decoder.feed::<f32>(&mut chunk).unwrap() {
+                            values.1 = Some(v);
+                        }
+                    } else if values.2.is_none() {
+                        if let Decoded::Complete(v) = decoder.feed::<i8>(&mut chunk).unwrap() {
+                            values.2 = Some(v);
+                        }
+                    } else if let Decoded::Complete(v) = decoder.feed::<u16>(&mut chunk).unwrap() {
+                        values.3 = Some(v);
+                    }
+                }
+            }
+            assert_eq!((Some(1), Some(-127.5), Some(-1), Some(0x0200)), values);
+            assert_eq!(0, decoder.buffered());
+        }
+    }
+
+    #[test]
+    fn incomplete() {
+        let mut decoder = StreamDecoder::new(ByteOrder::LittleEndian);
+        let mut chunk: &[u8] = &[1, 2, 3];
+        assert_eq!(Decoded::Incomplete(5), decoder.feed::<u64>(&mut chunk).unwrap());
+        assert!(chunk.is_empty());
+        assert_eq!(3, decoder.buffered());
+        let mut chunk: &[u8] = &[];
+        assert_eq!(Decoded::Incomplete(5), decoder.feed::<u64>(&mut chunk).unwrap());
+        assert_eq!(EndiannessError::InvalidWidth, decoder.feed::<u16>(&mut chunk).unwrap_err());
+        decoder.reset();
+        let mut chunk: &[u8] = &[1, 2, 3];
+        assert_eq!(Decoded::Complete(0x0201), decoder.feed::<u16>(&mut chunk).unwrap());
+        assert_eq!(&[3], chunk);
+    }
+
+    #[test]
+    fn too_large() {
+        let mut decoder = StreamDecoder::new(ByteOrder::LittleEndian);
+        let mut chunk: &[u8] = &[0; 32];
+        assert!(decoder.feed::<Large>(&mut chunk).is_err());
+        assert_eq!(32, chunk.len());
+    }
+}
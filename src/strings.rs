@@ -0,0 +1,243 @@
+//! Strings terminated by a NUL byte or stored after their length, and UTF-16 and UTF-32
+//! text.
+//!
+//! The UTF-8 readers borrow the string from the stream and return it together with the
+//! number of bytes it occupied, terminator or prefix included. UTF-16 and UTF-32 text is
+//! decoded into a new `String` of a length given by the caller.
+
+#[cfg(feature = "std")]
+use core::char;
+use core::str;
+
+#[cfg(feature = "std")]
+use read_u32;
+use {ByteOrder, EndiannessError, EndiannessResult, read_len_prefixed_bytes};
+
+fn to_str(bytes: &[u8]) -> EndiannessResult<&str> {
+    str::from_utf8(bytes).map_err(|err| EndiannessError::InvalidUtf8 { valid_up_to: err.valid_up_to() })
+}
+
+/// Reads a NUL-terminated UTF-8 string, scanning at most `max_len` bytes for the NUL.
+///
+/// Returns the string without the NUL and the number of bytes read, NUL included. Returns
+/// `Overflow` if there is no NUL within `max_len` bytes, `ShortSlice` if the stream ends
+/// before that, and `InvalidUtf8` if the string is not valid UTF-8.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(("ELF", 4), read_cstr(b"ELF\0\x02", 16).unwrap());
+/// assert_eq!(EndiannessError::Overflow, read_cstr(b"ELF\0", 3).unwrap_err());
+/// ```
+pub fn read_cstr(data: &[u8], max_len: usize) -> EndiannessResult<(&str, usize)> {
+    let scan = &data[..max_len.min(data.len())];
+    match scan.iter().position(|&b| b == 0) {
+        Some(len) => Ok((to_str(&data[..len])?, len + 1)),
+        None if scan.len() == max_len => Err(EndiannessError::Overflow),
+        None => Err(EndiannessError::ShortSlice { expected: data.len() + 1, found: data.len() }),
+    }
+}
+
+/// Reads a UTF-8 string stored after its length in bytes, a `width`-byte unsigned
+/// integer. Classic Pascal strings have a one-byte length.
+///
+/// Returns the string and the number of bytes read, prefix included. Fails like
+/// `read_len_prefixed_bytes`, and with `InvalidUtf8` if the string is not valid UTF-8.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(("hi", 3), read_pascal_str(b"\x02hi!", 1, 255, ByteOrder::BigEndian).unwrap());
+/// ```
+pub fn read_pascal_str(data: &[u8], width: usize, max_len: usize, endianness: ByteOrder)
+                       -> EndiannessResult<(&str, usize)> {
+    let (bytes, read) = read_len_prefixed_bytes(data, width, max_len, endianness)?;
+    Ok((to_str(bytes)?, read))
+}
+
+// Checks that `data` holds `len` code units and returns an iterator over them.
+#[cfg(feature = "std")]
+fn utf16_units(data: &[u8], len: usize, endianness: ByteOrder)
+               -> EndiannessResult<impl Iterator<Item = u16> + '_> {
+    let size = len.checked_mul(2).ok_or(EndiannessError::Overflow)?;
+    if data.len() < size {
+        return Err(EndiannessError::ShortSlice { expected: size, found: data.len() });
+    }
+    Ok(data[..size].chunks_exact(2).map(move |unit| match endianness {
+        ByteOrder::BigEndian => u16::from_be_bytes([unit[0], unit[1]]),
+        ByteOrder::LittleEndian => u16::from_le_bytes([unit[0], unit[1]]),
+    }))
+}
+
+/// Reads a UTF-16 string of `len` code units, that is `2 * len` bytes.
+///
+/// Surrogate pairs are combined into one character. Returns `ShortSlice` if the stream is
+/// too short and `InvalidUtf16` if the string has an unpaired surrogate. A byte order mark
+/// is kept as U+FEFF; use `read_utf16_string_with_bom` to honor it instead. Requires the
+/// `std` feature.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0x3d, 0xd8, 0x00, 0xde, 0x21, 0x00];
+/// assert_eq!("\u{1f600}!", read_utf16_string(&v, 3, ByteOrder::LittleEndian).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn read_utf16_string(data: &[u8], len: usize, endianness: ByteOrder) -> EndiannessResult<String> {
+    let mut string = String::with_capacity(len);
+    let mut valid_up_to = 0;
+    for c in char::decode_utf16(utf16_units(data, len, endianness)?) {
+        match c {
+            Ok(c) => {
+                string.push(c);
+                valid_up_to += 2 * c.len_utf16();
+            }
+            Err(_) => return Err(EndiannessError::InvalidUtf16 { valid_up_to }),
+        }
+    }
+    Ok(string)
+}
+
+/// Reads a UTF-16 string of `len` code units like `read_utf16_string`, but replaces
+/// unpaired surrogates with U+FFFD. File names on NTFS may contain them. Requires the
+/// `std` feature.
+#[cfg(feature = "std")]
+pub fn read_utf16_string_lossy(data: &[u8], len: usize, endianness: ByteOrder) -> EndiannessResult<String> {
+    let units = utf16_units(data, len, endianness)?;
+    Ok(char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect())
+}
+
+/// Reads a UTF-16 string of `len` code units, the byte order mark included if there is one.
+///
+/// A leading byte order mark selects the byte order and is not part of the result; without
+/// one, the string is read in `default` order. Fails like `read_utf16_string`. Requires the
+/// `std` feature.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0xfe, 0xff, 0x00, 0x49, 0x00, 0x44];
+/// assert_eq!("ID", read_utf16_string_with_bom(&v, 3, ByteOrder::LittleEndian).unwrap());
+/// assert_eq!("\u{4900}", read_utf16_string_with_bom(&v[2..], 1, ByteOrder::LittleEndian).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn read_utf16_string_with_bom(data: &[u8], len: usize, default: ByteOrder) -> EndiannessResult<String> {
+    let endianness = match (len, data) {
+        (0, _) => return Ok(String::new()),
+        (_, [0xfe, 0xff, ..]) => ByteOrder::BigEndian,
+        (_, [0xff, 0xfe, ..]) => ByteOrder::LittleEndian,
+        _ => return read_utf16_string(data, len, default),
+    };
+    match read_utf16_string(&data[2..], len - 1, endianness) {
+        Err(EndiannessError::ShortSlice { expected, found }) => {
+            Err(EndiannessError::ShortSlice { expected: expected + 2, found: found + 2 })
+        }
+        Err(EndiannessError::InvalidUtf16 { valid_up_to }) => {
+            Err(EndiannessError::InvalidUtf16 { valid_up_to: valid_up_to + 2 })
+        }
+        result => result,
+    }
+}
+
+/// Reads a UTF-32 string of `len` code units, that is `4 * len` bytes.
+///
+/// Returns `ShortSlice` if the stream is too short and `InvalidUtf32` if a code unit is a
+/// surrogate or above U+10FFFF. Requires the `std` feature.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = [0, 0, 0, 0x57, 0, 0x01, 0xf6, 0x00];
+/// assert_eq!("W\u{1f600}", read_utf32_string(&v, 2, ByteOrder::BigEndian).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn read_utf32_string(data: &[u8], len: usize, endianness: ByteOrder) -> EndiannessResult<String> {
+    let size = len.checked_mul(4).ok_or(EndiannessError::Overflow)?;
+    if data.len() < size {
+        return Err(EndiannessError::ShortSlice { expected: size, found: data.len() });
+    }
+    let mut string = String::with_capacity(len);
+    for (i, unit) in data[..size].chunks_exact(4).enumerate() {
+        match char::from_u32(read_u32(unit, endianness)?) {
+            Some(c) => string.push(c),
+            None => return Err(EndiannessError::InvalidUtf32 { valid_up_to: 4 * i }),
+        }
+    }
+    Ok(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError, read_cstr, read_pascal_str};
+    #[cfg(feature = "std")]
+    use {read_utf16_string, read_utf16_string_lossy, read_utf16_string_with_bom, read_utf32_string};
+
+    #[test]
+    fn cstr() {
+        assert_eq!(("", 1), read_cstr(b"\0abc", 1).unwrap());
+        assert_eq!(("abc", 4), read_cstr(b"abc\0def\0", 4).unwrap());
+        assert_eq!(("h\u{e9}", 4), read_cstr(b"h\xc3\xa9\0", 8).unwrap());
+        assert_eq!(EndiannessError::Overflow, read_cstr(b"abc\0", 3).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, read_cstr(b"abc", 0).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 }, read_cstr(b"abc", 8).unwrap_err());
+        assert_eq!(EndiannessError::InvalidUtf8 { valid_up_to: 1 }, read_cstr(b"a\xff\0", 8).unwrap_err());
+    }
+
+    #[test]
+    fn pascal() {
+        assert_eq!(("abc", 5), read_pascal_str(b"\x03\x00abcd", 2, 8, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(("", 1), read_pascal_str(b"\x00", 1, 8, ByteOrder::BigEndian).unwrap());
+        assert_eq!(EndiannessError::Overflow, read_pascal_str(b"\x09abc", 1, 8, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_pascal_str(b"\x03ab", 1, 8, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidUtf8 { valid_up_to: 0 },
+                   read_pascal_str(b"\x02\xc3\x28", 1, 8, ByteOrder::BigEndian).unwrap_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn utf16() {
+        let be = [0x00, 0x61, 0xd8, 0x3d, 0xde, 0x00, 0x00, 0x62];
+        assert_eq!("a\u{1f600}b", read_utf16_string(&be, 4, ByteOrder::BigEndian).unwrap());
+        assert_eq!("a\u{1f600}", read_utf16_string(&be, 3, ByteOrder::BigEndian).unwrap());
+        assert_eq!("", read_utf16_string(&[], 0, ByteOrder::BigEndian).unwrap());
+        assert_eq!(EndiannessError::ShortSlice { expected: 10, found: 8 },
+                   read_utf16_string(&be, 5, ByteOrder::BigEndian).unwrap_err());
+        // The pair is cut after the high surrogate.
+        assert_eq!(EndiannessError::InvalidUtf16 { valid_up_to: 2 },
+                   read_utf16_string(&be, 2, ByteOrder::BigEndian).unwrap_err());
+        let lone_low = [0x00, 0x61, 0xde, 0x00, 0x00, 0x62];
+        assert_eq!(EndiannessError::InvalidUtf16 { valid_up_to: 2 },
+                   read_utf16_string(&lone_low, 3, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!("a\u{fffd}b", read_utf16_string_lossy(&lone_low, 3, ByteOrder::BigEndian).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn utf16_bom() {
+        let le = [0xff, 0xfe, 0x61, 0x00, 0x00, 0xdc];
+        assert_eq!("a", read_utf16_string_with_bom(&le, 2, ByteOrder::BigEndian).unwrap());
+        assert_eq!("", read_utf16_string_with_bom(&le, 1, ByteOrder::BigEndian).unwrap());
+        assert_eq!("", read_utf16_string_with_bom(&le, 0, ByteOrder::BigEndian).unwrap());
+        assert_eq!("\u{feff}", read_utf16_string(&le, 1, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(EndiannessError::InvalidUtf16 { valid_up_to: 4 },
+                   read_utf16_string_with_bom(&le, 3, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 6 },
+                   read_utf16_string_with_bom(&le, 4, ByteOrder::BigEndian).unwrap_err());
+        assert_eq!("\u{6100}", read_utf16_string_with_bom(&le[2..], 1, ByteOrder::BigEndian).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn utf32() {
+        let le = [0x61, 0, 0, 0, 0xff, 0xff, 0x10, 0, 0x00, 0xd8, 0, 0, 0, 0, 0x11, 0];
+        assert_eq!("a\u{10ffff}", read_utf32_string(&le, 2, ByteOrder::LittleEndian).unwrap());
+        assert_eq!("", read_utf32_string(&le, 0, ByteOrder::LittleEndian).unwrap());
+        assert_eq!(EndiannessError::InvalidUtf32 { valid_up_to: 8 },
+                   read_utf32_string(&le, 3, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::InvalidUtf32 { valid_up_to: 0 },
+                   read_utf32_string(&le[12..], 1, ByteOrder::LittleEndian).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 20, found: 16 },
+                   read_utf32_string(&le, 5, ByteOrder::LittleEndian).unwrap_err());
+    }
+}
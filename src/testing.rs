@@ -0,0 +1,83 @@
+//! Proptest strategies for testing parsers built on this crate.
+//!
+//! Requires the `testing` feature.
+//!
+//! ```rust
+//! # extern crate endianness;
+//! # #[macro_use] extern crate proptest;
+//! use endianness::*;
+//! use endianness::testing::encoded;
+//!
+//! proptest! {
+//!     fn reads_back((value, order, bytes) in encoded::<u32>()) {
+//!         prop_assert_eq!(value, read_u32(&bytes, order).unwrap());
+//!     }
+//! }
+//! # fn main() { reads_back(); }
+//! ```
+
+use proptest::prelude::*;
+use std::vec::Vec;
+
+use {ByteOrder, EndianWrite, write_sleb128, write_uleb128};
+
+/// Generates both byte orders.
+pub fn byte_order() -> impl Strategy<Value = ByteOrder> {
+    prop_oneof![Just(ByteOrder::BigEndian), Just(ByteOrder::LittleEndian)]
+}
+
+/// Generates any value of `T` together with a byte order and the value encoded in it.
+pub fn encoded<T: Arbitrary + EndianWrite>() -> impl Strategy<Value = (T, ByteOrder, Vec<u8>)> {
+    (any::<T>(), byte_order()).prop_map(|(value, order)| {
+        let mut bytes = vec![0; value.encoded_len()];
+        value.write(&mut bytes, order).unwrap();
+        (value, order, bytes)
+    })
+}
+
+/// Generates any `u64` together with its ULEB128 encoding.
+pub fn uleb128() -> impl Strategy<Value = (u64, Vec<u8>)> {
+    any::<u64>().prop_map(|value| {
+        let mut bytes = vec![0; 10];
+        let len = write_uleb128(&mut bytes, value).unwrap();
+        bytes.truncate(len);
+        (value, bytes)
+    })
+}
+
+/// Generates any `i64` together with its SLEB128 encoding.
+pub fn sleb128() -> impl Strategy<Value = (i64, Vec<u8>)> {
+    any::<i64>().prop_map(|value| {
+        let mut bytes = vec![0; 10];
+        let len = write_sleb128(&mut bytes, value).unwrap();
+        bytes.truncate(len);
+        (value, bytes)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use {read, read_f64, read_sleb128, read_uleb128};
+    use super::{encoded, sleb128, uleb128};
+
+    proptest! {
+        #[test]
+        fn integers((value, order, bytes) in encoded::<i128>()) {
+            prop_assert_eq!(16, bytes.len());
+            prop_assert_eq!(value, read::<i128>(&bytes, order).unwrap());
+        }
+
+        #[test]
+        fn floats((value, order, bytes) in encoded::<f64>()) {
+            prop_assert_eq!(value.to_bits(), read_f64(&bytes, order).unwrap().to_bits());
+        }
+
+        #[test]
+        fn varints((u, ubytes) in uleb128(), (s, sbytes) in sleb128()) {
+            prop_assert_eq!((u, ubytes.len()), read_uleb128(&ubytes).unwrap());
+            prop_assert_eq!((s, sbytes.len()), read_sleb128(&sbytes).unwrap());
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Traits for reading and writing values generically over their width.
+
+use {ByteOrder, EndiannessResult};
+use {read_u8, read_i8, read_u16, read_i16, read_u32, read_i32, read_u64, read_i64, read_u128,
+     read_i128, read_f32, read_f64};
+use {write_u8, write_i8, write_u16, write_i16, write_u32, write_i32, write_u64, write_i64,
+     write_u128, write_i128, write_f32, write_f64};
+
+/// Types that can be read from a stream of bytes in a given byte order.
+pub trait EndianRead: Sized {
+    /// The number of bytes a value occupies in a stream.
+    const SIZE: usize;
+
+    /// Reads a value from the beginning of a stream of bytes.
+    fn read(data: &[u8], endianness: ByteOrder) -> EndiannessResult<Self>;
+}
+
+/// Types that can be written to a stream of bytes in a given byte order.
+pub trait EndianWrite {
+    /// Returns the number of bytes the value occupies in a stream.
+    fn encoded_len(&self) -> usize;
+
+    /// Writes the value to the beginning of a stream of bytes.
+    fn write(&self, data: &mut [u8], endianness: ByteOrder) -> EndiannessResult<()>;
+}
+
+/// Types whose byte order can be reversed in place.
+///
+/// Use it to fix up values that were copied or memory-mapped from a stream whose byte
+/// order differs from the host's.
+pub trait SwapBytes {
+    /// Reverses the byte order of the value, or of every number it consists of.
+    fn swap_bytes(&mut self);
+}
+
+impl<T: SwapBytes, const N: usize> SwapBytes for [T; N] {
+    fn swap_bytes(&mut self) {
+        for item in self.iter_mut() {
+            item.swap_bytes();
+        }
+    }
+}
+
+impl<T: EndianWrite + ?Sized> EndianWrite for &T {
+    fn encoded_len(&self) -> usize {
+        (**self).encoded_len()
+    }
+
+    fn write(&self, data: &mut [u8], endianness: ByteOrder) -> EndiannessResult<()> {
+        (**self).write(data, endianness)
+    }
+}
+
+/// Reads a value of any supported type from a stream of bytes.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let v = vec![0, 128, 128, 0];
+/// assert_eq!(-32768i16, read(&v[2..4], ByteOrder::BigEndian).unwrap());
+/// assert_eq!(8421376, read::<u32>(&v, ByteOrder::LittleEndian).unwrap());
+/// ```
+pub fn read<T: EndianRead>(data: &[u8], endianness: ByteOrder) -> EndiannessResult<T> {
+    T::read(data, endianness)
+}
+
+/// Writes a value of any supported type to a stream of bytes.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut v = [0u8; 6];
+/// write(&mut v, -32768i16, ByteOrder::BigEndian).unwrap();
+/// write(&mut v[2..], 8421376u32, ByteOrder::LittleEndian).unwrap();
+/// assert_eq!([128, 0, 0, 128, 128, 0], v);
+/// ```
+pub fn write<T: EndianWrite>(data: &mut [u8], value: T, endianness: ByteOrder) -> EndiannessResult<()> {
+    value.write(data, endianness)
+}
+
+macro_rules! impl_endian {
+    ($ty:ty, $size:expr, $read:ident, $write:ident) => (
+        impl EndianRead for $ty {
+            const SIZE: usize = $size;
+
+            #[inline]
+            fn read(data: &[u8], endianness: ByteOrder) -> EndiannessResult<$ty> {
+                $read(data, endianness)
+            }
+        }
+
+        impl EndianWrite for $ty {
+            #[inline]
+            fn encoded_len(&self) -> usize {
+                $size
+            }
+
+            #[inline]
+            fn write(&self, data: &mut [u8], endianness: ByteOrder) -> EndiannessResult<()> {
+                $write(data, *self, endianness)
+            }
+        }
+    );
+}
+
+macro_rules! impl_swap {
+    ($($ty:ident),*) => (
+        $(
+            impl SwapBytes for $ty {
+                fn swap_bytes(&mut self) {
+                    *self = $ty::swap_bytes(*self);
+                }
+            }
+        )*
+    );
+}
+
+impl_swap!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
+impl SwapBytes for f32 {
+    fn swap_bytes(&mut self) {
+        *self = f32::from_bits(self.to_bits().swap_bytes());
+    }
+}
+
+impl SwapBytes for f64 {
+    fn swap_bytes(&mut self) {
+        *self = f64::from_bits(self.to_bits().swap_bytes());
+    }
+}
+
+impl_endian!(u8, 1, read_u8, write_u8);
+impl_endian!(i8, 1, read_i8, write_i8);
+impl_endian!(u16, 2, read_u16, write_u16);
+impl_endian!(i16, 2, read_i16, write_i16);
+impl_endian!(u32, 4, read_u32, write_u32);
+impl_endian!(i32, 4, read_i32, write_i32);
+impl_endian!(u64, 8, read_u64, write_u64);
+impl_endian!(i64, 8, read_i64, write_i64);
+impl_endian!(u128, 16, read_u128, write_u128);
+impl_endian!(i128, 16, read_i128, write_i128);
+impl_endian!(f32, 4, read_f32, write_f32);
+impl_endian!(f64, 8, read_f64, write_f64);
+
+#[cfg(test)]
+mod tests {
+    // Generic functions must round-trip values and agree with the size they report.
+    macro_rules! round_trip {
+        ($name:ident, $ty:ty, $value:expr) => (
+            mod $name {
+                use {ByteOrder, EndianRead, EndianWrite, EndiannessError, read, write};
+
+                #[test]
+                fn big_endian() {
+                    let mut data = [0u8; 16];
+                    write(&mut data, $value, ByteOrder::BigEndian).unwrap();
+                    assert_eq!($value, read::<$ty>(&data, ByteOrder::BigEndian).unwrap());
+                    assert_eq!(&$value.to_be_bytes()[..], &data[..<$ty>::SIZE]);
+                }
+
+                #[test]
+                fn little_endian() {
+                    let mut data = [0u8; 16];
+                    write(&mut data, &$value, ByteOrder::LittleEndian).unwrap();
+                    assert_eq!($value, read::<$ty>(&data, ByteOrder::LittleEndian).unwrap());
+                    assert_eq!(&$value.to_le_bytes()[..], &data[..<$ty>::SIZE]);
+                }
+
+                #[test]
+                fn size() {
+                    let value: $ty = $value;
+                    assert_eq!(<$ty>::SIZE, value.encoded_len());
+                    let short = vec![0u8; <$ty>::SIZE - 1];
+                    assert_eq!(EndiannessError::ShortSlice { expected: <$ty>::SIZE, found: short.len() },
+                               read::<$ty>(&short, ByteOrder::BigEndian).unwrap_err());
+                }
+            }
+        );
+    }
+
+    round_trip!(test_u8, u8, 0xa5u8);
+    round_trip!(test_i8, i8, -91i8);
+    round_trip!(test_u16, u16, 0xa55au16);
+    round_trip!(test_i16, i16, -23206i16);
+    round_trip!(test_u32, u32, 0xdeadbeefu32);
+    round_trip!(test_i32, i32, -559038737i32);
+    round_trip!(test_u64, u64, 0x0123456789abcdefu64);
+    round_trip!(test_i64, i64, -81985529216486896i64);
+    round_trip!(test_u128, u128, 0x0123456789abcdef0123456789abcdefu128);
+    round_trip!(test_i128, i128, -1512366075204170929049582354406559215i128);
+    round_trip!(test_f32, f32, -127.5f32);
+    round_trip!(test_f64, f64, 1.0e-300f64);
+
+    mod swap {
+        use SwapBytes;
+
+        #[test]
+        fn numbers() {
+            let mut v = 0x0102u16;
+            SwapBytes::swap_bytes(&mut v);
+            assert_eq!(0x0201, v);
+            let mut v = -2i8;
+            SwapBytes::swap_bytes(&mut v);
+            assert_eq!(-2, v);
+            let mut v = f32::from_bits(0x0000ffc2);
+            v.swap_bytes();
+            assert_eq!(-127.5, v);
+        }
+
+        #[test]
+        fn arrays() {
+            let mut v = [[0x0102u16, 0x0304], [0x0506, 0x0708]];
+            v.swap_bytes();
+            assert_eq!([[0x0201, 0x0403], [0x0605, 0x0807]], v);
+        }
+    }
+}
@@ -0,0 +1,542 @@
+//! Variable-length integer encodings.
+//!
+//! Protobuf varints are unsigned LEB128, so `read_uleb128`/`write_uleb128` cover them too.
+//!
+//! The readers return the decoded value together with the number of bytes it occupied,
+//! and the writers return the number of bytes written. The byte order of each encoding
+//! is fixed by its specification, so none of these functions take a `ByteOrder`.
+
+use {ByteOrder, EndiannessError, EndiannessResult};
+use {read_u16, read_u32, read_u64, write_u16, write_u32, write_u64};
+
+/// Returns the number of bytes `write_uleb128` needs for the value.
+pub fn uleb128_len(value: u64) -> usize {
+    let bits = 64 - (value | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Reads an unsigned LEB128 integer, as used by WebAssembly and DWARF.
+///
+/// Returns the value and the number of bytes read. Returns `ShortSlice` if the stream
+/// ends before the last byte of the value, and `Overflow` if the value does not fit
+/// into 64 bits.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!((624485, 3), read_uleb128(&[0xe5, 0x8e, 0x26, 0xff]).unwrap());
+/// ```
+pub fn read_uleb128(data: &[u8]) -> EndiannessResult<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, &byte) in data.iter().enumerate() {
+        let bits = u64::from(byte & 0x7f);
+        let shift = 7 * i;
+        // The tenth byte may only hold the single remaining bit of a 64-bit value.
+        if shift >= 64 || (shift == 63 && bits > 1) {
+            return Err(EndiannessError::Overflow);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    // At least one more byte is needed for the value to end.
+    Err(EndiannessError::ShortSlice { expected: data.len() + 1, found: data.len() })
+}
+
+/// Writes an unsigned LEB128 integer and returns the number of bytes written.
+///
+/// Nothing is written if the stream is too short for the whole value.
+pub fn write_uleb128(data: &mut [u8], value: u64) -> EndiannessResult<usize> {
+    let len = uleb128_len(value);
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    let mut rest = value;
+    for byte in data[..len - 1].iter_mut() {
+        *byte = (rest & 0x7f) as u8 | 0x80;
+        rest >>= 7;
+    }
+    data[len - 1] = rest as u8;
+    Ok(len)
+}
+
+/// Returns the number of bytes `write_sleb128` needs for the value.
+pub fn sleb128_len(value: i64) -> usize {
+    // The encoding needs the significant bits plus a sign bit.
+    let magnitude = if value < 0 { !value } else { value };
+    let bits = 65 - magnitude.leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Reads a signed LEB128 integer, as used by WebAssembly and DWARF.
+///
+/// Returns the value and the number of bytes read, with the same errors as `read_uleb128`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!((-123456, 3), read_sleb128(&[0xc0, 0xbb, 0x78]).unwrap());
+/// ```
+pub fn read_sleb128(data: &[u8]) -> EndiannessResult<(i64, usize)> {
+    let mut value = 0i64;
+    for (i, &byte) in data.iter().enumerate() {
+        let bits = i64::from(byte & 0x7f);
+        let shift = 7 * i;
+        // The tenth byte holds the last bit of a 64-bit value; the rest must repeat the sign.
+        if shift >= 64 || (shift == 63 && bits != 0 && bits != 0x7f) {
+            return Err(EndiannessError::Overflow);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            if shift + 7 < 64 && byte & 0x40 != 0 {
+                value |= -1 << (shift + 7);
+            }
+            return Ok((value, i + 1));
+        }
+    }
+    // At least one more byte is needed for the value to end.
+    Err(EndiannessError::ShortSlice { expected: data.len() + 1, found: data.len() })
+}
+
+/// Writes a signed LEB128 integer and returns the number of bytes written.
+///
+/// Nothing is written if the stream is too short for the whole value.
+pub fn write_sleb128(data: &mut [u8], value: i64) -> EndiannessResult<usize> {
+    let len = sleb128_len(value);
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    let mut rest = value;
+    for byte in data[..len - 1].iter_mut() {
+        *byte = (rest & 0x7f) as u8 | 0x80;
+        rest >>= 7;
+    }
+    data[len - 1] = (rest & 0x7f) as u8;
+    Ok(len)
+}
+
+/// Maps a signed integer to an unsigned one so that values close to zero stay small,
+/// as protobuf does for `sint64` fields: 0, -1, 1, -2, … become 0, 1, 2, 3, …
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Reverses `zigzag_encode`.
+pub fn zigzag_decode(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Reads a ZigZag-encoded varint, as used by protobuf `sint32` and `sint64` fields.
+///
+/// Returns the value and the number of bytes read, with the same errors as `read_uleb128`.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!((-2, 1), read_zigzag_varint(&[3]).unwrap());
+/// ```
+pub fn read_zigzag_varint(data: &[u8]) -> EndiannessResult<(i64, usize)> {
+    let (value, len) = read_uleb128(data)?;
+    Ok((zigzag_decode(value), len))
+}
+
+/// Writes a ZigZag-encoded varint and returns the number of bytes written.
+pub fn write_zigzag_varint(data: &mut [u8], value: i64) -> EndiannessResult<usize> {
+    write_uleb128(data, zigzag_encode(value))
+}
+
+/// The largest value a MIDI variable-length quantity can hold, which takes four bytes.
+pub const VLQ_MAX: u32 = 0x0fff_ffff;
+
+/// Returns the number of bytes `write_vlq` needs for the value, which must not exceed
+/// `VLQ_MAX`.
+pub fn vlq_len(value: u32) -> usize {
+    let bits = 32 - (value | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Reads a variable-length quantity as used by Standard MIDI Files.
+///
+/// The value is stored in big-endian groups of seven bits, at most four of them.
+/// Returns the value and the number of bytes read. Returns `ShortSlice` if the stream
+/// ends before the last byte of the value, and `Overflow` if the quantity is longer
+/// than four bytes.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!((0x2000, 2), read_vlq(&[0xc0, 0x00]).unwrap());
+/// ```
+pub fn read_vlq(data: &[u8]) -> EndiannessResult<(u32, usize)> {
+    let mut value = 0u32;
+    for (i, &byte) in data.iter().enumerate() {
+        if i == 4 {
+            return Err(EndiannessError::Overflow);
+        }
+        value = (value << 7) | u32::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    // At least one more byte is needed for the value to end.
+    Err(EndiannessError::ShortSlice { expected: data.len() + 1, found: data.len() })
+}
+
+/// Writes a MIDI variable-length quantity and returns the number of bytes written.
+///
+/// Returns `Overflow` if the value is greater than `VLQ_MAX`. Nothing is written if the
+/// stream is too short for the whole value.
+pub fn write_vlq(data: &mut [u8], value: u32) -> EndiannessResult<usize> {
+    if value > VLQ_MAX {
+        return Err(EndiannessError::Overflow);
+    }
+    let len = vlq_len(value);
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    for (i, byte) in data[..len].iter_mut().enumerate() {
+        let group = (value >> (7 * (len - 1 - i))) as u8 & 0x7f;
+        *byte = if i + 1 < len { group | 0x80 } else { group };
+    }
+    Ok(len)
+}
+
+/// Returns the number of bytes `write_compact_size` needs for the value.
+pub fn compact_size_len(value: u64) -> usize {
+    if value < 0xfd {
+        1
+    } else if value <= 0xffff {
+        3
+    } else if value <= 0xffff_ffff {
+        5
+    } else {
+        9
+    }
+}
+
+/// Reads a CompactSize integer, as used by the Bitcoin wire format.
+///
+/// Values below `0xfd` take a single byte; larger ones are a marker byte (`0xfd`, `0xfe`
+/// or `0xff`) followed by a little-endian 16, 32 or 64-bit integer. Returns the value and
+/// the number of bytes read. Returns `NonCanonical` if the value would fit a shorter
+/// form, as Bitcoin nodes reject those encodings.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!((515, 3), read_compact_size(&[0xfd, 0x03, 0x02]).unwrap());
+/// ```
+pub fn read_compact_size(data: &[u8]) -> EndiannessResult<(u64, usize)> {
+    let order = ByteOrder::LittleEndian;
+    let first = match data.first() {
+        Some(&byte) => byte,
+        None => return Err(EndiannessError::ShortSlice { expected: 1, found: 0 }),
+    };
+    let len = match first {
+        0xfd => 3,
+        0xfe => 5,
+        0xff => 9,
+        _ => 1,
+    };
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    let (value, min) = match len {
+        3 => (u64::from(read_u16(&data[1..], order)?), 0xfd),
+        5 => (u64::from(read_u32(&data[1..], order)?), 0x1_0000),
+        9 => (read_u64(&data[1..], order)?, 0x1_0000_0000),
+        _ => (u64::from(first), 0),
+    };
+    if value < min {
+        Err(EndiannessError::NonCanonical)
+    } else {
+        Ok((value, len))
+    }
+}
+
+/// Writes a CompactSize integer in its shortest form and returns the number of bytes
+/// written.
+///
+/// Nothing is written if the stream is too short for the whole value.
+pub fn write_compact_size(data: &mut [u8], value: u64) -> EndiannessResult<usize> {
+    let len = compact_size_len(value);
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    let order = ByteOrder::LittleEndian;
+    match len {
+        1 => data[0] = value as u8,
+        3 => {
+            data[0] = 0xfd;
+            write_u16(&mut data[1..], value as u16, order)?;
+        }
+        5 => {
+            data[0] = 0xfe;
+            write_u32(&mut data[1..], value as u32, order)?;
+        }
+        _ => {
+            data[0] = 0xff;
+            write_u64(&mut data[1..], value, order)?;
+        }
+    }
+    Ok(len)
+}
+
+/// The largest value a QUIC variable-length integer can hold, 2^62 - 1.
+pub const QUIC_VARINT_MAX: u64 = (1 << 62) - 1;
+
+/// Returns the number of bytes `write_quic_varint` needs for the value, which must not
+/// exceed `QUIC_VARINT_MAX`.
+pub fn quic_varint_len(value: u64) -> usize {
+    if value < 1 << 6 {
+        1
+    } else if value < 1 << 14 {
+        2
+    } else if value < 1 << 30 {
+        4
+    } else {
+        8
+    }
+}
+
+/// Reads a QUIC variable-length integer (RFC 9000, section 16).
+///
+/// The two most significant bits of the first byte give the length of 1, 2, 4 or 8 bytes,
+/// and the remaining bits hold the value in big-endian. Returns the value and the number
+/// of bytes read. Longer encodings than necessary are accepted, as the RFC allows them.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!((15293, 2), read_quic_varint(&[0x7b, 0xbd]).unwrap());
+/// ```
+pub fn read_quic_varint(data: &[u8]) -> EndiannessResult<(u64, usize)> {
+    let first = match data.first() {
+        Some(&byte) => byte,
+        None => return Err(EndiannessError::ShortSlice { expected: 1, found: 0 }),
+    };
+    let len = 1 << (first >> 6);
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    let value = data[1..len].iter().fold(u64::from(first & 0x3f), |value, &byte| {
+        (value << 8) | u64::from(byte)
+    });
+    Ok((value, len))
+}
+
+/// Writes a QUIC variable-length integer in its shortest form and returns the number of
+/// bytes written.
+///
+/// Returns `Overflow` if the value is greater than `QUIC_VARINT_MAX`. Nothing is written
+/// if the stream is too short for the whole value.
+pub fn write_quic_varint(data: &mut [u8], value: u64) -> EndiannessResult<usize> {
+    if value > QUIC_VARINT_MAX {
+        return Err(EndiannessError::Overflow);
+    }
+    let len = quic_varint_len(value);
+    if data.len() < len {
+        return Err(EndiannessError::ShortSlice { expected: len, found: data.len() });
+    }
+    data[..len].copy_from_slice(&value.to_be_bytes()[8 - len..]);
+    data[0] |= (len.trailing_zeros() as u8) << 6;
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteOrder, EndiannessError, read_u64};
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!((0, 1), read_uleb128(&[0]).unwrap());
+        assert_eq!((127, 1), read_uleb128(&[0x7f]).unwrap());
+        assert_eq!((128, 2), read_uleb128(&[0x80, 0x01]).unwrap());
+        // Redundant continuation bytes are accepted, as the DWARF and Wasm specs allow padding.
+        assert_eq!((1, 3), read_uleb128(&[0x81, 0x80, 0x00]).unwrap());
+        assert_eq!((u64::MAX, 10),
+                   read_uleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]).unwrap());
+    }
+
+    #[test]
+    fn round_trip() {
+        let values = [0, 1, 127, 128, 300, 16383, 16384, u64::from(u32::MAX), 1 << 63, u64::MAX];
+        for &value in &values {
+            let mut v = [0u8; 10];
+            let len = write_uleb128(&mut v, value).unwrap();
+            assert_eq!(uleb128_len(value), len);
+            assert_eq!((value, len), read_uleb128(&v).unwrap());
+        }
+        assert_eq!(1, uleb128_len(0));
+        assert_eq!(10, uleb128_len(u64::MAX));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 1, found: 0 },
+                   read_uleb128(&[]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   read_uleb128(&[0x80, 0x80]).unwrap_err());
+        assert_eq!(EndiannessError::Overflow,
+                   read_uleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, read_uleb128(&[0x80; 11]).unwrap_err());
+        let mut v = [0u8; 2];
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   write_uleb128(&mut v, 16384).unwrap_err());
+        assert_eq!([0, 0], v);
+    }
+
+    #[test]
+    fn signed_known_values() {
+        assert_eq!((0, 1), read_sleb128(&[0]).unwrap());
+        assert_eq!((63, 1), read_sleb128(&[0x3f]).unwrap());
+        assert_eq!((-1, 1), read_sleb128(&[0x7f]).unwrap());
+        assert_eq!((-64, 1), read_sleb128(&[0x40]).unwrap());
+        assert_eq!((64, 2), read_sleb128(&[0xc0, 0x00]).unwrap());
+        assert_eq!((-129, 2), read_sleb128(&[0xff, 0x7e]).unwrap());
+        assert_eq!((i64::MIN, 10),
+                   read_sleb128(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]).unwrap());
+        assert_eq!((i64::MAX, 10),
+                   read_sleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]).unwrap());
+    }
+
+    #[test]
+    fn signed_round_trip() {
+        let values = [0, 1, -1, 63, 64, -64, -65, 8191, -8192, i64::from(i32::MIN), i64::MAX, i64::MIN];
+        for &value in &values {
+            let mut v = [0u8; 10];
+            let len = write_sleb128(&mut v, value).unwrap();
+            assert_eq!(sleb128_len(value), len);
+            assert_eq!((value, len), read_sleb128(&v).unwrap());
+
+            let len = write_zigzag_varint(&mut v, value).unwrap();
+            assert_eq!((value, len), read_zigzag_varint(&v).unwrap());
+        }
+    }
+
+    #[test]
+    fn zigzag() {
+        assert_eq!([0, 1, 2, 3, 4], [zigzag_encode(0), zigzag_encode(-1), zigzag_encode(1),
+                                     zigzag_encode(-2), zigzag_encode(2)]);
+        assert_eq!(u64::MAX, zigzag_encode(i64::MIN));
+        assert_eq!(u64::MAX - 1, zigzag_encode(i64::MAX));
+        assert_eq!(i64::MIN, zigzag_decode(u64::MAX));
+        assert_eq!(-150, zigzag_decode(299));
+    }
+
+    #[test]
+    fn signed_errors() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   read_sleb128(&[0xff]).unwrap_err());
+        assert_eq!(EndiannessError::Overflow,
+                   read_sleb128(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   read_zigzag_varint(&[0x80]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   write_sleb128(&mut [0u8; 1], 64).unwrap_err());
+    }
+
+    #[test]
+    fn vlq() {
+        // Examples from the Standard MIDI File specification.
+        let examples: [(u32, &[u8]); 8] = [
+            (0x00, &[0x00]),
+            (0x7f, &[0x7f]),
+            (0x80, &[0x81, 0x00]),
+            (0x2000, &[0xc0, 0x00]),
+            (0x3fff, &[0xff, 0x7f]),
+            (0x4000, &[0x81, 0x80, 0x00]),
+            (0x1fffff, &[0xff, 0xff, 0x7f]),
+            (0x0fffffff, &[0xff, 0xff, 0xff, 0x7f]),
+        ];
+        for &(value, bytes) in &examples {
+            assert_eq!((value, bytes.len()), read_vlq(bytes).unwrap());
+            let mut v = [0u8; 4];
+            assert_eq!(bytes.len(), write_vlq(&mut v, value).unwrap());
+            assert_eq!(bytes, &v[..bytes.len()]);
+            assert_eq!(bytes.len(), vlq_len(value));
+        }
+    }
+
+    #[test]
+    fn vlq_errors() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   read_vlq(&[0x81, 0x80]).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, read_vlq(&[0x81, 0x80, 0x80, 0x80, 0x00]).unwrap_err());
+        assert_eq!(EndiannessError::Overflow, write_vlq(&mut [0u8; 5], VLQ_MAX + 1).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 2, found: 1 },
+                   write_vlq(&mut [0u8; 1], 0x80).unwrap_err());
+    }
+
+    #[test]
+    fn compact_size() {
+        let examples: [(u64, &[u8]); 6] = [
+            (0, &[0x00]),
+            (0xfc, &[0xfc]),
+            (0xfd, &[0xfd, 0xfd, 0x00]),
+            (0xffff, &[0xfd, 0xff, 0xff]),
+            (0x10000, &[0xfe, 0x00, 0x00, 0x01, 0x00]),
+            (0x1_0000_0000, &[0xff, 0, 0, 0, 0, 1, 0, 0, 0]),
+        ];
+        for &(value, bytes) in &examples {
+            assert_eq!((value, bytes.len()), read_compact_size(bytes).unwrap());
+            let mut v = [0u8; 9];
+            assert_eq!(bytes.len(), write_compact_size(&mut v, value).unwrap());
+            assert_eq!(bytes, &v[..bytes.len()]);
+        }
+        let mut v = [0u8; 9];
+        write_compact_size(&mut v, u64::MAX).unwrap();
+        assert_eq!(u64::MAX, read_u64(&v[1..], ByteOrder::LittleEndian).unwrap());
+    }
+
+    #[test]
+    fn compact_size_errors() {
+        assert_eq!(EndiannessError::NonCanonical, read_compact_size(&[0xfd, 0xfc, 0x00]).unwrap_err());
+        assert_eq!(EndiannessError::NonCanonical,
+                   read_compact_size(&[0xfe, 0xff, 0xff, 0x00, 0x00]).unwrap_err());
+        assert_eq!(EndiannessError::NonCanonical,
+                   read_compact_size(&[0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 1, found: 0 },
+                   read_compact_size(&[]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 5, found: 4 },
+                   read_compact_size(&[0xfe, 0, 0, 1]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 5, found: 4 },
+                   write_compact_size(&mut [0u8; 4], 0x10000).unwrap_err());
+    }
+
+    #[test]
+    fn quic_varint() {
+        // Examples from RFC 9000, appendix A.1.
+        let examples: [(u64, &[u8]); 4] = [
+            (151288809941952652, &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c]),
+            (494878333, &[0x9d, 0x7f, 0x3e, 0x7d]),
+            (15293, &[0x7b, 0xbd]),
+            (37, &[0x25]),
+        ];
+        for &(value, bytes) in &examples {
+            assert_eq!((value, bytes.len()), read_quic_varint(bytes).unwrap());
+            let mut v = [0u8; 8];
+            assert_eq!(bytes.len(), write_quic_varint(&mut v, value).unwrap());
+            assert_eq!(bytes, &v[..bytes.len()]);
+        }
+        // The same value may use a longer encoding.
+        assert_eq!((37, 2), read_quic_varint(&[0x40, 0x25]).unwrap());
+        let mut v = [0u8; 8];
+        assert_eq!(8, write_quic_varint(&mut v, QUIC_VARINT_MAX).unwrap());
+        assert_eq!([0xff; 8], v);
+    }
+
+    #[test]
+    fn quic_varint_errors() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 1, found: 0 },
+                   read_quic_varint(&[]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_quic_varint(&[0x9d, 0x7f, 0x3e]).unwrap_err());
+        assert_eq!(EndiannessError::Overflow,
+                   write_quic_varint(&mut [0u8; 8], QUIC_VARINT_MAX + 1).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   write_quic_varint(&mut [0u8; 3], 1 << 14).unwrap_err());
+    }
+}
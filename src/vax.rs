@@ -0,0 +1,132 @@
+//! VAX floating point numbers.
+//!
+//! VAX floats are stored like PDP-11 integers: little-endian 16-bit words, most
+//! significant word first. The value of a normalized VAX float is
+//! `(-1)^sign * 0.1fraction * 2^(exponent - bias)`, with a hidden bit like IEEE 754 but
+//! an exponent that is off by two. There are no infinities, NaNs or subnormal numbers:
+//! an exponent of zero means zero, or a reserved operand if the sign bit is set.
+//!
+//! * F_floating: 32 bits, 8-bit exponent with a bias of 128, 23-bit fraction.
+//! * D_floating: 64 bits, 8-bit exponent with a bias of 128, 55-bit fraction.
+//! * G_floating: 64 bits, 11-bit exponent with a bias of 1024, 52-bit fraction.
+
+use EndiannessResult;
+use {pow2, read_u32_pdp, read_u64_pdp};
+
+// Applies the sign and maps reserved operands (sign set, exponent zero) to NaN.
+fn finish(value: f64, negative: bool, exponent: u64) -> f64 {
+    match (negative, exponent) {
+        (false, 0) => 0.0,
+        (true, 0) => f64::NAN,
+        (true, _) => -value,
+        (false, _) => value,
+    }
+}
+
+/// Reads a VAX F_floating number.
+///
+/// Values with an exponent of 1 or 2 are below `f32::MIN_POSITIVE` and are rounded to
+/// nearest, ties to even, into the subnormal range of `f32`, which loses the lowest one or
+/// two fraction bits. All other values are exact. Reserved operands are returned as NaN.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(1.0, read_vax_f(&[0x80, 0x40, 0x00, 0x00]).unwrap());
+/// ```
+pub fn read_vax_f(data: &[u8]) -> EndiannessResult<f32> {
+    let bits = read_u32_pdp(data)?;
+    let exponent = u64::from((bits >> 23) & 0xff);
+    let mantissa = f64::from((bits & 0x007f_ffff) | 0x0080_0000);
+    let value = mantissa * pow2(exponent as i32 - 128 - 24);
+    Ok(finish(value, bits >> 31 == 1, exponent) as f32)
+}
+
+/// Reads a VAX D_floating number.
+///
+/// The 55-bit fraction is rounded to the 52 bits of `f64`, to nearest with ties to even.
+/// Reserved operands are returned as NaN.
+pub fn read_vax_d(data: &[u8]) -> EndiannessResult<f64> {
+    let bits = read_u64_pdp(data)?;
+    let exponent = (bits >> 55) & 0xff;
+    let mantissa = ((bits & 0x007f_ffff_ffff_ffff) | 0x0080_0000_0000_0000) as f64;
+    let value = mantissa * pow2(exponent as i32 - 128 - 56);
+    Ok(finish(value, bits >> 63 == 1, exponent))
+}
+
+/// Reads a VAX G_floating number.
+///
+/// Values with an exponent of 1 or 2 are below `f64::MIN_POSITIVE` and are rounded to
+/// nearest, ties to even, into the subnormal range of `f64`, which loses the lowest one or
+/// two fraction bits. All other values are exact. Reserved operands are returned as NaN.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// assert_eq!(-0.5, read_vax_g(&[0x00, 0xc0, 0, 0, 0, 0, 0, 0]).unwrap());
+/// ```
+pub fn read_vax_g(data: &[u8]) -> EndiannessResult<f64> {
+    let bits = read_u64_pdp(data)?;
+    let exponent = (bits >> 52) & 0x7ff;
+    let mantissa = ((bits & 0x000f_ffff_ffff_ffff) | 0x0010_0000_0000_0000) as f64;
+    // Scaled in two steps since the smallest exponents are out of range for `pow2`.
+    let value = mantissa * pow2(-512) * pow2(exponent as i32 - 1024 - 53 + 512);
+    Ok(finish(value, bits >> 63 == 1, exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use EndiannessError;
+    use super::*;
+
+    #[test]
+    fn f_floating() {
+        assert_eq!(1.0, read_vax_f(&[0x80, 0x40, 0x00, 0x00]).unwrap());
+        assert_eq!(-0.5, read_vax_f(&[0x00, 0xc0, 0x00, 0x00]).unwrap());
+        assert_eq!(3.0, read_vax_f(&[0x40, 0x41, 0x00, 0x00]).unwrap());
+        // The smallest exponent is below f32::MIN_POSITIVE: the subnormal result keeps
+        // the fraction down to 2^-149 and rounds off the bits of 2^-150 and 2^-151.
+        assert_eq!(2f64.powi(-128), f64::from(read_vax_f(&[0x80, 0x00, 0x00, 0x00]).unwrap()));
+        assert_eq!(2f64.powi(-128), f64::from(read_vax_f(&[0x80, 0x00, 0x01, 0x00]).unwrap()));
+        assert_eq!(2f64.powi(-128) + 2f64.powi(-149),
+                   f64::from(read_vax_f(&[0x80, 0x00, 0x03, 0x00]).unwrap()));
+        assert_eq!(1.7014117e38, read_vax_f(&[0xff, 0x7f, 0xff, 0xff]).unwrap());
+        // A "dirty" zero with a fraction but no exponent is still zero.
+        assert_eq!(0.0, read_vax_f(&[0x12, 0x00, 0x34, 0x56]).unwrap());
+        assert!(read_vax_f(&[0x00, 0x80, 0x00, 0x00]).unwrap().is_nan());
+    }
+
+    #[test]
+    fn d_floating() {
+        assert_eq!(1.0, read_vax_d(&[0x80, 0x40, 0, 0, 0, 0, 0, 0]).unwrap());
+        assert_eq!(-3.0, read_vax_d(&[0x40, 0xc1, 0, 0, 0, 0, 0, 0]).unwrap());
+        // The three lowest fraction bits are rounded away: 1 + 2^-55 is 1.0 in f64.
+        assert_eq!(1.0, read_vax_d(&[0x80, 0x40, 0, 0, 0, 0, 0x01, 0x00]).unwrap());
+        assert!(read_vax_d(&[0x00, 0x80, 0, 0, 0, 0, 0, 0]).unwrap().is_nan());
+    }
+
+    #[test]
+    fn g_floating() {
+        assert_eq!(1.0, read_vax_g(&[0x10, 0x40, 0, 0, 0, 0, 0, 0]).unwrap());
+        assert_eq!(-0.5, read_vax_g(&[0x00, 0xc0, 0, 0, 0, 0, 0, 0]).unwrap());
+        assert_eq!(0.1, read_vax_g(&[0xd9, 0x3f, 0x99, 0x99, 0x99, 0x99, 0x9a, 0x99]).unwrap());
+        // The smallest value is 2^-1024, a subnormal f64, which keeps the fraction down to
+        // 2^-1074 and rounds off the bits of 2^-1075 and 2^-1076.
+        assert_eq!(f64::MIN_POSITIVE / 4.0, read_vax_g(&[0x10, 0x00, 0, 0, 0, 0, 0, 0]).unwrap());
+        assert_eq!(f64::MIN_POSITIVE / 4.0, read_vax_g(&[0x10, 0x00, 0, 0, 0, 0, 0x01, 0x00]).unwrap());
+        assert_eq!(f64::MIN_POSITIVE / 4.0 + f64::from_bits(1),
+                   read_vax_g(&[0x10, 0x00, 0, 0, 0, 0, 0x03, 0x00]).unwrap());
+        assert_eq!(0.0, read_vax_g(&[0; 8]).unwrap());
+        assert!(read_vax_g(&[0x00, 0x80, 0, 0, 0, 0, 0, 0]).unwrap().is_nan());
+    }
+
+    #[test]
+    fn short_slice() {
+        assert_eq!(EndiannessError::ShortSlice { expected: 4, found: 3 },
+                   read_vax_f(&[0; 3]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   read_vax_d(&[0; 7]).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 7 },
+                   read_vax_g(&[0; 7]).unwrap_err());
+    }
+}
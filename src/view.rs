@@ -0,0 +1,101 @@
+//! Zero-copy views of byte slices as slices of wrapper types.
+
+#![allow(unsafe_code)]
+
+use core::slice;
+
+use {EndiannessError, EndiannessResult};
+
+/// Types that can be read directly from the bytes of a buffer.
+///
+/// # Safety
+///
+/// Implementors must be plain data without padding, and every bit pattern must be a
+/// valid value of the type. The wrapper types such as `U32Be` satisfy this.
+pub unsafe trait ByteView: Copy {}
+
+fn check<T: ByteView>(data: &[u8]) -> EndiannessResult<usize> {
+    let size = size_of::<T>();
+    let aligned = (data.as_ptr() as usize).is_multiple_of(align_of::<T>());
+    if size == 0 || !data.len().is_multiple_of(size) || !aligned {
+        Err(EndiannessError::Misaligned)
+    } else {
+        Ok(data.len() / size)
+    }
+}
+
+/// Views a stream of bytes as a slice of `T` without copying it.
+///
+/// The length of the stream must be a multiple of the size of `T` and the stream must
+/// be suitably aligned, otherwise `Misaligned` is returned. The wrapper types have an
+/// alignment of one, so only the length matters for them.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let table = [0, 0, 1, 0, 0, 0, 0, 2];
+/// let entries: &[U32Be] = cast_slice(&table).unwrap();
+/// assert_eq!(256, entries[0].get());
+/// assert_eq!(2, entries[1].get());
+/// ```
+pub fn cast_slice<T: ByteView>(data: &[u8]) -> EndiannessResult<&[T]> {
+    let len = check::<T>(data)?;
+    // Safety: the size and alignment were checked above, and `T` accepts any bytes.
+    Ok(unsafe { slice::from_raw_parts(data.as_ptr() as *const T, len) })
+}
+
+/// Views a mutable stream of bytes as a slice of `T` without copying it.
+///
+/// The same requirements as for `cast_slice` apply.
+pub fn cast_slice_mut<T: ByteView>(data: &mut [u8]) -> EndiannessResult<&mut [T]> {
+    let len = check::<T>(data)?;
+    // Safety: as above; the borrow of `data` is carried over to the result.
+    Ok(unsafe { slice::from_raw_parts_mut(data.as_mut_ptr() as *mut T, len) })
+}
+
+#[cfg(test)]
+mod tests {
+    use EndiannessError;
+    use super::*;
+    use wrappers::*;
+
+    #[test]
+    fn views() {
+        let table = [194, 255, 0, 0, 0, 0, 0, 1];
+        let be: &[U32Be] = cast_slice(&table).unwrap();
+        assert_eq!(2, be.len());
+        assert_eq!(0xc2ff0000, be[0].get());
+        assert_eq!(1, be[1].get());
+
+        let le: &[U16Le] = cast_slice(&table[1..7]).unwrap();
+        assert_eq!([0x00ff, 0, 0], [le[0].get(), le[1].get(), le[2].get()]);
+
+        let float: &[F32Be] = cast_slice(&table[..4]).unwrap();
+        assert_eq!(-127.5, float[0].get());
+
+        let wide: &[I64Le] = cast_slice(&table).unwrap();
+        assert_eq!(0x0100_0000_0000_ffc2, wide[0].get());
+
+        let empty: &[U128Be] = cast_slice(&[]).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn mutable_view() {
+        let mut table = [0u8; 4];
+        {
+            let view: &mut [U16Be] = cast_slice_mut(&mut table).unwrap();
+            view[1] = cast_slice::<U16Be>(&[1, 2]).unwrap()[0];
+        }
+        assert_eq!([0, 0, 1, 2], table);
+    }
+
+    #[test]
+    fn misaligned() {
+        let table = [0u8; 7];
+        assert_eq!(EndiannessError::Misaligned,
+                   cast_slice::<U32Be>(&table).unwrap_err());
+        assert_eq!(EndiannessError::Misaligned,
+                   cast_slice_mut::<I16Le>(&mut [0u8; 3]).unwrap_err());
+    }
+}
@@ -0,0 +1,757 @@
+//! A serde data format with fixed-width numbers in a byte order chosen by the caller.
+//!
+//! The encoding is close to bincode's:
+//!
+//! * integers and floats take their full width, in the given byte order;
+//! * `bool` is one byte, 0 or 1, and `char` is its scalar value as a `u32`;
+//! * strings, byte strings, sequences and maps start with their length as a `u64`;
+//! * `Option` starts with a one-byte tag, 0 for `None` and 1 for `Some`;
+//! * tuples and structs are their fields in order, without a length;
+//! * enum variants start with their index as a `u32`;
+//! * unit types take no bytes.
+//!
+//! The format does not describe itself, so `deserialize_any` is not supported. The
+//! encoding of a value is deterministic: `to_bytes` always produces the same bytes for
+//! equal values, as long as their `Serialize` implementations do.
+
+use core::{fmt, str};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+#[cfg(feature = "std")]
+use serde::ser::{self, Serialize};
+
+#[cfg(feature = "std")]
+use EndianWrite;
+use {ByteOrder, EndianRead, EndiannessError};
+
+/// The error of the serde data format.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WireError {
+    /// The input is too short, or a length does not fit into `usize` or, when
+    /// serializing, into a `u64`.
+    Endianness(EndiannessError),
+    /// The input contains a value that is not valid for its type, such as a `bool` other
+    /// than 0 or 1. The string names the type.
+    Invalid(&'static str),
+    /// The value was decoded, but the input has this many bytes left.
+    TrailingBytes(usize),
+    /// The type needs a feature the format does not have, such as `deserialize_any`.
+    Unsupported(&'static str),
+    /// An error reported by a `Serialize` or `Deserialize` implementation.
+    #[cfg(feature = "std")]
+    Custom(String),
+    /// An error reported by a `Deserialize` implementation. The message needs `std`.
+    #[cfg(not(feature = "std"))]
+    Custom,
+}
+
+impl From<EndiannessError> for WireError {
+    fn from(err: EndiannessError) -> WireError {
+        WireError::Endianness(err)
+    }
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WireError::Endianness(ref err) => err.fmt(f),
+            WireError::Invalid(ty) => write!(f, "The input contains an invalid {}.", ty),
+            WireError::TrailingBytes(n) => write!(f, "The input has {} trailing bytes.", n),
+            WireError::Unsupported(what) => write!(f, "The format does not support {}.", what),
+            #[cfg(feature = "std")]
+            WireError::Custom(ref msg) => f.write_str(msg),
+            #[cfg(not(feature = "std"))]
+            WireError::Custom => write!(f, "The value could not be decoded."),
+        }
+    }
+}
+
+impl de::StdError for WireError {}
+
+impl de::Error for WireError {
+    #[cfg(feature = "std")]
+    fn custom<T: fmt::Display>(msg: T) -> WireError {
+        WireError::Custom(msg.to_string())
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn custom<T: fmt::Display>(_msg: T) -> WireError {
+        WireError::Custom
+    }
+}
+
+#[cfg(feature = "std")]
+impl ser::Error for WireError {
+    fn custom<T: fmt::Display>(msg: T) -> WireError {
+        WireError::Custom(msg.to_string())
+    }
+}
+
+/// Result type alias of the serde data format.
+pub type WireResult<T> = Result<T, WireError>;
+
+/// Decodes a value of type `T` that fills the whole of `data`.
+///
+/// Returns `TrailingBytes` if `data` is longer than the encoding of the value. Strings and
+/// byte strings can be borrowed from `data`. Requires the `serde` feature.
+///
+/// ```rust
+/// # extern crate endianness;
+/// # extern crate serde;
+/// use endianness::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Header<'a> {
+///     id: u16,
+///     flags: Option<u8>,
+///     name: &'a str,
+/// }
+///
+/// # fn main() {
+/// let data = [0, 7, 1, 3, 0, 0, 0, 0, 0, 0, 0, 2, b'o', b'k'];
+/// let header: Header = from_bytes(&data, ByteOrder::BigEndian).unwrap();
+/// assert_eq!(Header { id: 7, flags: Some(3), name: "ok" }, header);
+/// # }
+/// ```
+pub fn from_bytes<'de, T: de::Deserialize<'de>>(data: &'de [u8], endianness: ByteOrder) -> WireResult<T> {
+    let mut deserializer = Deserializer { input: data, endianness };
+    let value = T::deserialize(&mut deserializer)?;
+    match deserializer.input.len() {
+        0 => Ok(value),
+        n => Err(WireError::TrailingBytes(n)),
+    }
+}
+
+struct Deserializer<'de> {
+    input: &'de [u8],
+    endianness: ByteOrder,
+}
+
+impl<'de> Deserializer<'de> {
+    fn take(&mut self, n: usize) -> WireResult<&'de [u8]> {
+        if self.input.len() < n {
+            return Err(EndiannessError::ShortSlice { expected: n, found: self.input.len() }.into());
+        }
+        let (head, tail) = self.input.split_at(n);
+        self.input = tail;
+        Ok(head)
+    }
+
+    fn read<T: EndianRead>(&mut self) -> WireResult<T> {
+        let bytes = self.take(T::SIZE)?;
+        Ok(T::read(bytes, self.endianness)?)
+    }
+
+    fn read_len(&mut self) -> WireResult<usize> {
+        let len: u64 = self.read()?;
+        if len > usize::MAX as u64 {
+            return Err(EndiannessError::Overflow.into());
+        }
+        Ok(len as usize)
+    }
+
+    fn read_bytes(&mut self) -> WireResult<&'de [u8]> {
+        let len = self.read_len()?;
+        self.take(len)
+    }
+
+    fn read_str(&mut self) -> WireResult<&'de str> {
+        let bytes = self.read_bytes()?;
+        str::from_utf8(bytes).map_err(|_| WireError::Invalid("string"))
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => (
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+                visitor.$visit(self.read::<$ty>()?)
+            }
+        )*
+    );
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = WireError;
+
+    deserialize_number! {
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> WireResult<V::Value> {
+        Err(WireError::Unsupported("self-describing types"))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> WireResult<V::Value> {
+        Err(WireError::Unsupported("ignored fields"))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        match self.read::<u8>()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            _ => Err(WireError::Invalid("bool")),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        match ::core::char::from_u32(self.read()?) {
+            Some(c) => visitor.visit_char(c),
+            None => Err(WireError::Invalid("char")),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        visitor.visit_borrowed_str(self.read_str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        visitor.visit_borrowed_bytes(self.read_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        match self.read::<u8>()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(WireError::Invalid("option tag")),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> WireResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V)
+                                                   -> WireResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_seq(Elements { de: self, left: len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> WireResult<V::Value> {
+        visitor.visit_seq(Elements { de: self, left: len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V)
+                                                 -> WireResult<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_map(Elements { de: self, left: len })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str],
+                                           visitor: V) -> WireResult<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str],
+                                         visitor: V) -> WireResult<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> WireResult<V::Value> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+// The elements of a sequence, tuple or map whose length is already known.
+struct Elements<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+    left: usize,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for Elements<'a, 'de> {
+    type Error = WireError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> WireResult<Option<T::Value>> {
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.left)
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for Elements<'a, 'de> {
+    type Error = WireError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> WireResult<Option<K::Value>> {
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> WireResult<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.left)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = WireError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> WireResult<(V::Value, Self)> {
+        let index: u32 = self.read()?;
+        let value = seed.deserialize(IntoDeserializer::<WireError>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = WireError;
+
+    fn unit_variant(self) -> WireResult<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> WireResult<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> WireResult<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V)
+                                       -> WireResult<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+/// Encodes `value` into a new vector, in the format `from_bytes` decodes.
+///
+/// Requires the `serde` and `std` features.
+///
+/// ```rust
+/// # extern crate endianness;
+/// # extern crate serde;
+/// use endianness::*;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Header<'a> {
+///     id: u16,
+///     flags: Option<u8>,
+///     name: &'a str,
+/// }
+///
+/// # fn main() {
+/// let header = Header { id: 7, flags: None, name: "ok" };
+/// assert_eq!(vec![7, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, b'o', b'k'],
+///            to_bytes(&header, ByteOrder::LittleEndian).unwrap());
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T, endianness: ByteOrder) -> WireResult<Vec<u8>> {
+    let mut serializer = Serializer { output: Vec::new(), endianness };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+#[cfg(feature = "std")]
+struct Serializer {
+    output: Vec<u8>,
+    endianness: ByteOrder,
+}
+
+#[cfg(feature = "std")]
+impl Serializer {
+    fn put<T: EndianWrite>(&mut self, value: T) -> WireResult<()> {
+        let start = self.output.len();
+        self.output.resize(start + value.encoded_len(), 0);
+        Ok(value.write(&mut self.output[start..], self.endianness)?)
+    }
+
+    fn put_len(&mut self, len: usize) -> WireResult<()> {
+        if len as u64 as usize != len {
+            return Err(EndiannessError::Overflow.into());
+        }
+        self.put(len as u64)
+    }
+
+    // Sequences and maps may not know their length up front, so it is written as a
+    // placeholder and patched once the elements are in.
+    fn begin(&mut self) -> WireResult<Compound<'_>> {
+        let start = self.output.len();
+        self.put(0u64)?;
+        Ok(Compound { ser: self, start: Some(start), len: 0 })
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! serialize_number {
+    ($($method:ident: $ty:ty,)*) => (
+        $(
+            fn $method(self, v: $ty) -> WireResult<()> {
+                self.put(v)
+            }
+        )*
+    );
+}
+
+#[cfg(feature = "std")]
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = WireError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    serialize_number! {
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+    }
+
+    fn serialize_bool(self, v: bool) -> WireResult<()> {
+        self.put(v as u8)
+    }
+
+    fn serialize_char(self, v: char) -> WireResult<()> {
+        self.put(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> WireResult<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> WireResult<()> {
+        self.put_len(v.len())?;
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> WireResult<()> {
+        self.put(0u8)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> WireResult<()> {
+        self.put(1u8)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> WireResult<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> WireResult<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, index: u32, _variant: &'static str)
+                              -> WireResult<()> {
+        self.put(index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T)
+                                                       -> WireResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, index: u32,
+                                                        _variant: &'static str, value: &T)
+                                                        -> WireResult<()> {
+        self.put(index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> WireResult<Compound<'a>> {
+        self.begin()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> WireResult<Compound<'a>> {
+        Ok(Compound { ser: self, start: None, len: 0 })
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> WireResult<Compound<'a>> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, index: u32, _variant: &'static str,
+                               len: usize) -> WireResult<Compound<'a>> {
+        self.put(index)?;
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> WireResult<Compound<'a>> {
+        self.begin()
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> WireResult<Compound<'a>> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, index: u32, _variant: &'static str,
+                                len: usize) -> WireResult<Compound<'a>> {
+        self.put(index)?;
+        self.serialize_tuple(len)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+// The fields of a compound value. For sequences and maps, `start` is the position of the
+// length placeholder and `len` counts the elements.
+#[cfg(feature = "std")]
+struct Compound<'a> {
+    ser: &'a mut Serializer,
+    start: Option<usize>,
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Compound<'a> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> WireResult<()> {
+        self.len += 1;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> WireResult<()> {
+        if let Some(start) = self.start {
+            let len = self.len as u64;
+            len.write(&mut self.ser.output[start..], self.ser.endianness)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! serialize_compound {
+    ($($tr:ident::$method:ident($($name:ident: $ty:ty),*),)*) => (
+        $(
+            #[cfg(feature = "std")]
+            impl<'a> ser::$tr for Compound<'a> {
+                type Ok = ();
+                type Error = WireError;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, $($name: $ty,)* value: &T) -> WireResult<()> {
+                    $(let _ = $name;)*
+                    self.element(value)
+                }
+
+                fn end(self) -> WireResult<()> {
+                    Compound::end(self)
+                }
+            }
+        )*
+    );
+}
+
+serialize_compound! {
+    SerializeSeq::serialize_element(),
+    SerializeTuple::serialize_element(),
+    SerializeTupleStruct::serialize_field(),
+    SerializeTupleVariant::serialize_field(),
+    SerializeStruct::serialize_field(key: &'static str),
+    SerializeStructVariant::serialize_field(key: &'static str),
+}
+
+// A map entry counts once, on its key.
+#[cfg(feature = "std")]
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = WireError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> WireResult<()> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> WireResult<()> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> WireResult<()> {
+        Compound::end(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    use {ByteOrder, EndiannessError, WireError, from_bytes, to_bytes};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(u16),
+        Rect { w: u8, h: u8 },
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        id: u32,
+        delta: i16,
+        ratio: f32,
+        shape: Shape,
+        tags: Vec<u8>,
+        name: String,
+        pair: (bool, char),
+    }
+
+    #[test]
+    fn decode_struct() {
+        let be = [
+            0, 0, 1, 2, 255, 254, 63, 128, 0, 0,
+            0, 0, 0, 2, 1, 2,
+            0, 0, 0, 0, 0, 0, 0, 2, 9, 8,
+            0, 0, 0, 0, 0, 0, 0, 1, b'x',
+            1, 0, 0, 0, b'A',
+        ];
+        let expected = Record {
+            id: 0x0102,
+            delta: -2,
+            ratio: 1.0,
+            shape: Shape::Rect { w: 1, h: 2 },
+            tags: vec![9, 8],
+            name: "x".to_string(),
+            pair: (true, 'A'),
+        };
+        assert_eq!(expected, from_bytes(&be, ByteOrder::BigEndian).unwrap());
+
+        let le = [2, 1, 0, 0, 254, 255, 0, 0, 128, 63,
+                  1, 0, 0, 0, 7, 0,
+                  0, 0, 0, 0, 0, 0, 0, 0,
+                  0, 0, 0, 0, 0, 0, 0, 0,
+                  0, 66, 0, 0, 0];
+        let decoded: Record = from_bytes(&le, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(Shape::Circle(7), decoded.shape);
+        assert_eq!((false, 'B'), decoded.pair);
+        assert_eq!(Ok(Shape::Empty), from_bytes(&[0, 0, 0, 0], ByteOrder::LittleEndian));
+    }
+
+    #[test]
+    fn decode_collections() {
+        let data = [0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 1, 10, 0, 2, 0];
+        let map: BTreeMap<u16, Option<u8>> = from_bytes(&data, ByteOrder::BigEndian).unwrap();
+        assert_eq!(vec![(1, Some(10)), (2, None)], map.into_iter().collect::<Vec<_>>());
+        let wide: (u128, i128) = from_bytes(&[255; 32], ByteOrder::BigEndian).unwrap();
+        assert_eq!((u128::MAX, -1), wide);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Err(WireError::Endianness(EndiannessError::ShortSlice { expected: 4, found: 3 })),
+                   from_bytes::<u32>(&[0, 0, 0], ByteOrder::BigEndian));
+        assert_eq!(Err(WireError::TrailingBytes(1)), from_bytes::<u16>(&[0, 0, 0], ByteOrder::BigEndian));
+        assert_eq!(Err(WireError::Invalid("bool")), from_bytes::<bool>(&[2], ByteOrder::BigEndian));
+        assert_eq!(Err(WireError::Invalid("option tag")), from_bytes::<Option<u8>>(&[5, 0], ByteOrder::BigEndian));
+        assert_eq!(Err(WireError::Invalid("char")),
+                   from_bytes::<char>(&[0, 0, 0xd8, 0], ByteOrder::BigEndian));
+        let bad_utf8 = [1, 0, 0, 0, 0, 0, 0, 0, 0xff];
+        assert_eq!(Err(WireError::Invalid("string")), from_bytes::<String>(&bad_utf8, ByteOrder::LittleEndian));
+        match from_bytes::<Shape>(&[0, 0, 0, 9], ByteOrder::BigEndian) {
+            Err(WireError::Custom(ref msg)) => assert!(msg.contains("variant index")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!("The input has 1 trailing bytes.", WireError::TrailingBytes(1).to_string());
+    }
+
+    #[test]
+    fn encode() {
+        let record = Record {
+            id: 0x0102,
+            delta: -2,
+            ratio: 1.0,
+            shape: Shape::Rect { w: 1, h: 2 },
+            tags: vec![9, 8],
+            name: "x".to_string(),
+            pair: (true, 'A'),
+        };
+        let be = to_bytes(&record, ByteOrder::BigEndian).unwrap();
+        assert_eq!(vec![0, 0, 1, 2, 255, 254, 63, 128, 0, 0,
+                        0, 0, 0, 2, 1, 2,
+                        0, 0, 0, 0, 0, 0, 0, 2, 9, 8,
+                        0, 0, 0, 0, 0, 0, 0, 1, b'x',
+                        1, 0, 0, 0, b'A'], be);
+        for &order in &[ByteOrder::BigEndian, ByteOrder::LittleEndian] {
+            let bytes = to_bytes(&record, order).unwrap();
+            assert_eq!(record, from_bytes(&bytes, order).unwrap());
+        }
+        assert_eq!(vec![0, 0, 0, 1, 0, 7], to_bytes(&Shape::Circle(7), ByteOrder::BigEndian).unwrap());
+        assert_eq!(vec![0, 0, 0, 0], to_bytes(&Shape::Empty, ByteOrder::BigEndian).unwrap());
+    }
+
+    #[test]
+    fn encode_collections() {
+        let map: BTreeMap<u16, Option<u8>> = vec![(1, Some(10)), (2, None)].into_iter().collect();
+        assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 1, 10, 0, 2, 0],
+                   to_bytes(&map, ByteOrder::BigEndian).unwrap());
+        assert_eq!(vec![255; 32], to_bytes(&(u128::MAX, -1i128), ByteOrder::LittleEndian).unwrap());
+        assert_eq!(vec![2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i'], to_bytes("hi", ByteOrder::LittleEndian).unwrap());
+    }
+
+    // A sequence whose length is not known until it has been serialized.
+    struct Unsized(u8);
+
+    impl Serialize for Unsized {
+        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq((0..self.0).filter(|n| n % 2 == 0))
+        }
+    }
+
+    #[test]
+    fn encode_unknown_length() {
+        assert_eq!(vec![3, 0, 0, 0, 0, 0, 0, 0, 0, 2, 4],
+                   to_bytes(&Unsized(5), ByteOrder::LittleEndian).unwrap());
+    }
+}
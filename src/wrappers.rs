@@ -0,0 +1,421 @@
+//! Numbers stored as raw bytes in a fixed byte order.
+//!
+//! The byte order is part of the type: `U32<Be>` (or `U32Be`) and `U32<Le>` cannot be
+//! mixed up, and converting them needs no runtime branch on a `ByteOrder`.
+//!
+//! The wrapper types have the size of the number they hold and an alignment of one,
+//! so they can be laid over any part of a buffer with `cast_slice`, or used as fields
+//! of `#[repr(C)]` structs that describe a binary header. Values are converted to and
+//! from native byte order on access, which keeps such structs correct on any host.
+//!
+//! ```rust
+//! use endianness::*;
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy)]
+//! struct Header {
+//!     magic: U32Be,
+//!     version: U16Le,
+//!     flags: U16Le,
+//! }
+//!
+//! unsafe impl ByteView for Header {}
+//!
+//! let mut buf = [0x7f, b'E', b'L', b'F', 2, 0, 1, 0];
+//! {
+//!     let header = &mut cast_slice_mut::<Header>(&mut buf).unwrap()[0];
+//!     assert_eq!(0x7f454c46, header.magic.get());
+//!     assert_eq!(2, header.version.get());
+//!     header.flags.set(0x0102);
+//!     header.version.update(|v| v + 1);
+//! }
+//! assert_eq!([3, 0, 2, 1], buf[4..]);
+//! ```
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::{Pod, Zeroable};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+#[cfg(feature = "zerocopy")]
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use {ByteOrder, EndiannessError};
+use view::ByteView;
+
+/// A byte order known at compile time.
+///
+/// It is implemented by the zero-sized `Be` and `Le` markers, which select the byte
+/// order of the generic wrapper types such as `U32<Be>`.
+pub trait Endian: Copy + Default + fmt::Debug {
+    /// The byte order the marker stands for.
+    const ORDER: ByteOrder;
+}
+
+/// Marker for big-endian byte order.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Be;
+
+/// Marker for little-endian byte order.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Le;
+
+impl Endian for Be {
+    const ORDER: ByteOrder = ByteOrder::BigEndian;
+}
+
+impl Endian for Le {
+    const ORDER: ByteOrder = ByteOrder::LittleEndian;
+}
+
+macro_rules! wrapper {
+    ($($name:ident, $be:ident, $le:ident, $ty:ident, $size:expr, $desc:expr;)*) => (
+        $(
+            #[doc = concat!("A", $desc, " stored in the byte order selected by `E`.")]
+            #[derive(Copy, Clone, Default)]
+            #[cfg_attr(feature = "zerocopy",
+                       derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned))]
+            #[repr(transparent)]
+            pub struct $name<E: Endian> {
+                bytes: [u8; $size],
+                order: PhantomData<E>,
+            }
+
+            #[doc = concat!("A", $desc, " stored in big-endian.")]
+            pub type $be = $name<Be>;
+
+            #[doc = concat!("A", $desc, " stored in little-endian.")]
+            pub type $le = $name<Le>;
+
+            impl<E: Endian> $name<E> {
+                /// Stores the value given in native byte order.
+                pub const fn new(value: $ty) -> $name<E> {
+                    let bytes = match E::ORDER {
+                        ByteOrder::BigEndian => value.to_be_bytes(),
+                        ByteOrder::LittleEndian => value.to_le_bytes(),
+                    };
+                    $name::from_bytes(bytes)
+                }
+
+                /// Wraps bytes that are already in the stored byte order.
+                pub const fn from_bytes(bytes: [u8; $size]) -> $name<E> {
+                    $name {
+                        bytes,
+                        order: PhantomData,
+                    }
+                }
+
+                /// Returns the stored bytes.
+                pub const fn to_bytes(self) -> [u8; $size] {
+                    self.bytes
+                }
+
+                /// Returns the value in native byte order.
+                pub const fn get(self) -> $ty {
+                    match E::ORDER {
+                        ByteOrder::BigEndian => $ty::from_be_bytes(self.bytes),
+                        ByteOrder::LittleEndian => $ty::from_le_bytes(self.bytes),
+                    }
+                }
+
+                /// Replaces the value with one given in native byte order.
+                pub fn set(&mut self, value: $ty) {
+                    *self = $name::new(value);
+                }
+
+                /// Replaces the value with the result of `f` applied to it and returns
+                /// the new value.
+                pub fn update<F: FnOnce($ty) -> $ty>(&mut self, f: F) -> $ty {
+                    let value = f(self.get());
+                    self.set(value);
+                    value
+                }
+
+                /// Returns a reference to the stored bytes.
+                pub fn as_bytes(&self) -> &[u8; $size] {
+                    &self.bytes
+                }
+
+                /// Returns a mutable reference to the stored bytes.
+                pub fn as_mut_bytes(&mut self) -> &mut [u8; $size] {
+                    &mut self.bytes
+                }
+            }
+
+            impl<E: Endian> From<$ty> for $name<E> {
+                fn from(value: $ty) -> $name<E> {
+                    $name::new(value)
+                }
+            }
+
+            impl<E: Endian> From<$name<E>> for $ty {
+                fn from(value: $name<E>) -> $ty {
+                    value.get()
+                }
+            }
+
+            /// Copies bytes that are already in the stored byte order. The slice must be
+            /// exactly as long as the type.
+            impl<'a, E: Endian> TryFrom<&'a [u8]> for $name<E> {
+                type Error = EndiannessError;
+
+                fn try_from(bytes: &'a [u8]) -> Result<$name<E>, EndiannessError> {
+                    match <[u8; $size]>::try_from(bytes) {
+                        Ok(bytes) => Ok($name::from_bytes(bytes)),
+                        Err(_) => Err(EndiannessError::LengthMismatch { expected: $size, found: bytes.len() }),
+                    }
+                }
+            }
+
+            impl<E: Endian> fmt::Debug for $name<E> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "{}<{:?}>({:?})", stringify!($name), E::default(), self.get())
+                }
+            }
+
+            impl<E: Endian> PartialEq for $name<E> {
+                fn eq(&self, other: &$name<E>) -> bool {
+                    self.get() == other.get()
+                }
+            }
+
+            impl<E: Endian> PartialEq<$ty> for $name<E> {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.get() == *other
+                }
+            }
+
+            impl<E: Endian> Add<$ty> for $name<E> {
+                type Output = $name<E>;
+
+                fn add(self, rhs: $ty) -> $name<E> {
+                    $name::new(self.get() + rhs)
+                }
+            }
+
+            impl<E: Endian> Sub<$ty> for $name<E> {
+                type Output = $name<E>;
+
+                fn sub(self, rhs: $ty) -> $name<E> {
+                    $name::new(self.get() - rhs)
+                }
+            }
+
+            impl<E: Endian> AddAssign<$ty> for $name<E> {
+                fn add_assign(&mut self, rhs: $ty) {
+                    *self = *self + rhs;
+                }
+            }
+
+            impl<E: Endian> SubAssign<$ty> for $name<E> {
+                fn sub_assign(&mut self, rhs: $ty) {
+                    *self = *self - rhs;
+                }
+            }
+
+            // Safety: the wrappers are `repr(transparent)` over a byte array, so they have
+            // no padding, an alignment of one, and accept any bit pattern.
+            #[allow(unsafe_code)]
+            unsafe impl<E: Endian> ByteView for $name<E> {}
+
+            #[cfg(feature = "bytemuck")]
+            #[allow(unsafe_code)]
+            unsafe impl<E: Endian> Zeroable for $name<E> {}
+
+            #[cfg(feature = "bytemuck")]
+            #[allow(unsafe_code)]
+            unsafe impl<E: Endian + 'static> Pod for $name<E> {}
+        )*
+    );
+}
+
+// Integers are totally ordered, so their wrappers can be used as map keys. Floats only
+// get partial comparisons, like `f32` and `f64` themselves.
+macro_rules! wrapper_ord {
+    ($($name:ident),*) => (
+        $(
+            impl<E: Endian> Eq for $name<E> {}
+
+            impl<E: Endian> PartialOrd for $name<E> {
+                fn partial_cmp(&self, other: &$name<E>) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl<E: Endian> Ord for $name<E> {
+                fn cmp(&self, other: &$name<E>) -> Ordering {
+                    self.get().cmp(&other.get())
+                }
+            }
+
+            impl<E: Endian> Hash for $name<E> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.get().hash(state);
+                }
+            }
+        )*
+    );
+}
+
+wrapper! {
+    U16, U16Be, U16Le, u16, 2, "n unsigned 16-bit integer";
+    I16, I16Be, I16Le, i16, 2, " signed 16-bit integer";
+    U32, U32Be, U32Le, u32, 4, "n unsigned 32-bit integer";
+    I32, I32Be, I32Le, i32, 4, " signed 32-bit integer";
+    U64, U64Be, U64Le, u64, 8, "n unsigned 64-bit integer";
+    I64, I64Be, I64Le, i64, 8, " signed 64-bit integer";
+    U128, U128Be, U128Le, u128, 16, "n unsigned 128-bit integer";
+    I128, I128Be, I128Le, i128, 16, " signed 128-bit integer";
+    F32, F32Be, F32Le, f32, 4, " single-precision floating point number";
+    F64, F64Be, F64Le, f64, 8, " double-precision floating point number";
+}
+
+wrapper_ord!(U16, I16, U32, I32, U64, I64, U128, I128);
+
+macro_rules! wrapper_partial_ord {
+    ($($name:ident),*) => (
+        $(
+            impl<E: Endian> PartialOrd for $name<E> {
+                fn partial_cmp(&self, other: &$name<E>) -> Option<Ordering> {
+                    self.get().partial_cmp(&other.get())
+                }
+            }
+        )*
+    );
+}
+
+wrapper_partial_ord!(F32, F64);
+
+#[cfg(test)]
+mod tests {
+    use cast_slice_mut;
+    use super::*;
+
+    #[test]
+    fn layout() {
+        assert_eq!(4, size_of::<U32Be>());
+        assert_eq!(1, align_of::<U32Be>());
+        assert_eq!(16, size_of::<I128Le>());
+        assert_eq!(1, align_of::<F64Le>());
+    }
+
+    #[test]
+    fn byte_orders() {
+        assert_eq!([1, 2], U16Be::new(0x0102).to_bytes());
+        assert_eq!([2, 1], U16Le::new(0x0102).to_bytes());
+        assert_eq!([255, 255, 255, 254], I32Be::new(-2).to_bytes());
+        assert_eq!([194, 255, 0, 0], F32Be::new(-127.5).to_bytes());
+        assert_eq!(-127.5, F32Le::from_bytes([0, 0, 255, 194]).get());
+        assert_eq!(1 << 120, U128Be::from_bytes([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).get());
+    }
+
+    #[test]
+    fn get_set() {
+        let mut v = U64Le::default();
+        assert_eq!(0, v.get());
+        v.set(0x0102030405060708);
+        assert_eq!([8, 7, 6, 5, 4, 3, 2, 1], v.to_bytes());
+        assert_eq!(0x0102030405060708, u64::from(v));
+        assert_eq!(-3, I64Be::from(-3).get());
+    }
+
+    #[test]
+    fn in_place() {
+        let mut image = [0, 0, 0, 41, 1, 2];
+        {
+            let counters: &mut [U16Be] = cast_slice_mut(&mut image).unwrap();
+            assert_eq!(42, counters[1].update(|v| v + 1));
+            counters[2].as_mut_bytes()[0] = 0xff;
+            assert_eq!(&[0xff, 2], counters[2].as_bytes());
+        }
+        assert_eq!([0, 0, 0, 42, 0xff, 2], image);
+    }
+
+    #[test]
+    fn comparisons() {
+        // Little-endian bytes compare differently from the values they hold.
+        assert!(U16Le::new(0x0100) > U16Le::new(0x00ff));
+        assert!(I32Be::new(-1) < I32Be::new(0));
+        assert_eq!(U64Le::new(5), 5);
+        assert!(F32Be::new(-0.0) == F32Be::new(0.0));
+        assert!(F64Le::new(f64::NAN) != F64Le::new(f64::NAN));
+        assert!(F32Le::new(-1.0) < F32Le::new(0.5));
+
+        let mut keys: Vec<U32Le> = vec![U32Le::new(300), U32Le::new(2), U32Le::new(70000)];
+        keys.sort();
+        assert_eq!([2, 300, 70000], [keys[0].get(), keys[1].get(), keys[2].get()]);
+
+        let mut counts = ::std::collections::HashMap::new();
+        *counts.entry(U16Be::new(7)).or_insert(0) += 1;
+        *counts.entry(U16Be::new(7)).or_insert(0) += 1;
+        assert_eq!(Some(&2), counts.get(&U16Be::new(7)));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut counter = U32Be::new(255);
+        counter += 1;
+        assert_eq!([0, 0, 1, 0], counter.to_bytes());
+        counter -= 2;
+        assert_eq!(254, counter.get());
+        assert_eq!(I16Le::new(-3), I16Le::new(2) - 5);
+        assert_eq!(2.5, (F64Be::new(1.0) + 1.5).get());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck() {
+        use bytemuck;
+
+        let table = [0u8, 0, 1, 0, 0, 0, 0, 2];
+        let entries: &[U32Be] = bytemuck::cast_slice(&table);
+        assert_eq!([256, 2], [entries[0].get(), entries[1].get()]);
+        let value: &I64Le = bytemuck::from_bytes(&table);
+        assert_eq!(0x0200_0000_0001_0000, value.get());
+        assert_eq!(0.0, F64Le::zeroed().get());
+        assert_eq!(&[1, 2], bytemuck::bytes_of(&U16Be::new(0x0102)));
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy() {
+        let table = [0u8, 0, 1, 0, 0, 0, 0, 2];
+        let entries = <[U32Be]>::ref_from_bytes(&table).unwrap();
+        assert_eq!([256, 2], [entries[0].get(), entries[1].get()]);
+        assert_eq!(0x0200_0000_0001_0000, I64Le::read_from_bytes(&table).unwrap().get());
+        assert!(U16Le::read_from_bytes(&table[..3]).is_err());
+        assert_eq!(&[1, 2], IntoBytes::as_bytes(&U16Be::new(0x0102)));
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let v = [0, 0, 1, 2, 3, 4, 5];
+        assert_eq!(0x01020304, U32Be::try_from(&v[2..6]).unwrap().get());
+        assert_eq!(0x0201, U16Le::try_from(&v[2..4]).unwrap().get());
+        assert_eq!(EndiannessError::LengthMismatch { expected: 4, found: 3 },
+                   U32Be::try_from(&v[..3]).unwrap_err());
+        assert_eq!(EndiannessError::LengthMismatch { expected: 2, found: 7 },
+                   I16Be::try_from(&v[..]).unwrap_err());
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!("U16<Be>(258)", format!("{:?}", U16Be::new(258)));
+        assert_eq!("F64<Le>(1.5)", format!("{:?}", F64Le::new(1.5)));
+    }
+
+    // Code generic over the byte order works with either marker.
+    fn sum<E: Endian>(values: &[U32<E>]) -> u32 {
+        values.iter().map(|v| v.get()).sum()
+    }
+
+    #[test]
+    fn generic() {
+        assert_eq!(3, sum(&[U32::<Be>::new(1), U32::new(2)]));
+        assert_eq!(3, sum(&[U32::<Le>::new(1), U32::new(2)]));
+        assert_eq!(U32Be::new(7).to_bytes(), U32::<Be>::from(7u32).to_bytes());
+    }
+}
@@ -0,0 +1,163 @@
+//! A writer that keeps track of its position in a stream of bytes.
+
+use {ByteOrder, EndianWrite, EndiannessError, EndiannessResult};
+
+/// A cursor over a mutable stream of bytes which writes values one after another
+/// in a fixed byte order.
+///
+/// A failed write leaves both the stream and the position untouched.
+///
+/// ```rust
+/// use endianness::*;
+///
+/// let mut v = [0u8; 6];
+/// {
+///     let mut writer = ByteWriter::new(&mut v, ByteOrder::BigEndian);
+///     writer.write_u16(128).unwrap();
+///     writer.write_i16(-32768).unwrap();
+///     assert!(writer.write_u32(0).is_err());
+///     assert_eq!(4, writer.position());
+/// }
+/// assert_eq!([0, 128, 128, 0, 0, 0], v);
+/// ```
+#[derive(Debug)]
+pub struct ByteWriter<'a> {
+    data: &'a mut [u8],
+    position: usize,
+    endianness: ByteOrder,
+}
+
+macro_rules! writer_write {
+    ($($name:ident, $ty:ty, $doc:expr;)*) => (
+        $(
+            #[doc = $doc]
+            pub fn $name(&mut self, value: $ty) -> EndiannessResult<()> {
+                self.write(value)
+            }
+        )*
+    );
+}
+
+impl<'a> ByteWriter<'a> {
+    /// Creates a writer at the beginning of the stream.
+    pub fn new(data: &'a mut [u8], endianness: ByteOrder) -> ByteWriter<'a> {
+        ByteWriter {
+            data,
+            position: 0,
+            endianness,
+        }
+    }
+
+    /// Returns the number of bytes written so far, which is also the offset of the next write.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    /// Returns the bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.data[..self.position]
+    }
+
+    /// Consumes the writer and returns the bytes written.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.data[..self.position]
+    }
+
+    /// Returns the byte order values are written in.
+    pub fn endianness(&self) -> ByteOrder {
+        self.endianness
+    }
+
+    /// Changes the byte order for subsequent writes.
+    pub fn set_endianness(&mut self, endianness: ByteOrder) {
+        self.endianness = endianness;
+    }
+
+    /// Writes a value of any supported type and advances the writer.
+    pub fn write<T: EndianWrite>(&mut self, value: T) -> EndiannessResult<()> {
+        value.write(&mut self.data[self.position..], self.endianness)?;
+        self.position += value.encoded_len();
+        Ok(())
+    }
+
+    /// Copies the bytes as they are and advances the writer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> EndiannessResult<()> {
+        if self.remaining() < bytes.len() {
+            Err(EndiannessError::ShortSlice { expected: bytes.len(), found: self.remaining() })
+        } else {
+            self.data[self.position..self.position + bytes.len()].copy_from_slice(bytes);
+            self.position += bytes.len();
+            Ok(())
+        }
+    }
+
+    writer_write! {
+        write_u8, u8, "Writes unsigned 8-bit integer and advances the writer.";
+        write_i8, i8, "Writes signed 8-bit integer and advances the writer.";
+        write_u16, u16, "Writes unsigned 16-bit integer and advances the writer.";
+        write_i16, i16, "Writes signed 16-bit integer and advances the writer.";
+        write_u32, u32, "Writes unsigned 32-bit integer and advances the writer.";
+        write_i32, i32, "Writes signed 32-bit integer and advances the writer.";
+        write_u64, u64, "Writes unsigned 64-bit integer and advances the writer.";
+        write_i64, i64, "Writes signed 64-bit integer and advances the writer.";
+        write_u128, u128, "Writes unsigned 128-bit integer and advances the writer.";
+        write_i128, i128, "Writes signed 128-bit integer and advances the writer.";
+        write_f32, f32, "Writes a single-precision floating point number and advances the writer.";
+        write_f64, f64, "Writes a double-precision floating point number and advances the writer.";
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {ByteCursor, ByteOrder, ByteWriter, EndiannessError};
+
+    #[test]
+    fn writes_advance() {
+        let mut v = [0u8; 9];
+        {
+            let mut writer = ByteWriter::new(&mut v, ByteOrder::LittleEndian);
+            writer.write_u16(1).unwrap();
+            writer.write_u32(2).unwrap();
+            writer.write_u8(3).unwrap();
+            writer.write_bytes(&[4, 5]).unwrap();
+            assert_eq!(9, writer.position());
+            assert_eq!(0, writer.remaining());
+        }
+        assert_eq!([1, 0, 2, 0, 0, 0, 3, 4, 5], v);
+    }
+
+    #[test]
+    fn round_trip_with_cursor() {
+        let mut v = [0u8; 32];
+        let len = {
+            let mut writer = ByteWriter::new(&mut v, ByteOrder::BigEndian);
+            writer.write_i64(-2).unwrap();
+            writer.write_f32(-127.5).unwrap();
+            writer.write_u128(1 << 100).unwrap();
+            writer.into_written().len()
+        };
+        let mut cursor = ByteCursor::new(&v[..len], ByteOrder::BigEndian);
+        assert_eq!(-2, cursor.read_i64().unwrap());
+        assert_eq!(-127.5, cursor.read_f32().unwrap());
+        assert_eq!(1 << 100, cursor.read_u128().unwrap());
+        assert_eq!(0, cursor.remaining());
+    }
+
+    #[test]
+    fn failed_write_keeps_position() {
+        let mut v = [0u8; 6];
+        let mut writer = ByteWriter::new(&mut v, ByteOrder::BigEndian);
+        writer.write_u32(0xffffffff).unwrap();
+        assert_eq!(EndiannessError::ShortSlice { expected: 8, found: 2 },
+                   writer.write_f64(1.0).unwrap_err());
+        assert_eq!(EndiannessError::ShortSlice { expected: 3, found: 2 },
+                   writer.write_bytes(&[1, 2, 3]).unwrap_err());
+        assert_eq!(4, writer.position());
+        assert_eq!(&[0xff; 4], writer.written());
+    }
+}